@@ -0,0 +1,212 @@
+use crate::ipc_path::decode_path;
+use crate::{
+    app_lock, contains_path, normalize_path, path_starts_with, run_blocking, store, AppError,
+    AppState,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, GlobalWindowEvent, Manager, State, WindowEvent};
+
+const ACCESS_FILE: &str = "access-settings.json";
+const EXPIRED_EVENT: &str = "access-expired";
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AccessSettings {
+    /// Clears the allow-list after this many seconds without file access.
+    idle_timeout_secs: Option<u64>,
+    /// Clears the allow-list whenever the window loses focus.
+    paranoid: bool,
+}
+
+/// Files dropped from the allow-list by expiry stay in `revoked` so they can be
+/// re-granted with `revalidate_access` instead of a full rescan.
+#[derive(Default)]
+pub struct AccessState {
+    settings: AccessSettings,
+    last_access: Option<Instant>,
+    revoked: HashSet<PathBuf>,
+}
+
+fn idle_expired(access: &AccessState) -> bool {
+    match (access.settings.idle_timeout_secs, access.last_access) {
+        (Some(timeout), Some(last)) => last.elapsed() >= Duration::from_secs(timeout),
+        _ => false,
+    }
+}
+
+/// Moves every allowed file to the revoked set. Returns false if none were allowed.
+fn revoke_all(state: &AppState, access: &mut AccessState) -> bool {
+    let mut allowed = state.allowed_files.write();
+    access.last_access = None;
+    if allowed.is_empty() {
+        return false;
+    }
+    access.revoked.extend(allowed.drain());
+    true
+}
+
+pub fn resume(app: &AppHandle) -> Result<(), AppError> {
+    let settings: AccessSettings = store::load(app, ACCESS_FILE)?;
+    let state = app.state::<AppState>();
+    state.access.write().settings = settings;
+    Ok(())
+}
+
+/// Starts a fresh session after a scan replaced the allow-list.
+pub fn reset(state: &AppState) {
+    let mut access = state.access.write();
+    access.revoked.clear();
+    access.last_access = Some(Instant::now());
+}
+
+/// Expires the allow-list once the idle timeout has passed, otherwise records the
+/// access. Called from `ensure_allowed_path`.
+pub fn ensure_active(state: &AppState) -> Result<(), AppError> {
+    let mut access = state.access.write();
+    if idle_expired(&access) {
+        revoke_all(state, &mut access);
+        return Err(AppError::AccessExpired);
+    }
+    access.last_access = Some(Instant::now());
+    Ok(())
+}
+
+pub fn was_revoked(state: &AppState, path: &Path) -> bool {
+    contains_path(&state.access.read().revoked, path)
+}
+
+fn expire(app: &AppHandle, check: impl FnOnce(&AccessState) -> bool) {
+    let state = app.state::<AppState>();
+    let mut access = state.access.write();
+    if check(&access) && revoke_all(&state, &mut access) {
+        let _ = app.emit_all(EXPIRED_EVENT, ());
+    }
+}
+
+/// Expires the allow-list on idle timeout, so the window can drop its documents.
+pub fn spawn(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(CHECK_INTERVAL);
+        expire(&app, idle_expired);
+    });
+}
+
+/// In paranoid mode, losing window focus clears the allow-list.
+pub fn on_window_event(event: GlobalWindowEvent) {
+    if let WindowEvent::Focused(false) = event.event() {
+        expire(&event.window().app_handle(), |access| {
+            access.settings.paranoid
+        });
+    }
+}
+
+#[tauri::command]
+pub async fn get_access_settings(state: State<'_, AppState>) -> Result<AccessSettings, AppError> {
+    Ok(state.access.read().settings.clone())
+}
+
+#[tauri::command]
+pub async fn set_access_settings(
+    app: AppHandle,
+    settings: AccessSettings,
+) -> Result<AccessSettings, AppError> {
+    run_blocking(app, move |app, state| {
+        if settings.idle_timeout_secs == Some(0) {
+            return Err(AppError::InvalidInput(
+                "Idle timeout must be greater than zero".to_string(),
+            ));
+        }
+        store::save(&app, ACCESS_FILE, &settings)?;
+        let mut access = state.access.write();
+        access.settings = settings.clone();
+        access.last_access = Some(Instant::now());
+        Ok(settings)
+    })
+    .await
+}
+
+/// Re-grants access to a previously allowed file if it still exists under the
+/// scanned root, or was picked individually. Returns false, and forgets the file, when
+/// it no longer qualifies.
+#[tauri::command]
+pub async fn revalidate_access(app: AppHandle, path: String) -> Result<bool, AppError> {
+    run_blocking(app, move |_, state| {
+        app_lock::ensure_unlocked(&state)?;
+        let root = state.root_path.read().clone();
+        let path_buf = decode_path(&path);
+        let normalized = normalize_path(&path_buf).unwrap_or_else(|_| path_buf.clone());
+
+        let mut access = state.access.write();
+        let mut allowed = state.allowed_files.write();
+        if !contains_path(&allowed, &normalized) && !contains_path(&access.revoked, &normalized) {
+            return Err(AppError::PathNotAllowed);
+        }
+        let single = contains_path(&state.single_files.read(), &normalized);
+        let under_root = root.is_some_and(|root| path_starts_with(&normalized, &root));
+        if !(under_root || single) || !normalized.is_file() {
+            allowed.remove(&normalized);
+            access.revoked.remove(&normalized);
+            return Ok(false);
+        }
+        access.revoked.remove(&normalized);
+        allowed.insert(normalized);
+        access.last_access = Some(Instant::now());
+        Ok(true)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with(path: &Path, idle_timeout_secs: Option<u64>) -> AppState {
+        let state = AppState::default();
+        state.allowed_files.write().insert(path.to_path_buf());
+        state.access.write().settings.idle_timeout_secs = idle_timeout_secs;
+        reset(&state);
+        state
+    }
+
+    #[test]
+    fn access_is_recorded_until_the_timeout() {
+        let path = Path::new("/p/.env");
+        let state = state_with(path, Some(3600));
+        assert!(ensure_active(&state).is_ok());
+        assert!(state.allowed_files.read().contains(path));
+
+        let state = state_with(path, None);
+        assert!(ensure_active(&state).is_ok());
+        assert!(!idle_expired(&state.access.read()));
+    }
+
+    #[test]
+    fn idle_timeout_revokes_the_allow_list() {
+        let path = Path::new("/p/.env");
+        let state = state_with(path, Some(0));
+        assert!(matches!(
+            ensure_active(&state),
+            Err(AppError::AccessExpired)
+        ));
+        assert!(state.allowed_files.read().is_empty());
+        assert!(was_revoked(&state, path));
+
+        // Nothing is left to expire until access starts again.
+        assert!(ensure_active(&state).is_ok());
+        assert!(!revoke_all(&state, &mut state.access.write()));
+    }
+
+    #[test]
+    fn a_new_scan_forgets_revoked_files() {
+        let path = Path::new("/p/.env");
+        let state = state_with(path, Some(0));
+        let _ = ensure_active(&state);
+        reset(&state);
+        assert!(!was_revoked(&state, path));
+    }
+}
@@ -0,0 +1,280 @@
+use crate::crypto::{decrypt_with_key, derive_key, encrypt_with_key, random_salt};
+use crate::{run_blocking, store, value_index, AppError, AppState};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+const LOCK_FILE: &str = "app-lock.json";
+const LOCKED_EVENT: &str = "app-locked";
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const DEFAULT_AUTO_LOCK_SECS: u64 = 300;
+/// Encrypted with the derived key at setup; decrypting it proves the password.
+const VERIFIER: &[u8] = b"env-shelf-app-lock";
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LockConfig {
+    salt: String,
+    verifier: String,
+    auto_lock_secs: u64,
+}
+
+/// In-memory lock state. The derived key is only held while unlocked. It decrypts the
+/// verifier and nothing else: the lock is an access gate, not encryption at rest. Env
+/// files stay plaintext on disk for the tools that read them; what the lock protects is
+/// the app showing or holding their values, and locking drops the values it keeps in
+/// memory.
+#[derive(Default)]
+pub struct LockState {
+    config: Option<LockConfig>,
+    key: Option<[u8; 32]>,
+    last_activity: Option<Instant>,
+}
+
+impl LockState {
+    fn is_locked(&self) -> bool {
+        self.config.is_some() && self.key.is_none()
+    }
+
+    fn idle_expired(&self) -> bool {
+        match (&self.config, self.last_activity) {
+            (Some(config), Some(last)) if self.key.is_some() => {
+                config.auto_lock_secs > 0
+                    && last.elapsed() >= Duration::from_secs(config.auto_lock_secs)
+            }
+            _ => false,
+        }
+    }
+
+    fn clear_key(&mut self) {
+        if let Some(key) = self.key.as_mut() {
+            key.fill(0);
+        }
+        self.key = None;
+        self.last_activity = None;
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockStatus {
+    enabled: bool,
+    locked: bool,
+    auto_lock_secs: Option<u64>,
+}
+
+fn status_of(lock: &LockState) -> LockStatus {
+    LockStatus {
+        enabled: lock.config.is_some(),
+        locked: lock.is_locked(),
+        auto_lock_secs: lock.config.as_ref().map(|config| config.auto_lock_secs),
+    }
+}
+
+fn unlock_key(config: &LockConfig, password: &str) -> Result<[u8; 32], AppError> {
+    let salt = STANDARD
+        .decode(&config.salt)
+        .map_err(|e| AppError::CryptoError(e.to_string()))?;
+    let verifier = STANDARD
+        .decode(&config.verifier)
+        .map_err(|e| AppError::CryptoError(e.to_string()))?;
+    let key = derive_key(password, &salt)?;
+    match decrypt_with_key(&key, &verifier) {
+        Ok(plain) if plain == VERIFIER => Ok(key),
+        _ => Err(AppError::CryptoError("Wrong master password".to_string())),
+    }
+}
+
+/// Drops unlocked state: the derived key, the file contents kept for watching and the
+/// value index.
+fn lock_now(state: &AppState, lock: &mut LockState) {
+    lock.clear_key();
+    state.watched_files.write().clear();
+    value_index::clear(state);
+}
+
+/// Loads the persisted lock setting; a configured app starts locked.
+pub fn resume(app: &AppHandle) -> Result<(), AppError> {
+    let config: Option<LockConfig> = store::load(app, LOCK_FILE)?;
+    let state = app.state::<AppState>();
+    state.app_lock.write().config = config;
+    Ok(())
+}
+
+/// Fails with `Locked` while the app lock is engaged and records activity otherwise.
+/// Commands on one file reach it through `ensure_allowed_path`; commands reading every
+/// allowed file or a whole group call it on entry.
+pub fn ensure_unlocked(state: &AppState) -> Result<(), AppError> {
+    let mut lock = state.app_lock.write();
+    if lock.idle_expired() {
+        lock_now(state, &mut lock);
+    }
+    if lock.is_locked() {
+        return Err(AppError::Locked);
+    }
+    if lock.config.is_some() {
+        lock.last_activity = Some(Instant::now());
+    }
+    Ok(())
+}
+
+/// Locks the app after the idle timeout and tells the window to discard its values.
+pub fn spawn(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(CHECK_INTERVAL);
+        let state = app.state::<AppState>();
+        let mut lock = state.app_lock.write();
+        if lock.idle_expired() {
+            lock_now(&state, &mut lock);
+            let _ = app.emit_all(LOCKED_EVENT, status_of(&lock));
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_lock_status(state: State<'_, AppState>) -> Result<LockStatus, AppError> {
+    Ok(status_of(&state.app_lock.read()))
+}
+
+/// Turns on the app lock, or changes its password and timeout, which requires the
+/// current password. `auto_lock_secs` of 0 disables the idle timeout.
+#[tauri::command]
+pub async fn enable_app_lock(
+    app: AppHandle,
+    password: String,
+    current_password: Option<String>,
+    auto_lock_secs: Option<u64>,
+) -> Result<LockStatus, AppError> {
+    run_blocking(app, move |app, state| {
+        if password.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Master password must not be empty".to_string(),
+            ));
+        }
+        let mut lock = state.app_lock.write();
+        if lock.is_locked() {
+            return Err(AppError::Locked);
+        }
+        if let Some(config) = &lock.config {
+            unlock_key(config, current_password.as_deref().unwrap_or(""))?;
+        }
+        let salt = random_salt();
+        let key = derive_key(&password, &salt)?;
+        let config = LockConfig {
+            salt: STANDARD.encode(salt),
+            verifier: STANDARD.encode(encrypt_with_key(&key, VERIFIER)?),
+            auto_lock_secs: auto_lock_secs.unwrap_or(DEFAULT_AUTO_LOCK_SECS),
+        };
+        store::save(&app, LOCK_FILE, &Some(config.clone()))?;
+        lock.config = Some(config);
+        lock.key = Some(key);
+        lock.last_activity = Some(Instant::now());
+        Ok(status_of(&lock))
+    })
+    .await
+}
+
+/// Removes the app lock; requires the current master password.
+#[tauri::command]
+pub async fn disable_app_lock(app: AppHandle, password: String) -> Result<LockStatus, AppError> {
+    run_blocking(app, move |app, state| {
+        let mut lock = state.app_lock.write();
+        let Some(config) = &lock.config else {
+            return Ok(status_of(&lock));
+        };
+        unlock_key(config, &password)?;
+        store::save::<Option<LockConfig>>(&app, LOCK_FILE, &None)?;
+        lock.clear_key();
+        lock.config = None;
+        Ok(status_of(&lock))
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn unlock_app(app: AppHandle, password: String) -> Result<LockStatus, AppError> {
+    run_blocking(app, move |_, state| {
+        let mut lock = state.app_lock.write();
+        let Some(config) = &lock.config else {
+            return Ok(status_of(&lock));
+        };
+        let key = unlock_key(config, &password)?;
+        lock.key = Some(key);
+        lock.last_activity = Some(Instant::now());
+        let status = status_of(&lock);
+        drop(lock);
+        value_index::rebuild(&state);
+        Ok(status)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn lock_app(state: State<'_, AppState>) -> Result<LockStatus, AppError> {
+    let mut lock = state.app_lock.write();
+    lock_now(&state, &mut lock);
+    Ok(status_of(&lock))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_for(password: &str, auto_lock_secs: u64) -> (LockConfig, [u8; 32]) {
+        let salt = random_salt();
+        let key = derive_key(password, &salt).unwrap();
+        let config = LockConfig {
+            salt: STANDARD.encode(salt),
+            verifier: STANDARD.encode(encrypt_with_key(&key, VERIFIER).unwrap()),
+            auto_lock_secs,
+        };
+        (config, key)
+    }
+
+    fn unlocked_state(auto_lock_secs: u64, idle: Duration) -> AppState {
+        let (config, key) = config_for("master", auto_lock_secs);
+        let state = AppState::default();
+        {
+            let mut lock = state.app_lock.write();
+            lock.config = Some(config);
+            lock.key = Some(key);
+            lock.last_activity = Instant::now().checked_sub(idle);
+        }
+        state
+    }
+
+    #[test]
+    fn only_the_master_password_unlocks() {
+        let (config, key) = config_for("master", 0);
+        assert_eq!(unlock_key(&config, "master").unwrap(), key);
+        assert!(matches!(
+            unlock_key(&config, "guess"),
+            Err(AppError::CryptoError(_))
+        ));
+    }
+
+    #[test]
+    fn a_configured_lock_without_a_key_refuses_commands() {
+        let state = AppState::default();
+        assert!(ensure_unlocked(&state).is_ok());
+        state.app_lock.write().config = Some(config_for("master", 0).0);
+        assert!(matches!(ensure_unlocked(&state), Err(AppError::Locked)));
+    }
+
+    #[test]
+    fn idle_timeout_locks_and_clears_the_key() {
+        let state = unlocked_state(60, Duration::from_secs(120));
+        assert!(matches!(ensure_unlocked(&state), Err(AppError::Locked)));
+        assert!(state.app_lock.read().key.is_none());
+    }
+
+    #[test]
+    fn activity_within_the_timeout_keeps_the_app_unlocked() {
+        let state = unlocked_state(60, Duration::from_secs(1));
+        assert!(ensure_unlocked(&state).is_ok());
+        let never = unlocked_state(0, Duration::from_secs(3600));
+        assert!(ensure_unlocked(&never).is_ok());
+    }
+}
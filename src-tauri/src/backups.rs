@@ -0,0 +1,657 @@
+use crate::definitions::fingerprint;
+use crate::interop::decoded_values;
+use crate::io_error::{IoCode, PathContext};
+use crate::ipc_path::decode_path;
+use crate::{
+    app_lock, diff_key_values, ensure_allowed_path, hash_content, hash_path, modified_millis,
+    normalize_path, parse_env_lines, read_env_text, run_blocking, store, unified_diff, AppError,
+    AppState, KeyChange, WritePreview,
+};
+use chrono::{Local, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+const BACKUPS_DIR: &str = "backups";
+const BLOBS_DIR: &str = "blobs";
+const BLOB_EXTENSION: &str = "zst";
+/// Copies saved beside the file before the backup store existed:
+/// `.{file name}.backup-{local time}`.
+const FOLDER_COPY_MARKER: &str = ".backup-";
+const FOLDER_COPY_TIME_FORMAT: &str = "%Y%m%d%H%M%S";
+/// Versions kept per file. Older ones are dropped, and their blobs deleted once no
+/// version of any file refers to them.
+const MAX_VERSIONS: usize = 50;
+const INDEX_FILE: &str = "backup-index.json";
+const COMPRESSION_LEVEL: i32 = 3;
+const SETTINGS_FILE: &str = "backup-settings.json";
+const MIN_INTERVAL_SECS: u64 = 60;
+const STOP_POLL: Duration = Duration::from_secs(1);
+
+/// Set once on startup so writes can back up the file they replace.
+static APP: OnceLock<AppHandle> = OnceLock::new();
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupEntry {
+    id: String,
+    created_at: i64,
+    content_hash: String,
+    size: u64,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupSettings {
+    auto_backup_interval_secs: Option<u64>,
+}
+
+/// Backup versions per file id, pointing into the blob store.
+type BackupIndex = BTreeMap<String, Vec<BackupEntry>>;
+
+/// Handle for the running auto-backup thread; dropping the flag stops it.
+pub struct AutoBackup {
+    stop: Arc<AtomicBool>,
+}
+
+fn file_id(path: &Path) -> String {
+    let canonical = normalize_path(path).unwrap_or_else(|_| path.to_path_buf());
+    hash_path(&canonical)
+}
+
+fn backups_root(app: &AppHandle) -> Result<PathBuf, AppError> {
+    let dir = store::app_data_dir(app)?.join(BACKUPS_DIR);
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn blob_path(app: &AppHandle, content_hash: &str) -> Result<PathBuf, AppError> {
+    let dir = backups_root(app)?.join(BLOBS_DIR);
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join(format!("{}.{}", content_hash, BLOB_EXTENSION)))
+}
+
+/// Stores compressed content under its hash; identical content is stored once no
+/// matter how many files or versions share it. Called under the store lock, like
+/// `remove_blobs`, so a blob is never deleted between being stored and indexed.
+fn store_blob(app: &AppHandle, content_hash: &str, contents: &[u8]) -> Result<(), AppError> {
+    let target = blob_path(app, content_hash)?;
+    if target.is_file() {
+        return Ok(());
+    }
+    let compressed = zstd::encode_all(contents, COMPRESSION_LEVEL)?;
+    let temp_path = target.with_extension("tmp");
+    fs::write(&temp_path, compressed)?;
+    fs::rename(&temp_path, &target)?;
+    Ok(())
+}
+
+fn remove_blobs(app: &AppHandle, hashes: &[String]) -> Result<(), AppError> {
+    for content_hash in hashes {
+        let target = blob_path(app, content_hash)?;
+        match fs::remove_file(&target) {
+            Err(error) if error.kind() != ErrorKind::NotFound => {
+                return Err(error).at(&target);
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Adds `entry` to a file's versions, oldest first, and drops the oldest beyond
+/// `MAX_VERSIONS`. Returns the blobs no version refers to any more.
+fn add_version(index: &mut BackupIndex, file_id: &str, entry: BackupEntry) -> Vec<String> {
+    let versions = index.entry(file_id.to_string()).or_default();
+    if versions.iter().any(|version| version.id == entry.id) {
+        return Vec::new();
+    }
+    versions.push(entry);
+    versions.sort_by_key(|version| version.created_at);
+    let excess = versions.len().saturating_sub(MAX_VERSIONS);
+    let dropped: Vec<BackupEntry> = versions.drain(..excess).collect();
+    let mut unreferenced: Vec<String> = dropped
+        .into_iter()
+        .map(|version| version.content_hash)
+        .filter(|content_hash| {
+            !index
+                .values()
+                .flatten()
+                .any(|version| version.content_hash == *content_hash)
+        })
+        .collect();
+    unreferenced.sort();
+    unreferenced.dedup();
+    unreferenced
+}
+
+/// Backup ids are `<created_at millis>-<content hash>`.
+fn parse_backup_id(id: &str) -> Option<(i64, String)> {
+    let (created_at, hash) = id.split_once('-')?;
+    Some((created_at.parse().ok()?, hash.to_string()))
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupUsage {
+    file_count: usize,
+    version_count: usize,
+    /// Compressed size of the blob store on disk.
+    stored_bytes: u64,
+}
+
+pub fn usage(app: &AppHandle) -> Result<BackupUsage, AppError> {
+    let index: BackupIndex = store::load(app, INDEX_FILE)?;
+    let blobs = backups_root(app)?.join(BLOBS_DIR);
+    let stored_bytes = match fs::read_dir(&blobs) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok()?.metadata().ok())
+            .map(|metadata| metadata.len())
+            .sum(),
+        Err(_) => 0,
+    };
+    Ok(BackupUsage {
+        file_count: index.len(),
+        version_count: index.values().map(Vec::len).sum(),
+        stored_bytes,
+    })
+}
+
+pub fn list_entries(app: &AppHandle, path: &Path) -> Result<Vec<BackupEntry>, AppError> {
+    let index: BackupIndex = store::load(app, INDEX_FILE)?;
+    let mut entries = index.get(&file_id(path)).cloned().unwrap_or_default();
+    entries.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(entries)
+}
+
+/// Stores a snapshot of `contents` unless the latest backup already has the same hash.
+pub fn snapshot(
+    app: &AppHandle,
+    path: &Path,
+    contents: &[u8],
+) -> Result<Option<BackupEntry>, AppError> {
+    let content_hash = hash_content(contents);
+    let created_at = Utc::now().timestamp_millis();
+    let entry = BackupEntry {
+        id: format!("{}-{}", created_at, content_hash),
+        created_at,
+        content_hash,
+        size: contents.len() as u64,
+    };
+    let state = app.state::<AppState>();
+    let file_id = file_id(path);
+    let add = |index: &mut BackupIndex| -> Result<Option<BackupEntry>, AppError> {
+        let latest = index
+            .get(&file_id)
+            .and_then(|versions| versions.iter().max_by_key(|version| version.created_at));
+        if latest
+            .map(|version| version.content_hash == entry.content_hash)
+            .unwrap_or(false)
+        {
+            return Ok(None);
+        }
+        store_blob(app, &entry.content_hash, contents)?;
+        let unreferenced = add_version(index, &file_id, entry.clone());
+        remove_blobs(app, &unreferenced)?;
+        Ok(Some(entry))
+    };
+    store::update(app, &state, INDEX_FILE, add)?
+}
+
+/// Stores the content `path` has now, before a write replaces it.
+pub fn snapshot_before_write(path: &Path) -> Result<(), AppError> {
+    let app = APP
+        .get()
+        .ok_or_else(|| AppError::io(IoCode::Unavailable, "Backups are unavailable"))?;
+    let contents = fs::read(path).at(path)?;
+    snapshot(app, path, &contents)?;
+    Ok(())
+}
+
+pub fn read_backup(app: &AppHandle, path: &Path, id: &str) -> Result<Vec<u8>, AppError> {
+    let Some((_, content_hash)) = parse_backup_id(id) else {
+        return Err(AppError::InvalidInput(format!("Invalid backup id: {}", id)));
+    };
+    let known = list_entries(app, path)?.iter().any(|entry| entry.id == id);
+    let target = blob_path(app, &content_hash)?;
+    if !known || !target.is_file() {
+        return Err(AppError::InvalidInput(format!("Backup not found: {}", id)));
+    }
+    Ok(zstd::decode_all(fs::File::open(target)?)?)
+}
+
+/// When a backup copy beside a file was taken, from its name, if it is one of `name`'s.
+fn folder_copy_time(copy_name: &str, name: &str) -> Option<i64> {
+    let timestamp = copy_name
+        .strip_prefix('.')?
+        .strip_prefix(name)?
+        .strip_prefix(FOLDER_COPY_MARKER)?;
+    let time = NaiveDateTime::parse_from_str(timestamp, FOLDER_COPY_TIME_FORMAT).ok()?;
+    Some(
+        Local
+            .from_local_datetime(&time)
+            .earliest()?
+            .timestamp_millis(),
+    )
+}
+
+/// Moves the copies saving with a backup used to leave beside each file into the
+/// backup store, where the backup list, diffs and key history can see them.
+pub fn migrate_folder_copies(app: &AppHandle, paths: &[PathBuf]) -> Result<usize, AppError> {
+    let mut by_folder: BTreeMap<&Path, Vec<&Path>> = BTreeMap::new();
+    for path in paths {
+        if let Some(folder) = path.parent() {
+            by_folder.entry(folder).or_default().push(path);
+        }
+    }
+    let state = app.state::<AppState>();
+    let mut migrated = 0;
+    for (folder, files) in by_folder {
+        let Ok(entries) = fs::read_dir(folder) else {
+            continue;
+        };
+        let copies: Vec<PathBuf> = entries
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|copy| copy.is_file())
+            .collect();
+        for file in files {
+            let Some(name) = file.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let file_id = file_id(file);
+            for copy in &copies {
+                let Some(created_at) = copy
+                    .file_name()
+                    .and_then(|copy_name| copy_name.to_str())
+                    .and_then(|copy_name| folder_copy_time(copy_name, name))
+                else {
+                    continue;
+                };
+                let contents = fs::read(copy).at(copy)?;
+                let content_hash = hash_content(&contents);
+                let entry = BackupEntry {
+                    id: format!("{}-{}", created_at, content_hash),
+                    created_at,
+                    content_hash,
+                    size: contents.len() as u64,
+                };
+                store::update(app, &state, INDEX_FILE, |index: &mut BackupIndex| {
+                    store_blob(app, &entry.content_hash, &contents)?;
+                    let unreferenced = add_version(index, &file_id, entry);
+                    remove_blobs(app, &unreferenced)
+                })??;
+                fs::remove_file(copy).at(copy)?;
+                migrated += 1;
+            }
+        }
+    }
+    Ok(migrated)
+}
+
+fn snapshot_all(app: &AppHandle) -> Result<usize, AppError> {
+    let state = app.state::<AppState>();
+    let paths: Vec<PathBuf> = state.allowed_files.read().iter().cloned().collect();
+    let mut created = 0;
+    for path in paths {
+        let Ok(contents) = fs::read(&path) else {
+            continue;
+        };
+        if snapshot(app, &path, &contents)?.is_some() {
+            created += 1;
+        }
+    }
+    Ok(created)
+}
+
+fn spawn_auto_backup(app: AppHandle, interval: Duration) -> AutoBackup {
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = stop.clone();
+    thread::spawn(move || {
+        let mut last_run = Instant::now();
+        while !thread_stop.load(Ordering::SeqCst) {
+            thread::sleep(STOP_POLL);
+            if last_run.elapsed() >= interval {
+                let _ = snapshot_all(&app);
+                last_run = Instant::now();
+            }
+        }
+    });
+    AutoBackup { stop }
+}
+
+fn replace_auto_backup(state: &AppState, next: Option<AutoBackup>) {
+    let mut guard = state.auto_backup.write();
+    if let Some(current) = guard.take() {
+        current.stop.store(true, Ordering::SeqCst);
+    }
+    *guard = next;
+}
+
+/// Restarts the auto-backup thread from persisted settings; called on app setup.
+pub fn resume(app: &AppHandle) -> Result<(), AppError> {
+    let _ = APP.set(app.clone());
+    let settings: BackupSettings = store::load(app, SETTINGS_FILE)?;
+    if let Some(secs) = settings.auto_backup_interval_secs {
+        let task = spawn_auto_backup(app.clone(), Duration::from_secs(secs));
+        replace_auto_backup(&app.state::<AppState>(), Some(task));
+    }
+    Ok(())
+}
+
+/// Enables periodic snapshots of every allowed file whose content changed, or
+/// disables them when `interval_secs` is `None`.
+#[tauri::command]
+pub async fn configure_auto_backup(
+    app: AppHandle,
+    interval_secs: Option<u64>,
+) -> Result<BackupSettings, AppError> {
+    run_blocking(app, move |app, state| {
+        let interval_secs = interval_secs.map(|secs| secs.max(MIN_INTERVAL_SECS));
+        let task =
+            interval_secs.map(|secs| spawn_auto_backup(app.clone(), Duration::from_secs(secs)));
+        replace_auto_backup(&state, task);
+
+        let settings = BackupSettings {
+            auto_backup_interval_secs: interval_secs,
+        };
+        store::save(&app, SETTINGS_FILE, &settings)?;
+        Ok(settings)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn get_backup_settings(app: AppHandle) -> Result<BackupSettings, AppError> {
+    run_blocking(app, move |app, _| store::load(&app, SETTINGS_FILE)).await
+}
+
+#[tauri::command]
+pub async fn list_backups(app: AppHandle, path: String) -> Result<Vec<BackupEntry>, AppError> {
+    run_blocking(app, move |app, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        list_entries(&app, &path_buf)
+    })
+    .await
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupDiff {
+    backup_id: String,
+    changes: Vec<KeyChange>,
+    preview: WritePreview,
+}
+
+/// Diffs a stored backup (before) against the current file on disk (after).
+#[tauri::command]
+pub async fn diff_against_backup(
+    app: AppHandle,
+    path: String,
+    backup_id: String,
+) -> Result<BackupDiff, AppError> {
+    run_blocking(app, move |app, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+
+        let backup =
+            String::from_utf8_lossy(&read_backup(&app, &path_buf, &backup_id)?).to_string();
+        let current = read_env_text(&state, &path_buf)?;
+
+        Ok(BackupDiff {
+            changes: diff_key_values(&parse_env_lines(&backup), &parse_env_lines(&current)),
+            preview: unified_diff(&path, &backup, &current),
+            backup_id,
+        })
+    })
+    .await
+}
+
+/// One value a key held, as seen across consecutive snapshots.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyVersion {
+    /// The change to this value happened between the previous version's `last_seen_at`
+    /// and this.
+    first_seen_at: i64,
+    last_seen_at: i64,
+    /// Backup that first shows the value; `None` when only the current file does.
+    backup_id: Option<String>,
+    /// False when the key was removed.
+    present: bool,
+    /// Left out when masked.
+    value: Option<String>,
+    value_fingerprint: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyHistory {
+    key: String,
+    /// Oldest first; the last one is the value in the file now.
+    versions: Vec<KeyVersion>,
+}
+
+fn key_value(contents: &str, key: &str) -> Option<String> {
+    decoded_values(&parse_env_lines(contents))
+        .into_iter()
+        .rev()
+        .find(|(line_key, _)| line_key == key)
+        .map(|(_, value)| value)
+}
+
+/// Collapses `(seen_at, backup id, value)` snapshots, oldest first, into the runs
+/// of one value.
+fn key_versions(
+    snapshots: Vec<(i64, Option<String>, Option<String>)>,
+    mask: bool,
+) -> Vec<KeyVersion> {
+    let mut versions: Vec<KeyVersion> = Vec::new();
+    let mut previous: Option<Option<String>> = None;
+    for (seen_at, backup_id, value) in snapshots {
+        if let (Some(last), Some(previous)) = (versions.last_mut(), &previous) {
+            if *previous == value {
+                last.last_seen_at = last.last_seen_at.max(seen_at);
+                continue;
+            }
+        }
+        // A key that never existed before its first appearance is not a version.
+        if versions.is_empty() && value.is_none() {
+            previous = Some(None);
+            continue;
+        }
+        versions.push(KeyVersion {
+            first_seen_at: seen_at,
+            last_seen_at: seen_at,
+            backup_id,
+            present: value.is_some(),
+            value: value.clone().filter(|_| !mask),
+            value_fingerprint: value.as_deref().map(fingerprint),
+        });
+        previous = Some(value);
+    }
+    versions
+}
+
+/// Rebuilds when a key's value changed from the file's backups plus its current
+/// content, e.g. to see when a token was rotated. Values are masked unless `mask` is
+/// false; fingerprints still tell them apart.
+#[tauri::command]
+pub async fn key_history(
+    app: AppHandle,
+    path: String,
+    key: String,
+    mask: Option<bool>,
+) -> Result<KeyHistory, AppError> {
+    run_blocking(app, move |app, state| {
+        app_lock::ensure_unlocked(&state)?;
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        let mask = mask.unwrap_or(true);
+
+        let mut snapshots: Vec<(i64, Option<String>, Option<String>)> = Vec::new();
+        for entry in list_entries(&app, &path_buf)?.into_iter().rev() {
+            let Ok(contents) = read_backup(&app, &path_buf, &entry.id) else {
+                continue;
+            };
+            let value = key_value(&String::from_utf8_lossy(&contents), &key);
+            snapshots.push((entry.created_at, Some(entry.id), value));
+        }
+        let current = read_env_text(&state, &path_buf)?;
+        let modified_at = modified_millis(&fs::metadata(&path_buf)?);
+        snapshots.push((modified_at, None, key_value(&current, &key)));
+
+        let versions = key_versions(snapshots, mask);
+
+        Ok(KeyHistory { key, versions })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(created_at: i64, content_hash: &str) -> BackupEntry {
+        BackupEntry {
+            id: format!("{}-{}", created_at, content_hash),
+            created_at,
+            content_hash: content_hash.to_string(),
+            size: 1,
+        }
+    }
+
+    #[test]
+    fn versions_stay_in_time_order_without_duplicates() {
+        let mut index = BackupIndex::new();
+        add_version(&mut index, "file", entry(20, "b"));
+        add_version(&mut index, "file", entry(10, "a"));
+        add_version(&mut index, "file", entry(20, "b"));
+        let ids: Vec<&str> = index["file"].iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, ["10-a", "20-b"]);
+    }
+
+    #[test]
+    fn retention_drops_the_oldest_versions() {
+        let mut index = BackupIndex::new();
+        for created_at in 0..MAX_VERSIONS as i64 {
+            assert!(add_version(&mut index, "file", entry(created_at, "shared")).is_empty());
+        }
+        let unreferenced = add_version(&mut index, "file", entry(-1, "oldest"));
+        assert_eq!(unreferenced, ["oldest"]);
+        assert_eq!(index["file"].len(), MAX_VERSIONS);
+        assert_eq!(index["file"][0].created_at, 0);
+    }
+
+    #[test]
+    fn blobs_still_used_by_another_file_are_kept() {
+        let mut index = BackupIndex::new();
+        add_version(&mut index, "other", entry(0, "shared"));
+        for created_at in 0..MAX_VERSIONS as i64 {
+            add_version(
+                &mut index,
+                "file",
+                entry(created_at, &format!("h{}", created_at)),
+            );
+        }
+        assert_eq!(
+            add_version(&mut index, "file", entry(-1, "shared")),
+            Vec::<String>::new()
+        );
+        assert_eq!(add_version(&mut index, "file", entry(100, "new")), ["h0"]);
+    }
+
+    #[test]
+    fn reads_the_time_of_copies_saved_beside_a_file() {
+        let expected = Local
+            .with_ymd_and_hms(2024, 3, 1, 12, 30, 5)
+            .unwrap()
+            .timestamp_millis();
+        assert_eq!(
+            folder_copy_time("..env.local.backup-20240301123005", ".env.local"),
+            Some(expected)
+        );
+        assert_eq!(
+            folder_copy_time("..env.local.backup-20240301123005", ".env"),
+            None
+        );
+        assert_eq!(folder_copy_time("..env.backup-soon", ".env"), None);
+        assert_eq!(folder_copy_time(".env.backup-20240301123005", ".env"), None);
+    }
+
+    fn seen(
+        seen_at: i64,
+        backup_id: Option<&str>,
+        value: Option<&str>,
+    ) -> (i64, Option<String>, Option<String>) {
+        (
+            seen_at,
+            backup_id.map(str::to_string),
+            value.map(str::to_string),
+        )
+    }
+
+    #[test]
+    fn key_versions_follow_value_changes() {
+        let versions = key_versions(
+            vec![
+                seen(1, Some("1-a"), None),
+                seen(2, Some("2-b"), Some("old")),
+                seen(3, Some("3-c"), Some("old")),
+                seen(4, Some("4-d"), None),
+                seen(5, None, Some("new")),
+            ],
+            false,
+        );
+        let runs: Vec<(i64, i64, Option<&str>, bool, Option<&str>)> = versions
+            .iter()
+            .map(|version| {
+                (
+                    version.first_seen_at,
+                    version.last_seen_at,
+                    version.backup_id.as_deref(),
+                    version.present,
+                    version.value.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            runs,
+            [
+                (2, 3, Some("2-b"), true, Some("old")),
+                (4, 4, Some("4-d"), false, None),
+                (5, 5, None, true, Some("new")),
+            ]
+        );
+    }
+
+    #[test]
+    fn masked_key_versions_keep_fingerprints() {
+        let versions = key_versions(
+            vec![
+                seen(1, Some("1-a"), Some("one")),
+                seen(2, None, Some("two")),
+            ],
+            true,
+        );
+        assert!(versions.iter().all(|version| version.value.is_none()));
+        assert_ne!(versions[0].value_fingerprint, versions[1].value_fingerprint);
+        assert!(versions[0].value_fingerprint.is_some());
+    }
+
+    #[test]
+    fn key_value_reads_the_last_definition_decoded() {
+        assert_eq!(
+            key_value("A=1\nA=\"two words\"\n", "A").as_deref(),
+            Some("two words")
+        );
+        assert_eq!(key_value("B=1\n", "A"), None);
+    }
+}
@@ -0,0 +1,192 @@
+use crate::io_error::IoCode;
+use crate::ipc_path::{decode_path, encode_path};
+use crate::{
+    check_write_conflict, ensure_allowed_path, journal, logging, provenance, run_blocking,
+    stage_temp_file, watcher, write_backup_copy, write_target, AppError, AppState, WriteOptions,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileWrite {
+    path: String,
+    content: String,
+    options: WriteOptions,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchResult {
+    written: Vec<String>,
+}
+
+struct Staged {
+    path: PathBuf,
+    temp_path: PathBuf,
+    original: Vec<u8>,
+}
+
+fn discard(staged: &[Staged]) {
+    for entry in staged {
+        let _ = fs::remove_file(&entry.temp_path);
+    }
+}
+
+/// Puts the original contents back on files that were already replaced.
+fn roll_back(replaced: &[Staged]) -> Vec<String> {
+    let mut failed = Vec::new();
+    for entry in replaced {
+        let restored = String::from_utf8(entry.original.clone())
+            .map_err(|_| AppError::NotText)
+            .and_then(|original| stage_temp_file(&entry.path, &original))
+            .and_then(|temp_path| fs::rename(temp_path, &entry.path).map_err(AppError::from));
+        if restored.is_err() {
+            failed.push(encode_path(&entry.path));
+        }
+    }
+    failed
+}
+
+/// Writes several files as one operation: every target is checked and staged to a
+/// temp file first, then all are renamed into place. If a rename fails, files already
+/// replaced are restored to their previous content.
+#[tauri::command]
+pub async fn write_batch(app: AppHandle, writes: Vec<FileWrite>) -> Result<BatchResult, AppError> {
+    run_blocking(app, move |_, state| {
+        let mut seen = HashSet::new();
+        for write in &writes {
+            let path = decode_path(&write.path);
+            ensure_allowed_path(&state, &path)?;
+            check_write_conflict(&path, &write.options)?;
+            if !seen.insert(write_target(&path)?) {
+                return Err(AppError::InvalidInput(format!(
+                    "{} appears more than once in the batch",
+                    write.path
+                )));
+            }
+        }
+
+        let mut staged: Vec<Staged> = Vec::with_capacity(writes.len());
+        for write in &writes {
+            let prepared = write_target(&decode_path(&write.path))
+                .and_then(|path| Ok((fs::read(&path)?, path)))
+                .and_then(|(original, path)| {
+                    if write.options.create_backup {
+                        write_backup_copy(&path)?;
+                    }
+                    let temp_path = stage_temp_file(&path, &write.content)?;
+                    Ok(Staged {
+                        path,
+                        temp_path,
+                        original,
+                    })
+                });
+            match prepared {
+                Ok(entry) => staged.push(entry),
+                Err(error) => {
+                    discard(&staged);
+                    return Err(error);
+                }
+            }
+        }
+
+        let mut journal_ids = Vec::with_capacity(staged.len());
+        for (write, entry) in writes.iter().zip(&staged) {
+            match journal::record(&entry.path, &entry.temp_path, &write.content) {
+                Ok(id) => journal_ids.push(id),
+                Err(error) => {
+                    journal_ids.into_iter().for_each(journal::complete);
+                    discard(&staged);
+                    return Err(error);
+                }
+            }
+        }
+
+        for (index, entry) in staged.iter().enumerate() {
+            if let Err(error) = fs::rename(&entry.temp_path, &entry.path) {
+                discard(&staged[index..]);
+                let failed = roll_back(&staged[..index]);
+                let mut message = format!("{}: {}", entry.path.display(), error);
+                if !failed.is_empty() {
+                    message.push_str(&format!("; could not restore {}", failed.join(", ")));
+                }
+                journal_ids.into_iter().for_each(journal::complete);
+                return Err(AppError::io(IoCode::Other, message));
+            }
+        }
+        journal_ids.into_iter().for_each(journal::complete);
+
+        for (write, entry) in writes.iter().zip(&staged) {
+            if let Err(error) = provenance::record(&entry.path, write.content.as_bytes()) {
+                tracing::warn!("failed to record write: {}", logging::redacted(&error));
+            }
+            watcher::record_write(&state, &entry.path, &write.content)?;
+        }
+        Ok(BatchResult {
+            written: writes.into_iter().map(|write| write.path).collect(),
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn staged(path: PathBuf, original: &str) -> Staged {
+        Staged {
+            temp_path: path.with_extension("tmp"),
+            path,
+            original: original.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn roll_back_restores_replaced_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join(".env");
+        let second = dir.path().join(".env.local");
+        fs::write(&first, "A=new\n").unwrap();
+        fs::write(&second, "B=new\n").unwrap();
+
+        let failed = roll_back(&[
+            staged(first.clone(), "A=old\n"),
+            staged(second.clone(), "B=old\n"),
+        ]);
+        assert!(failed.is_empty());
+        assert_eq!(fs::read_to_string(&first).unwrap(), "A=old\n");
+        assert_eq!(fs::read_to_string(&second).unwrap(), "B=old\n");
+    }
+
+    #[test]
+    fn roll_back_reports_files_it_cannot_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let present = dir.path().join(".env");
+        fs::write(&present, "A=new\n").unwrap();
+        let gone = dir.path().join(".env.gone");
+
+        let failed = roll_back(&[
+            staged(gone.clone(), "B=old\n"),
+            staged(present.clone(), "A=old\n"),
+        ]);
+        assert_eq!(failed, [encode_path(&gone)]);
+        assert_eq!(fs::read_to_string(&present).unwrap(), "A=old\n");
+    }
+
+    #[test]
+    fn discard_removes_only_temp_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env");
+        fs::write(&path, "A=1\n").unwrap();
+        let entry = staged(path.clone(), "A=1\n");
+        fs::write(&entry.temp_path, "A=2\n").unwrap();
+
+        discard(&[entry]);
+        assert!(!path.with_extension("tmp").exists());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "A=1\n");
+    }
+}
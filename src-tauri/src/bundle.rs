@@ -0,0 +1,507 @@
+use crate::ipc_path::{decode_path, encode_path};
+use crate::{
+    access, app_lock, create_new_file, crypto, ensure_allowed_path, env_file_regex, hash_content,
+    is_env_file_name, logging, normalize_path, parse_env_lines, parse_env_text, path_starts_with,
+    provenance, read_env_text, run_blocking, serialize_with_layout, write_contents, AppError,
+    AppState, DiscoveryPatterns, EnvLine, WriteOptions,
+};
+use chrono::Utc;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io::{Cursor, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use tauri::AppHandle;
+use zip::{write::FileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+pub const BUNDLE_MAGIC: &[u8; 8] = b"ENVSHLF1";
+pub const BUNDLE_EXTENSION: &str = "envshelf";
+pub const MANIFEST_NAME: &str = "manifest.json";
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleEntry {
+    pub relative_path: String,
+    pub content_hash: String,
+    pub size: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleManifest {
+    pub root_name: String,
+    pub created_at: i64,
+    pub files: Vec<BundleEntry>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleInfo {
+    path: String,
+    file_count: usize,
+    size: u64,
+}
+
+fn bundle_error(error: impl ToString) -> AppError {
+    AppError::BundleError(error.to_string())
+}
+
+/// Encrypts an archive and prefixes the bundle marker.
+fn seal(passphrase: &str, archive: &[u8]) -> Result<Vec<u8>, AppError> {
+    let mut payload = BUNDLE_MAGIC.to_vec();
+    payload.extend(crypto::encrypt(passphrase, archive)?);
+    Ok(payload)
+}
+
+/// Checks the bundle marker and decrypts the archive behind it.
+fn unseal(passphrase: &str, payload: &[u8]) -> Result<Vec<u8>, AppError> {
+    let encrypted = payload
+        .strip_prefix(BUNDLE_MAGIC.as_slice())
+        .ok_or_else(|| bundle_error("Not an EnvShelf bundle"))?;
+    crypto::decrypt(passphrase, encrypted)
+}
+
+/// Refuses an output that is a symlink, a folder or any file other than an earlier
+/// bundle, so an export can only create a bundle or replace one.
+fn check_output(output: &Path) -> Result<(), AppError> {
+    let parent_is_dir = output
+        .parent()
+        .map(|parent| parent.as_os_str().is_empty() || parent.is_dir())
+        .unwrap_or(false);
+    if !parent_is_dir {
+        return Err(AppError::InvalidInput(
+            "Bundle folder does not exist".to_string(),
+        ));
+    }
+    let Ok(metadata) = fs::symlink_metadata(output) else {
+        return Ok(());
+    };
+    let mut magic = [0u8; BUNDLE_MAGIC.len()];
+    let is_bundle = metadata.is_file()
+        && fs::File::open(output)
+            .and_then(|mut file| file.read_exact(&mut magic))
+            .is_ok()
+        && &magic == BUNDLE_MAGIC;
+    if !is_bundle {
+        return Err(AppError::InvalidInput(
+            "Bundle path already exists and is not a bundle".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Zips every allowed env file (paths relative to the scanned root) together with a
+/// manifest, then encrypts the archive with a passphrase-derived AES-256-GCM key. The
+/// output may replace an earlier bundle but never another file.
+#[tauri::command]
+pub async fn export_workspace_bundle(
+    app: AppHandle,
+    passphrase: String,
+    output_path: String,
+) -> Result<BundleInfo, AppError> {
+    run_blocking(app, move |_, state| {
+        app_lock::ensure_unlocked(&state)?;
+        if passphrase.is_empty() {
+            return Err(AppError::InvalidInput("Passphrase is required".to_string()));
+        }
+        let output = decode_path(&output_path);
+        let has_extension = output
+            .extension()
+            .map(|ext| ext == BUNDLE_EXTENSION)
+            .unwrap_or(false);
+        if !has_extension {
+            return Err(AppError::InvalidInput(format!(
+                "Bundle path must end with .{}",
+                BUNDLE_EXTENSION
+            )));
+        }
+        check_output(&output)?;
+
+        let root = state
+            .root_path
+            .read()
+            .clone()
+            .ok_or(AppError::InvalidRootPath)?;
+        let mut paths: Vec<PathBuf> = state.allowed_files.read().iter().cloned().collect();
+        paths.sort();
+
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+        let mut entries = Vec::with_capacity(paths.len());
+        for path in &paths {
+            let Ok(relative) = path.strip_prefix(&root) else {
+                continue;
+            };
+            let relative_path = relative.to_string_lossy().replace('\\', "/");
+            ensure_allowed_path(&state, path)?;
+            let contents = read_env_text(&state, path)?.into_bytes();
+            writer
+                .start_file(relative_path.clone(), options)
+                .map_err(bundle_error)?;
+            writer.write_all(&contents)?;
+            entries.push(BundleEntry {
+                relative_path,
+                content_hash: hash_content(&contents),
+                size: contents.len() as u64,
+            });
+        }
+
+        let manifest = BundleManifest {
+            root_name: root
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            created_at: Utc::now().timestamp_millis(),
+            files: entries,
+        };
+        writer
+            .start_file(MANIFEST_NAME, options)
+            .map_err(bundle_error)?;
+        writer.write_all(&serde_json::to_vec_pretty(&manifest).map_err(bundle_error)?)?;
+        let archive = writer.finish().map_err(bundle_error)?.into_inner();
+
+        let payload = seal(&passphrase, &archive)?;
+        if output.exists() {
+            fs::remove_file(&output)?;
+        }
+        let mut file = create_new_file(&output)?;
+        file.write_all(&payload)?;
+        file.sync_all()?;
+
+        Ok(BundleInfo {
+            path: output_path,
+            file_count: manifest.files.len(),
+            size: payload.len() as u64,
+        })
+    })
+    .await
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportStrategy {
+    Overwrite,
+    Skip,
+    Merge,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportAction {
+    Create,
+    Overwrite,
+    Merge,
+    Skip,
+    Unchanged,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportEntry {
+    relative_path: String,
+    target_path: String,
+    action: ImportAction,
+    added_keys: Vec<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    root_name: String,
+    created_at: i64,
+    dry_run: bool,
+    entries: Vec<ImportEntry>,
+}
+
+/// Rejects absolute paths and `..` components so a bundle can never write outside
+/// the chosen target root.
+fn safe_relative_path(relative: &str) -> Option<PathBuf> {
+    let path = PathBuf::from(relative);
+    let is_safe = path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+    is_safe.then_some(path)
+}
+
+/// Whether the entry names an env file, so a bundle cannot plant other files such as
+/// `.git/hooks/pre-commit` or `package.json` under the root.
+fn is_env_entry(relative: &Path, regex: &Regex) -> bool {
+    relative
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| is_env_file_name(name, regex, &DiscoveryPatterns::default()))
+        .unwrap_or(false)
+}
+
+/// Refuses a target whose nearest existing folder, or the file itself when it exists,
+/// resolves outside `root` through a symlink.
+fn ensure_inside_root(root: &Path, target: &Path) -> Result<(), AppError> {
+    let mut existing = target.parent();
+    while let Some(folder) = existing.filter(|folder| !folder.exists()) {
+        existing = folder.parent();
+    }
+    let folder_inside = existing
+        .and_then(|folder| normalize_path(folder).ok())
+        .map(|folder| path_starts_with(&folder, root))
+        .unwrap_or(false);
+    let file_inside = !target.exists()
+        || normalize_path(target)
+            .map(|file| path_starts_with(&file, root))
+            .unwrap_or(false);
+    if !folder_inside || !file_inside {
+        return Err(AppError::PathNotAllowed);
+    }
+    Ok(())
+}
+
+/// Creates `target` and any missing folders, checking again once the folders exist.
+fn create_imported_file(root: &Path, target: &Path, contents: &str) -> Result<PathBuf, AppError> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    ensure_inside_root(root, target)?;
+    let mut file = create_new_file(target)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    normalize_path(target)
+}
+
+/// Appends the keys of `incoming` that `existing` does not define yet.
+pub fn merge_contents(existing: &str, incoming: &str) -> (String, Vec<String>) {
+    let (mut lines, layout) = parse_env_text(existing);
+    let present: HashSet<String> = lines
+        .iter()
+        .filter_map(|line| match line {
+            EnvLine::Kv { key, .. } => Some(key.clone()),
+            _ => None,
+        })
+        .collect();
+    let mut added = Vec::new();
+    for line in parse_env_lines(incoming) {
+        if let EnvLine::Kv { key, .. } = &line {
+            if !present.contains(key) && !added.contains(key) {
+                added.push(key.clone());
+                lines.push(line);
+            }
+        }
+    }
+    (serialize_with_layout(&lines, layout), added)
+}
+
+/// Writes the files of a bundle into `target_root`, which must be the scanned root or a
+/// folder inside it. Nothing is written unless every entry stays inside the root.
+#[tauri::command]
+pub async fn import_workspace_bundle(
+    app: AppHandle,
+    bundle_path: String,
+    passphrase: String,
+    target_root: String,
+    strategy: ImportStrategy,
+    dry_run: bool,
+) -> Result<ImportReport, AppError> {
+    run_blocking(app, move |_, state| {
+        app_lock::ensure_unlocked(&state)?;
+        access::ensure_active(&state)?;
+        let target_root = normalize_path(&decode_path(&target_root))?;
+        let root = state
+            .root_path
+            .read()
+            .clone()
+            .ok_or(AppError::InvalidRootPath)?;
+        if !target_root.is_dir() || !path_starts_with(&target_root, &root) {
+            return Err(AppError::PathNotAllowed);
+        }
+
+        let archive = unseal(&passphrase, &fs::read(decode_path(&bundle_path))?)?;
+        let mut zip = ZipArchive::new(Cursor::new(archive)).map_err(bundle_error)?;
+
+        let read_entry = |zip: &mut ZipArchive<Cursor<Vec<u8>>>, name: &str| {
+            let mut contents = Vec::new();
+            zip.by_name(name)
+                .map_err(bundle_error)?
+                .read_to_end(&mut contents)?;
+            Ok::<_, AppError>(contents)
+        };
+        let manifest: BundleManifest =
+            serde_json::from_slice(&read_entry(&mut zip, MANIFEST_NAME)?).map_err(bundle_error)?;
+
+        let regex = env_file_regex()?;
+        let mut entries = Vec::with_capacity(manifest.files.len());
+        let mut writes = Vec::new();
+        for file in &manifest.files {
+            let relative = safe_relative_path(&file.relative_path)
+                .filter(|relative| is_env_entry(relative, &regex))
+                .ok_or_else(|| bundle_error(format!("Unsafe path: {}", file.relative_path)))?;
+            let target = target_root.join(relative);
+            ensure_inside_root(&root, &target)?;
+            if target.exists() {
+                ensure_allowed_path(&state, &target)?;
+            }
+            let incoming = String::from_utf8(read_entry(&mut zip, &file.relative_path)?)
+                .map_err(|_| AppError::NotText)?;
+
+            let (action, existing_hash, contents, added_keys) = if !target.exists() {
+                (ImportAction::Create, None, Some(incoming), Vec::new())
+            } else {
+                let existing = read_env_text(&state, &target)?;
+                let existing_hash = Some(hash_content(existing.as_bytes()));
+                if existing == incoming {
+                    (ImportAction::Unchanged, None, None, Vec::new())
+                } else {
+                    match strategy {
+                        ImportStrategy::Skip => (ImportAction::Skip, None, None, Vec::new()),
+                        ImportStrategy::Overwrite => (
+                            ImportAction::Overwrite,
+                            existing_hash,
+                            Some(incoming),
+                            Vec::new(),
+                        ),
+                        ImportStrategy::Merge => {
+                            let (merged, added) = merge_contents(&existing, &incoming);
+                            if added.is_empty() {
+                                (ImportAction::Unchanged, None, None, added)
+                            } else {
+                                (ImportAction::Merge, existing_hash, Some(merged), added)
+                            }
+                        }
+                    }
+                }
+            };
+
+            if let Some(contents) = contents {
+                writes.push((target.clone(), existing_hash, contents));
+            }
+            entries.push(ImportEntry {
+                relative_path: file.relative_path.clone(),
+                target_path: encode_path(&target),
+                action,
+                added_keys,
+            });
+        }
+
+        if !dry_run {
+            for (target, existing_hash, contents) in writes {
+                if existing_hash.is_none() {
+                    let created = create_imported_file(&root, &target, &contents)?;
+                    if let Err(error) = provenance::record(&created, contents.as_bytes()) {
+                        tracing::warn!("failed to record write: {}", logging::redacted(&error));
+                    }
+                    state.allowed_files.write().insert(created);
+                } else {
+                    let options = WriteOptions {
+                        create_backup: false,
+                        expected_hash: existing_hash,
+                        expected_mtime: None,
+                        quote_style: None,
+                    };
+                    write_contents(&state, &target, &contents, &options)?;
+                }
+            }
+        }
+
+        Ok(ImportReport {
+            root_name: manifest.root_name,
+            created_at: manifest.created_at,
+            dry_run,
+            entries,
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_archives_open_with_the_same_passphrase() {
+        let archive = b"PK archive bytes".to_vec();
+        let payload = seal("correct horse", &archive).unwrap();
+        assert!(payload.starts_with(BUNDLE_MAGIC));
+        assert_eq!(unseal("correct horse", &payload).unwrap(), archive);
+    }
+
+    #[test]
+    fn sealed_archives_refuse_a_wrong_passphrase() {
+        let payload = seal("correct horse", b"secret").unwrap();
+        assert!(matches!(
+            unseal("battery staple", &payload),
+            Err(AppError::CryptoError(_))
+        ));
+    }
+
+    #[test]
+    fn refuses_payloads_without_the_bundle_marker() {
+        let encrypted = crypto::encrypt("pass", b"secret").unwrap();
+        assert!(matches!(
+            unseal("pass", &encrypted),
+            Err(AppError::BundleError(_))
+        ));
+    }
+
+    #[test]
+    fn exported_zip_round_trips_through_a_bundle() {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file("app/.env", FileOptions::default())
+            .unwrap();
+        writer.write_all(b"A=1\n").unwrap();
+        let archive = writer.finish().unwrap().into_inner();
+
+        let opened = unseal("pass", &seal("pass", &archive).unwrap()).unwrap();
+        let mut zip = ZipArchive::new(Cursor::new(opened)).unwrap();
+        let mut contents = String::new();
+        zip.by_name("app/.env")
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(contents, "A=1\n");
+    }
+
+    #[test]
+    fn relative_paths_stay_below_the_target() {
+        assert!(safe_relative_path("app/.env").is_some());
+        assert!(safe_relative_path("../.env").is_none());
+        assert!(safe_relative_path("app/../../.env").is_none());
+        assert!(safe_relative_path("/etc/.env").is_none());
+    }
+
+    #[test]
+    fn only_env_files_are_imported() {
+        let regex = env_file_regex().unwrap();
+        assert!(is_env_entry(Path::new("app/.env.local"), &regex));
+        assert!(is_env_entry(Path::new("web.env"), &regex));
+        assert!(!is_env_entry(Path::new(".git/hooks/pre-commit"), &regex));
+        assert!(!is_env_entry(Path::new("package.json"), &regex));
+    }
+
+    #[test]
+    fn merge_adds_only_missing_keys() {
+        let (merged, added) = merge_contents("A=1\n# keep\nB=2\n", "B=20\nC=3\nC=30\n");
+        assert_eq!(merged, "A=1\n# keep\nB=2\nC=3\n");
+        assert_eq!(added, ["C"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn refuses_targets_behind_a_symlink_out_of_the_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("root");
+        let outside = dir.path().join("outside");
+        fs::create_dir_all(&root).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        let root = root.canonicalize().unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("linked")).unwrap();
+
+        assert!(ensure_inside_root(&root, &root.join("app/new/.env")).is_ok());
+        assert!(matches!(
+            ensure_inside_root(&root, &root.join("linked/.env")),
+            Err(AppError::PathNotAllowed)
+        ));
+        assert!(matches!(
+            ensure_inside_root(&root, &root.join("linked/deeper/.env")),
+            Err(AppError::PathNotAllowed)
+        ));
+    }
+}
@@ -0,0 +1,346 @@
+use crate::ipc_path::encode_path;
+use crate::{
+    app_lock, env_file_regex, hash_content, is_env_file_name, modified_millis, normalize_path,
+    run_blocking, AppError, AppState, DiscoveryPatterns, ScanResult,
+};
+use globset::GlobSet;
+use ignore::gitignore::Gitignore;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/// What the last scan looked for, so new files can be recognised without a walk.
+pub struct Discovery {
+    pub root: PathBuf,
+    pub patterns: DiscoveryPatterns,
+    pub excludes: GlobSet,
+    pub ignore: Gitignore,
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct FileStamp {
+    size: u64,
+    modified_at: i64,
+    content_hash: String,
+}
+
+impl FileStamp {
+    pub fn new(metadata: &fs::Metadata, content_hash: String) -> Self {
+        Self {
+            size: metadata.len(),
+            modified_at: modified_millis(metadata),
+            content_hash,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ScanSnapshot {
+    discovery: Option<Discovery>,
+    files: HashMap<PathBuf, FileStamp>,
+    result: Option<ScanResult>,
+}
+
+impl ScanSnapshot {
+    /// Files whose stamps the next change check compares against.
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+}
+
+/// What a rescan of the same root found compared to the previous scan.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanDiff {
+    added_files: Vec<String>,
+    removed_files: Vec<String>,
+    added_groups: Vec<String>,
+    removed_groups: Vec<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileChangeKind {
+    Added,
+    Modified,
+    Deleted,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileChange {
+    path: String,
+    change: FileChangeKind,
+}
+
+fn stamp(path: &Path) -> Option<FileStamp> {
+    let metadata = fs::metadata(path).ok()?;
+    let bytes = fs::read(path).ok()?;
+    Some(FileStamp::new(&metadata, hash_content(&bytes)))
+}
+
+fn file_paths(result: &ScanResult) -> BTreeSet<&str> {
+    result
+        .groups
+        .iter()
+        .flat_map(|group| &group.env_files)
+        .map(|file| file.absolute_path.as_str())
+        .collect()
+}
+
+fn group_ids(result: &ScanResult) -> BTreeSet<&str> {
+    result
+        .groups
+        .iter()
+        .map(|group| group.id.as_str())
+        .collect()
+}
+
+fn missing_from<'a>(left: &BTreeSet<&'a str>, right: &BTreeSet<&'a str>) -> Vec<String> {
+    left.difference(right)
+        .map(|item| item.to_string())
+        .collect()
+}
+
+fn diff_scans(previous: &ScanResult, next: &ScanResult) -> ScanDiff {
+    let (before, after) = (file_paths(previous), file_paths(next));
+    let (groups_before, groups_after) = (group_ids(previous), group_ids(next));
+    ScanDiff {
+        added_files: missing_from(&after, &before),
+        removed_files: missing_from(&before, &after),
+        added_groups: missing_from(&groups_after, &groups_before),
+        removed_groups: missing_from(&groups_before, &groups_after),
+    }
+}
+
+/// Replaces the snapshot after a scan and returns the diff against the previous scan
+/// when it covered the same root. Single files outside the root are stamped here since
+/// the walk never saw them.
+pub fn record(
+    state: &AppState,
+    discovery: Discovery,
+    mut files: HashMap<PathBuf, FileStamp>,
+    result: &ScanResult,
+) -> Option<ScanDiff> {
+    for path in state.single_files.read().iter() {
+        if !files.contains_key(path) {
+            if let Some(stamp) = stamp(path) {
+                files.insert(path.clone(), stamp);
+            }
+        }
+    }
+    let mut snapshot = state.scan_snapshot.write();
+    let diff = snapshot
+        .result
+        .as_ref()
+        .filter(|previous| previous.root_path == result.root_path)
+        .map(|previous| diff_scans(previous, result));
+    *snapshot = ScanSnapshot {
+        discovery: Some(discovery),
+        files,
+        result: Some(result.clone()),
+    };
+    diff
+}
+
+/// The hash the last scan recorded for `path`, while its size and mtime still match,
+/// so callers can skip reading a file that has not been touched.
+pub fn unchanged_hash(state: &AppState, path: &Path, metadata: &fs::Metadata) -> Option<String> {
+    let snapshot = state.scan_snapshot.read();
+    let previous = snapshot.files.get(path)?;
+    let unchanged =
+        metadata.len() == previous.size && modified_millis(metadata) == previous.modified_at;
+    unchanged.then(|| previous.content_hash.clone())
+}
+
+/// Unchanged size and mtime are trusted; otherwise the content hash decides.
+fn is_modified(path: &Path, previous: &FileStamp) -> Option<bool> {
+    let metadata = fs::metadata(path).ok()?;
+    if metadata.len() == previous.size && modified_millis(&metadata) == previous.modified_at {
+        return Some(false);
+    }
+    let bytes = fs::read(path).ok()?;
+    Some(hash_content(&bytes) != previous.content_hash)
+}
+
+/// Env files that appeared in folders the last scan already knew about.
+fn added_files(discovery: &Discovery, known: &HashMap<PathBuf, FileStamp>) -> Vec<PathBuf> {
+    let Ok(regex) = env_file_regex() else {
+        return Vec::new();
+    };
+    let folders: BTreeSet<&Path> = known
+        .keys()
+        .filter(|path| path.starts_with(&discovery.root))
+        .filter_map(|path| path.parent())
+        .collect();
+    let mut added = Vec::new();
+    for folder in folders {
+        let Ok(entries) = fs::read_dir(folder) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let excluded = path
+                .strip_prefix(&discovery.root)
+                .map(|relative| discovery.excludes.is_match(relative))
+                .unwrap_or(false);
+            if !path.is_file()
+                || !is_env_file_name(&name, &regex, &discovery.patterns)
+                || excluded
+                || discovery.ignore.matched(&path, false).is_ignore()
+            {
+                continue;
+            }
+            if let Ok(path) = normalize_path(&path) {
+                if !known.contains_key(&path) {
+                    added.push(path);
+                }
+            }
+        }
+    }
+    added
+}
+
+/// Compares allowed files against the snapshot from the last scan without walking the
+/// tree again. New files are only detected in folders that already had env files.
+#[tauri::command]
+pub async fn list_changed_files(app: AppHandle) -> Result<Vec<FileChange>, AppError> {
+    run_blocking(app, move |_, state| {
+        app_lock::ensure_unlocked(&state)?;
+        let snapshot = state.scan_snapshot.read();
+        let mut changes = Vec::new();
+        for (path, previous) in &snapshot.files {
+            let change = match is_modified(path, previous) {
+                None => FileChangeKind::Deleted,
+                Some(true) => FileChangeKind::Modified,
+                Some(false) => continue,
+            };
+            changes.push(FileChange {
+                path: encode_path(path),
+                change,
+            });
+        }
+        if let Some(discovery) = &snapshot.discovery {
+            changes.extend(
+                added_files(discovery, &snapshot.files)
+                    .into_iter()
+                    .map(|path| FileChange {
+                        path: encode_path(&path),
+                        change: FileChangeKind::Added,
+                    }),
+            );
+        }
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(changes)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn scan(root: &str, groups: Vec<(&str, Vec<&str>)>) -> ScanResult {
+        let groups: Vec<_> = groups
+            .into_iter()
+            .map(|(id, files)| {
+                let files: Vec<_> = files
+                    .into_iter()
+                    .map(|path| {
+                        json!({
+                            "id": path, "absolutePath": path, "fileName": ".env",
+                            "folderPath": root, "size": 0, "modifiedAt": 0,
+                            "contentHash": "", "keyCount": 0, "commentCount": 0,
+                            "hasParseErrors": false, "modifiedBy": "unknown",
+                            "appWrittenAt": null, "skipped": false, "skipReason": null,
+                        })
+                    })
+                    .collect();
+                json!({
+                    "id": id, "name": id, "rootPath": root,
+                    "envFiles": files, "framework": null,
+                })
+            })
+            .collect();
+        serde_json::from_value(json!({ "rootPath": root, "groups": groups })).unwrap()
+    }
+
+    fn discovery(root: &Path) -> Discovery {
+        Discovery {
+            root: root.to_path_buf(),
+            patterns: DiscoveryPatterns::default(),
+            excludes: GlobSet::empty(),
+            ignore: Gitignore::empty(),
+        }
+    }
+
+    #[test]
+    fn diffs_files_and_groups_between_scans() {
+        let before = scan(
+            "/p",
+            &[("api", &["/p/api/.env"]), ("web", &["/p/web/.env"])],
+        );
+        let after = scan(
+            "/p",
+            &[
+                ("api", &["/p/api/.env", "/p/api/.env.local"]),
+                ("cli", &["/p/cli/.env"]),
+            ],
+        );
+        let diff = diff_scans(&before, &after);
+        assert_eq!(diff.added_files, ["/p/api/.env.local", "/p/cli/.env"]);
+        assert_eq!(diff.removed_files, ["/p/web/.env"]);
+        assert_eq!(diff.added_groups, ["cli"]);
+        assert_eq!(diff.removed_groups, ["web"]);
+    }
+
+    #[test]
+    fn only_a_rescan_of_the_same_root_is_diffed() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = AppState::default();
+        let first = scan("/p", vec![("api", vec!["/p/api/.env"])]);
+        assert!(record(&state, discovery(dir.path()), HashMap::new(), &first).is_none());
+
+        let rescan = scan("/p", Vec::new());
+        let diff = record(&state, discovery(dir.path()), HashMap::new(), &rescan).unwrap();
+        assert_eq!(diff.removed_files, ["/p/api/.env"]);
+
+        let other_root = scan("/q", Vec::new());
+        assert!(record(&state, discovery(dir.path()), HashMap::new(), &other_root).is_none());
+    }
+
+    #[test]
+    fn detects_modified_and_deleted_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env");
+        fs::write(&path, "A=1\n").unwrap();
+        let previous = stamp(&path).unwrap();
+        assert_eq!(is_modified(&path, &previous), Some(false));
+
+        fs::write(&path, "A=22\n").unwrap();
+        assert_eq!(is_modified(&path, &previous), Some(true));
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(is_modified(&path, &previous), None);
+    }
+
+    #[test]
+    fn finds_new_env_files_next_to_known_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = normalize_path(dir.path()).unwrap();
+        let known_path = root.join(".env");
+        fs::write(&known_path, "A=1\n").unwrap();
+        fs::write(root.join(".env.local"), "B=1\n").unwrap();
+        fs::write(root.join("notes.txt"), "C=1\n").unwrap();
+        let known = HashMap::from([(known_path.clone(), stamp(&known_path).unwrap())]);
+
+        assert_eq!(
+            added_files(&discovery(&root), &known),
+            [root.join(".env.local")]
+        );
+    }
+}
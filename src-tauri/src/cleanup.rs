@@ -0,0 +1,96 @@
+use crate::ipc_path::{decode_path, encode_path};
+use crate::{is_ignored_dir, journal, logging, run_blocking, store, AppError, AppState};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use tauri::AppHandle;
+use walkdir::WalkDir;
+
+const ROOTS_FILE: &str = "roots.json";
+/// Temp files younger than this may belong to a write in progress.
+const MIN_AGE: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupReport {
+    removed: Vec<String>,
+    failed: Vec<String>,
+}
+
+/// Records a scanned root so temp files left there can be found on the next launch.
+pub fn remember_root(app: &AppHandle, state: &AppState, root: &Path) -> Result<(), AppError> {
+    let root = encode_path(root);
+    store::update(app, state, ROOTS_FILE, |roots: &mut BTreeSet<String>| {
+        roots.insert(root);
+    })
+}
+
+fn is_orphaned(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .map(|age| age >= MIN_AGE)
+        .unwrap_or(false)
+}
+
+/// Removes `.<name>.tmp-<timestamp>` files that `write_contents` leaves behind when
+/// the app stops between creating a temp file and renaming it, except those of writes
+/// the journal can still re-apply.
+fn remove_temp_files(app: &AppHandle) -> Result<CleanupReport, AppError> {
+    let temp_regex = Regex::new(r"^\..+\.tmp-\d{14}$").map_err(|_| AppError::RegexError)?;
+    let roots: BTreeSet<String> = store::load(app, ROOTS_FILE)?;
+    let staged = journal::staged_files();
+    let mut report = CleanupReport {
+        removed: Vec::new(),
+        failed: Vec::new(),
+    };
+    for root in roots
+        .iter()
+        .map(|root| decode_path(root))
+        .filter(|root| root.is_dir())
+    {
+        let entries = WalkDir::new(root)
+            .follow_links(false)
+            .into_iter()
+            .filter_entry(|entry| !is_ignored_dir(entry))
+            .filter_map(Result::ok);
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy();
+            if !entry.file_type().is_file()
+                || !temp_regex.is_match(&name)
+                || !is_orphaned(entry.path())
+                || staged.contains(entry.path())
+            {
+                continue;
+            }
+            let display = encode_path(entry.path());
+            match fs::remove_file(entry.path()) {
+                Ok(()) => report.removed.push(display),
+                Err(_) => report.failed.push(display),
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Runs the cleanup in the background on startup.
+pub fn spawn(app: AppHandle) {
+    thread::spawn(move || {
+        if let Err(error) = remove_temp_files(&app) {
+            tracing::warn!(
+                "failed to clean up temp files: {}",
+                logging::redacted(&error)
+            );
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn cleanup_temp_files(app: AppHandle) -> Result<CleanupReport, AppError> {
+    run_blocking(app, move |app, _| remove_temp_files(&app)).await
+}
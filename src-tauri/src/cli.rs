@@ -0,0 +1,150 @@
+use crate::io_error::IoCode;
+use crate::ipc_path::decode_path;
+use crate::lint::{lint_paths, Diagnostic, Severity};
+use crate::{
+    diff_key_values, load_ignore_file, max_file_bytes, normalize_path, parse_env_lines,
+    read_env_text, unified_diff, walk_root, AppError, AppState, DiscoveryPatterns, ScanResult,
+};
+use globset::GlobSet;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+const USAGE: &str = "usage: envshelf scan [path] [--json]
+       envshelf lint [path] [--json]
+       envshelf diff <a> <b> [--json]";
+
+/// Runs a headless subcommand when the first argument names one and returns its exit
+/// code, or `None` to start the app normally. Exit codes: 0 clean, 1 findings or
+/// differences, 2 usage or IO errors.
+pub fn run(args: &[String]) -> Option<i32> {
+    let (command, rest) = args.split_first()?;
+    let json = rest.iter().any(|arg| arg == "--json");
+    let operands: Vec<&str> = rest
+        .iter()
+        .filter(|arg| !arg.starts_with("--"))
+        .map(String::as_str)
+        .collect();
+    let outcome = match (command.as_str(), operands.as_slice()) {
+        ("scan", [] | [_]) => scan(operands.first().copied().unwrap_or("."), json),
+        ("lint", [] | [_]) => lint(operands.first().copied().unwrap_or("."), json),
+        ("diff", [before, after]) => diff(before, after, json),
+        ("scan" | "lint" | "diff", _) => {
+            eprintln!("{}", USAGE);
+            return Some(2);
+        }
+        _ => return None,
+    };
+    match outcome {
+        Ok(code) => Some(code),
+        Err(error) => {
+            eprintln!("envshelf: {}", error);
+            Some(2)
+        }
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), AppError> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| AppError::io(IoCode::InvalidData, e.to_string()))?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Walks `path` with the default discovery patterns and allows the files found, the
+/// same way a scan in the app does.
+fn walk(state: &AppState, path: &str) -> Result<ScanResult, AppError> {
+    let root = normalize_path(Path::new(path))?;
+    if !root.is_dir() {
+        return Err(AppError::InvalidRootPath);
+    }
+    let ignore = load_ignore_file(&root)?;
+    let walked = walk_root(
+        &root,
+        &DiscoveryPatterns::default(),
+        &GlobSet::empty(),
+        &ignore,
+        max_file_bytes(state),
+        &state.cancel_scan,
+    )?;
+    *state.allowed_files.write() = walked.allowed_files;
+    Ok(ScanResult {
+        root_path: root.to_string_lossy().to_string(),
+        groups: walked.groups,
+        slow_paths: walked.slow_paths,
+        skipped_paths: walked.skipped_paths,
+        diff: None,
+    })
+}
+
+fn scan(path: &str, json: bool) -> Result<i32, AppError> {
+    let result = walk(&AppState::default(), path)?;
+    if json {
+        print_json(&result)?;
+        return Ok(0);
+    }
+    for group in &result.groups {
+        println!("{} ({})", group.name, group.root_path);
+        for file in &group.env_files {
+            let errors = if file.has_parse_errors {
+                ", parse errors"
+            } else {
+                ""
+            };
+            println!("  {}  {} keys{}", file.file_name, file.key_count, errors);
+        }
+    }
+    Ok(0)
+}
+
+fn lint(path: &str, json: bool) -> Result<i32, AppError> {
+    let state = AppState::default();
+    let result = walk(&state, path)?;
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    for group in &result.groups {
+        let paths: Vec<PathBuf> = group
+            .env_files
+            .iter()
+            .map(|file| decode_path(&file.absolute_path))
+            .collect();
+        diagnostics.extend(lint_paths(&state, paths));
+    }
+
+    if json {
+        print_json(&diagnostics)?;
+    } else {
+        for diagnostic in &diagnostics {
+            let line = diagnostic
+                .line
+                .map(|line| format!(":{}", line))
+                .unwrap_or_default();
+            println!(
+                "{}{}: {}: {} [{}]",
+                diagnostic.path,
+                line,
+                diagnostic.severity.label(),
+                diagnostic.message,
+                diagnostic.rule
+            );
+        }
+    }
+    let failed = diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity == Severity::Error);
+    Ok(if failed { 1 } else { 0 })
+}
+
+fn diff(before_path: &str, after_path: &str, json: bool) -> Result<i32, AppError> {
+    let state = AppState::default();
+    let before = read_env_text(&state, Path::new(before_path))?;
+    let after = read_env_text(&state, Path::new(after_path))?;
+    let preview = unified_diff(after_path, &before, &after);
+    if json {
+        print_json(&diff_key_values(
+            &parse_env_lines(&before),
+            &parse_env_lines(&after),
+        ))?;
+    } else {
+        print!("{}", preview.diff);
+    }
+    Ok(if preview.changed { 1 } else { 0 })
+}
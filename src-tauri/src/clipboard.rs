@@ -0,0 +1,52 @@
+use crate::interop::decoded_values;
+use crate::ipc_path::decode_path;
+use crate::{
+    ensure_allowed_path, parse_env_lines, read_env_text, run_blocking, AppError, AppState,
+};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, ClipboardManager};
+
+const MAX_CLIPBOARD_TTL_SECS: u64 = 300;
+
+/// Copies a value to the system clipboard and clears it after `ttl_secs`, unless the
+/// clipboard was overwritten by something else in the meantime.
+#[tauri::command]
+pub async fn copy_value_secure(
+    app: AppHandle,
+    path: String,
+    key: String,
+    ttl_secs: u64,
+) -> Result<(), AppError> {
+    run_blocking(app, move |app, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+
+        let contents = read_env_text(&state, &path_buf)?;
+        let lines = parse_env_lines(&contents);
+        let value = decoded_values(&lines)
+            .into_iter()
+            .find(|(line_key, _)| *line_key == key)
+            .map(|(_, value)| value)
+            .ok_or_else(|| AppError::KeyNotFound(key.clone()))?;
+
+        let mut clipboard = app.clipboard_manager();
+        clipboard
+            .write_text(value.clone())
+            .map_err(|e| AppError::ClipboardError(e.to_string()))?;
+
+        let ttl = Duration::from_secs(ttl_secs.clamp(1, MAX_CLIPBOARD_TTL_SECS));
+        thread::spawn(move || {
+            thread::sleep(ttl);
+            let mut clipboard = app.clipboard_manager();
+            if let Ok(Some(current)) = clipboard.read_text() {
+                if current == value {
+                    let _ = clipboard.write_text(String::new());
+                }
+            }
+        });
+
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,97 @@
+use crate::ipc_path::decode_path;
+use crate::{
+    ensure_allowed_path, ensure_no_directives, find_kv_value, parse_env_text, quoting,
+    read_env_text, run_blocking, serialize_for_write, set_kv_value, write_contents, AppError,
+    AppState, WriteOptions,
+};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ValueTransform {
+    Base64Encode,
+    Base64Decode,
+    UrlEncode,
+    UrlDecode,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransformResult {
+    key: String,
+    before: String,
+    after: String,
+    written: bool,
+}
+
+pub fn apply_transform(transform: ValueTransform, value: &str) -> Result<String, AppError> {
+    match transform {
+        ValueTransform::Base64Encode => Ok(STANDARD.encode(value.as_bytes())),
+        ValueTransform::Base64Decode => {
+            let bytes = STANDARD
+                .decode(value.trim())
+                .map_err(|e| AppError::InvalidInput(e.to_string()))?;
+            String::from_utf8(bytes)
+                .map_err(|_| AppError::InvalidInput("Decoded value is not UTF-8".to_string()))
+        }
+        ValueTransform::UrlEncode => Ok(urlencoding::encode(value).into_owned()),
+        ValueTransform::UrlDecode => urlencoding::decode(value)
+            .map(|decoded| decoded.into_owned())
+            .map_err(|e| AppError::InvalidInput(e.to_string())),
+    }
+}
+
+/// Encodes `value` for the file with its quotes and escapes, keeping it quoted when
+/// `original` was quoted.
+fn requote(original: &str, value: &str) -> String {
+    if original.trim_start().starts_with(['"', '\'']) {
+        quoting::quote_literal(value)
+    } else {
+        quoting::encode_literal(value)
+    }
+}
+
+/// Encodes or decodes the value of `key`. When `options` is provided the result is
+/// written back to the file; otherwise this is a preview.
+#[tauri::command]
+pub async fn transform_value(
+    app: AppHandle,
+    path: String,
+    key: String,
+    transform: ValueTransform,
+    options: Option<WriteOptions>,
+) -> Result<TransformResult, AppError> {
+    run_blocking(app, move |_, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+
+        let contents = read_env_text(&state, &path_buf)?;
+        let (mut lines, layout) = parse_env_text(&contents);
+        ensure_no_directives(&lines, &key)?;
+        let original = find_kv_value(&lines, &key)
+            .map(str::to_string)
+            .ok_or_else(|| AppError::KeyNotFound(key.clone()))?;
+        let before = quoting::decode_value(&original);
+        let after = apply_transform(transform, &before)?;
+
+        let written = match options {
+            Some(options) => {
+                set_kv_value(&mut lines, &key, &requote(&original, &after));
+                let content = serialize_for_write(&state, &path_buf, &lines, layout, &options)?;
+                write_contents(&state, &path_buf, &content, &options)?;
+                true
+            }
+            None => false,
+        };
+
+        Ok(TransformResult {
+            key,
+            before,
+            after,
+            written,
+        })
+    })
+    .await
+}
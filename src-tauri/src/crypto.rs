@@ -0,0 +1,101 @@
+use crate::AppError;
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
+};
+use argon2::Argon2;
+use rand::{rngs::OsRng, RngCore};
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+
+pub fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; KEY_LEN], AppError> {
+    let mut key = [0u8; KEY_LEN];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| AppError::CryptoError(e.to_string()))?;
+    Ok(key)
+}
+
+pub fn random_salt() -> [u8; SALT_LEN] {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    salt
+}
+
+pub fn encrypt_with_key(key: &[u8; KEY_LEN], plaintext: &[u8]) -> Result<Vec<u8>, AppError> {
+    let cipher =
+        Aes256Gcm::new_from_slice(key).map_err(|e| AppError::CryptoError(e.to_string()))?;
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|_| AppError::CryptoError("Encryption failed".to_string()))?;
+    let mut output = nonce.to_vec();
+    output.extend_from_slice(&ciphertext);
+    Ok(output)
+}
+
+pub fn decrypt_with_key(key: &[u8; KEY_LEN], payload: &[u8]) -> Result<Vec<u8>, AppError> {
+    if payload.len() < NONCE_LEN {
+        return Err(AppError::CryptoError("Payload is truncated".to_string()));
+    }
+    let cipher =
+        Aes256Gcm::new_from_slice(key).map_err(|e| AppError::CryptoError(e.to_string()))?;
+    let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| AppError::CryptoError("Wrong passphrase or corrupted data".to_string()))
+}
+
+/// Encrypts with a passphrase. Output layout: salt | nonce | ciphertext.
+pub fn encrypt(passphrase: &str, plaintext: &[u8]) -> Result<Vec<u8>, AppError> {
+    let salt = random_salt();
+    let key = derive_key(passphrase, &salt)?;
+    let mut output = salt.to_vec();
+    output.extend(encrypt_with_key(&key, plaintext)?);
+    Ok(output)
+}
+
+pub fn decrypt(passphrase: &str, payload: &[u8]) -> Result<Vec<u8>, AppError> {
+    if payload.len() < SALT_LEN {
+        return Err(AppError::CryptoError("Payload is truncated".to_string()));
+    }
+    let (salt, rest) = payload.split_at(SALT_LEN);
+    let key = derive_key(passphrase, salt)?;
+    decrypt_with_key(&key, rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decrypts_what_it_encrypted() {
+        let payload = encrypt("passphrase", b"A=1\n").unwrap();
+        assert_eq!(decrypt("passphrase", &payload).unwrap(), b"A=1\n");
+    }
+
+    #[test]
+    fn salts_and_nonces_differ_per_encryption() {
+        let first = encrypt("passphrase", b"same").unwrap();
+        let second = encrypt("passphrase", b"same").unwrap();
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn refuses_a_wrong_passphrase_or_tampered_payload() {
+        let mut payload = encrypt("passphrase", b"A=1").unwrap();
+        assert!(decrypt("other", &payload).is_err());
+        let last = payload.len() - 1;
+        payload[last] ^= 1;
+        assert!(decrypt("passphrase", &payload).is_err());
+    }
+
+    #[test]
+    fn refuses_truncated_payloads() {
+        assert!(decrypt("passphrase", &[0u8; SALT_LEN - 1]).is_err());
+        assert!(decrypt("passphrase", &[0u8; SALT_LEN + NONCE_LEN - 1]).is_err());
+    }
+}
@@ -0,0 +1,100 @@
+use crate::ipc_path::encode_path;
+use crate::{
+    app_lock, hash_content, hash_path, parse_env_lines, read_env_text, run_blocking, unquote,
+    AppError, AppState, EnvLine,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+/// Length of the value fingerprint; enough to tell values apart, too short to look up.
+const FINGERPRINT_LEN: usize = 12;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyDefinition {
+    path: String,
+    group_id: String,
+    project: String,
+    line: usize,
+    /// Same fingerprint means same value, without sending the value itself.
+    value_fingerprint: String,
+    empty: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyDefinitions {
+    key: String,
+    definitions: Vec<KeyDefinition>,
+    distinct_values: usize,
+}
+
+/// Short hash of a value, shown where the value itself must not be.
+pub fn fingerprint(value: &str) -> String {
+    let mut fingerprint = hash_content(value.as_bytes());
+    fingerprint.truncate(FINGERPRINT_LEN);
+    fingerprint
+}
+
+/// Every allowed file and line that assigns `key`, with a fingerprint per value so the
+/// UI can show how many different values the workspace uses.
+#[tauri::command]
+pub async fn where_is_key_defined(app: AppHandle, key: String) -> Result<KeyDefinitions, AppError> {
+    run_blocking(app, move |_, state| {
+        app_lock::ensure_unlocked(&state)?;
+        let key = key.trim().to_string();
+        if key.is_empty() {
+            return Err(AppError::InvalidInput("Key is empty".to_string()));
+        }
+        let mut paths: Vec<PathBuf> = state.allowed_files.read().iter().cloned().collect();
+        paths.sort();
+
+        let mut definitions = Vec::new();
+        for path in &paths {
+            let Ok(contents) = read_env_text(&state, path) else {
+                continue;
+            };
+            let folder = path.parent().unwrap_or(path);
+            let project = folder
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            for (index, line) in parse_env_lines(&contents).iter().enumerate() {
+                let EnvLine::Kv {
+                    key: line_key,
+                    value,
+                    ..
+                } = line
+                else {
+                    continue;
+                };
+                if *line_key != key {
+                    continue;
+                }
+                let value = unquote(value);
+                definitions.push(KeyDefinition {
+                    path: encode_path(path),
+                    group_id: hash_path(folder),
+                    project: project.clone(),
+                    line: index + 1,
+                    value_fingerprint: fingerprint(value),
+                    empty: value.is_empty(),
+                });
+            }
+        }
+
+        let distinct_values = definitions
+            .iter()
+            .map(|definition| definition.value_fingerprint.as_str())
+            .collect::<HashSet<_>>()
+            .len();
+        Ok(KeyDefinitions {
+            key,
+            definitions,
+            distinct_values,
+        })
+    })
+    .await
+}
@@ -0,0 +1,66 @@
+use crate::backups::{self, BackupUsage};
+use crate::ipc_path::encode_path;
+use crate::value_index::{self, ValueIndexStatus};
+use crate::{run_blocking, AppError, AppState};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheSizes {
+    palette_entries: usize,
+    value_index: ValueIndexStatus,
+    dialect_settings: usize,
+    /// Files the change check compares against the last scan.
+    scan_snapshot_files: usize,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsReport {
+    app_version: String,
+    root_path: Option<String>,
+    single_files: Vec<String>,
+    allowed_files: usize,
+    caches: CacheSizes,
+    watched_files: usize,
+    auto_backup_running: bool,
+    /// `None` when the backup store could not be read.
+    backups: Option<BackupUsage>,
+    last_scan_ms: Option<u64>,
+}
+
+/// Support information for bug reports. Holds no file contents, so it works while the
+/// app is locked.
+#[tauri::command]
+pub async fn diagnostics(app: AppHandle) -> Result<DiagnosticsReport, AppError> {
+    run_blocking(app, move |app, state| {
+        let mut single_files: Vec<String> = state
+            .single_files
+            .read()
+            .iter()
+            .map(|path| encode_path(path))
+            .collect();
+        single_files.sort();
+        Ok(DiagnosticsReport {
+            app_version: app.package_info().version.to_string(),
+            root_path: state.root_path.read().as_deref().map(encode_path),
+            single_files,
+            allowed_files: state.allowed_files.read().len(),
+            caches: CacheSizes {
+                palette_entries: state.palette_index.read().entry_count(),
+                value_index: value_index::status(&state.value_index.read()),
+                dialect_settings: state.dialects.read().len(),
+                scan_snapshot_files: state.scan_snapshot.read().file_count(),
+            },
+            watched_files: state.watched_files.read().len(),
+            auto_backup_running: state.auto_backup.read().is_some(),
+            backups: backups::usage(&app).ok(),
+            last_scan_ms: state
+                .last_scan_duration
+                .read()
+                .map(|duration| duration.as_millis() as u64),
+        })
+    })
+    .await
+}
@@ -0,0 +1,124 @@
+use crate::ipc_path::{decode_path, encode_path};
+use crate::quoting::Dialect;
+use crate::{
+    ensure_allowed_path, files_in_group, normalize_path, run_blocking, store, AppError, AppState,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+const DIALECTS_FILE: &str = "dialects.json";
+
+/// Dialect per file or project folder, keyed by canonical path.
+type Dialects = BTreeMap<String, Dialect>;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DialectSetting {
+    path: String,
+    dialect: Dialect,
+    /// True when the dialect comes from the file itself rather than its project.
+    file_level: bool,
+}
+
+/// Loads persisted dialect settings into the app state; called on app setup.
+pub fn resume(app: &AppHandle) -> Result<(), AppError> {
+    let stored: Dialects = store::load(app, DIALECTS_FILE)?;
+    let state = app.state::<AppState>();
+    *state.dialects.write() = stored
+        .into_iter()
+        .map(|(path, dialect)| (decode_path(&path), dialect))
+        .collect();
+    Ok(())
+}
+
+/// The dialect of a file: its own setting, else its project folder's, else dotenv.
+pub fn dialect_for(state: &AppState, path: &Path) -> Dialect {
+    let Ok(path) = normalize_path(path) else {
+        return Dialect::default();
+    };
+    let dialects = state.dialects.read();
+    dialects
+        .get(&path)
+        .or_else(|| path.parent().and_then(|folder| dialects.get(folder)))
+        .copied()
+        .unwrap_or_default()
+}
+
+fn store_dialect(
+    app: &AppHandle,
+    state: &AppState,
+    target: PathBuf,
+    dialect: Option<Dialect>,
+) -> Result<(), AppError> {
+    let key = encode_path(&target);
+    store::update(app, state, DIALECTS_FILE, |dialects: &mut Dialects| {
+        match dialect {
+            Some(dialect) => dialects.insert(key, dialect),
+            None => dialects.remove(&key),
+        };
+    })?;
+    let mut cached = state.dialects.write();
+    match dialect {
+        Some(dialect) => cached.insert(target, dialect),
+        None => cached.remove(&target),
+    };
+    Ok(())
+}
+
+/// Sets the dialect of one file; `None` falls back to the project setting.
+#[tauri::command]
+pub async fn set_file_dialect(
+    app: AppHandle,
+    path: String,
+    dialect: Option<Dialect>,
+) -> Result<DialectSetting, AppError> {
+    run_blocking(app, move |app, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        store_dialect(&app, &state, normalize_path(&path_buf)?, dialect)?;
+        setting(&state, path)
+    })
+    .await
+}
+
+/// Sets the dialect for every file of a project group; `None` resets to dotenv.
+#[tauri::command]
+pub async fn set_project_dialect(
+    app: AppHandle,
+    group_id: String,
+    dialect: Option<Dialect>,
+) -> Result<(), AppError> {
+    run_blocking(app, move |app, state| {
+        let folder = files_in_group(&state, &group_id)?
+            .first()
+            .and_then(|path| path.parent())
+            .map(Path::to_path_buf)
+            .ok_or(AppError::GroupNotFound)?;
+        store_dialect(&app, &state, folder, dialect)
+    })
+    .await
+}
+
+fn setting(state: &AppState, path: String) -> Result<DialectSetting, AppError> {
+    let path_buf = decode_path(&path);
+    let file_level = state
+        .dialects
+        .read()
+        .contains_key(&normalize_path(&path_buf)?);
+    Ok(DialectSetting {
+        dialect: dialect_for(state, &path_buf),
+        path,
+        file_level,
+    })
+}
+
+#[tauri::command]
+pub async fn get_dialect(app: AppHandle, path: String) -> Result<DialectSetting, AppError> {
+    run_blocking(app, move |_, state| {
+        ensure_allowed_path(&state, &decode_path(&path))?;
+        setting(&state, path)
+    })
+    .await
+}
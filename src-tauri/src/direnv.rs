@@ -0,0 +1,186 @@
+use crate::ipc_path::decode_path;
+use crate::{
+    ensure_allowed_path, parse_env_text, read_env_text, run_blocking, serialize_env_line, unquote,
+    write_contents, AppError, AppState, EnvLine, LineEnding, TextLayout, WriteOptions,
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use tauri::AppHandle;
+
+pub const ENVRC_FILE_NAME: &str = ".envrc";
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum EnvrcLine {
+    Blank,
+    Comment {
+        raw: String,
+    },
+    Export {
+        key: String,
+        value: String,
+        raw: Option<String>,
+    },
+    PathAdd {
+        path: String,
+        raw: Option<String>,
+    },
+    Dotenv {
+        path: Option<String>,
+        #[serde(rename = "ifExists")]
+        if_exists: bool,
+        raw: Option<String>,
+    },
+    /// Any other shell statement; kept verbatim and never rewritten.
+    Shell {
+        raw: String,
+    },
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvrcDocument {
+    path: String,
+    lines: Vec<EnvrcLine>,
+    #[serde(flatten)]
+    layout: TextLayout,
+}
+
+fn directive_regex() -> Option<&'static Regex> {
+    static DIRECTIVE_REGEX: OnceLock<Option<Regex>> = OnceLock::new();
+    DIRECTIVE_REGEX
+        .get_or_init(|| Regex::new(r"^\s*(PATH_add|dotenv_if_exists|dotenv)(?:\s+(.+?))?\s*$").ok())
+        .as_ref()
+}
+
+/// Quotes a value for POSIX shells. Plain words are left as-is; anything else is
+/// wrapped in single quotes with embedded quotes escaped as `'\''`.
+pub fn shell_quote(value: &str) -> String {
+    let is_plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-./:@%+=,".contains(c));
+    if is_plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+fn parse_envrc_line(line: EnvLine) -> EnvrcLine {
+    match line {
+        EnvLine::Blank { .. } => EnvrcLine::Blank,
+        EnvLine::Comment { raw, .. } => EnvrcLine::Comment { raw },
+        EnvLine::Kv {
+            key,
+            value,
+            has_export: true,
+            raw,
+            ..
+        } => EnvrcLine::Export {
+            key,
+            value: unquote(&value).to_string(),
+            raw,
+        },
+        EnvLine::Kv { raw, .. } => EnvrcLine::Shell {
+            raw: raw.unwrap_or_default(),
+        },
+        line @ (EnvLine::Unset { .. } | EnvLine::Append { .. }) => EnvrcLine::Shell {
+            raw: serialize_env_line(&line),
+        },
+        EnvLine::Invalid { raw, .. } => {
+            let caps = directive_regex().and_then(|regex| regex.captures(&raw));
+            let Some(caps) = caps else {
+                return EnvrcLine::Shell { raw };
+            };
+            let argument = caps.get(2).map(|m| unquote(m.as_str()).to_string());
+            match caps.get(1).map(|m| m.as_str()) {
+                Some("PATH_add") => EnvrcLine::PathAdd {
+                    path: argument.unwrap_or_default(),
+                    raw: Some(raw),
+                },
+                Some(directive) => EnvrcLine::Dotenv {
+                    path: argument,
+                    if_exists: directive == "dotenv_if_exists",
+                    raw: Some(raw),
+                },
+                None => EnvrcLine::Shell { raw },
+            }
+        }
+    }
+}
+
+fn serialize_envrc_line(line: &EnvrcLine) -> String {
+    match line {
+        EnvrcLine::Blank => String::new(),
+        EnvrcLine::Comment { raw } | EnvrcLine::Shell { raw } => raw.clone(),
+        EnvrcLine::Export { raw: Some(raw), .. }
+        | EnvrcLine::PathAdd { raw: Some(raw), .. }
+        | EnvrcLine::Dotenv { raw: Some(raw), .. } => raw.clone(),
+        EnvrcLine::Export { key, value, .. } => format!("export {}={}", key, shell_quote(value)),
+        EnvrcLine::PathAdd { path, .. } => format!("PATH_add {}", shell_quote(path)),
+        EnvrcLine::Dotenv {
+            path, if_exists, ..
+        } => {
+            let directive = if *if_exists {
+                "dotenv_if_exists"
+            } else {
+                "dotenv"
+            };
+            match path {
+                Some(path) => format!("{} {}", directive, shell_quote(path)),
+                None => directive.to_string(),
+            }
+        }
+    }
+}
+
+fn serialize_envrc(document: &EnvrcDocument) -> String {
+    let newline = match document.layout.line_ending {
+        LineEnding::Lf => "\n",
+        LineEnding::Crlf => "\r\n",
+    };
+    let mut output = document
+        .lines
+        .iter()
+        .map(serialize_envrc_line)
+        .collect::<Vec<_>>()
+        .join(newline);
+    if document.layout.trailing_newline {
+        output.push_str(newline);
+    }
+    output
+}
+
+#[tauri::command]
+pub async fn read_envrc(app: AppHandle, path: String) -> Result<EnvrcDocument, AppError> {
+    run_blocking(app, move |_, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+
+        let contents = read_env_text(&state, &path_buf)?;
+        let (lines, layout) = parse_env_text(&contents);
+        Ok(EnvrcDocument {
+            path,
+            lines: lines.into_iter().map(parse_envrc_line).collect(),
+            layout,
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn write_envrc(
+    app: AppHandle,
+    path: String,
+    document: EnvrcDocument,
+    options: WriteOptions,
+) -> Result<(), AppError> {
+    run_blocking(app, move |_, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        write_contents(&state, &path_buf, &serialize_envrc(&document), &options)
+    })
+    .await
+}
@@ -0,0 +1,89 @@
+use crate::ipc_path::{decode_path, encode_path};
+use crate::{
+    env_file_regex, is_env_file_name, is_ignored_dir, mounts, normalize_path, run_blocking,
+    AppError, DiscoveryPatterns,
+};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use walkdir::WalkDir;
+
+/// The estimate stops after this many entries or this long, whichever comes first.
+const ESTIMATE_ENTRY_LIMIT: usize = 20_000;
+const ESTIMATE_TIME_LIMIT: Duration = Duration::from_millis(500);
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DropPolicy {
+    /// Accept folders on network shares, which can make scans slow.
+    allow_network: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DropTarget {
+    root_path: String,
+    network: bool,
+    /// Files and folders seen by the estimate.
+    entries: usize,
+    env_files: usize,
+    /// The estimate hit its entry or time limit, so the folder is at least this big.
+    truncated: bool,
+}
+
+/// Checks a path dropped onto the window before it is scanned: it must resolve to a
+/// local folder (network shares only when the policy allows them). Also returns a
+/// bounded estimate of how much a scan would walk.
+#[tauri::command]
+pub async fn validate_drop_path(
+    app: AppHandle,
+    path: String,
+    policy: Option<DropPolicy>,
+) -> Result<DropTarget, AppError> {
+    run_blocking(app, move |_, _| {
+        let policy = policy.unwrap_or_default();
+        let root = normalize_path(&decode_path(&path)).map_err(|_| AppError::InvalidRootPath)?;
+        if !root.is_dir() {
+            return Err(AppError::InvalidInput(format!("{} is not a folder", path)));
+        }
+        let network = mounts::is_network_path(&root);
+        if network && !policy.allow_network {
+            return Err(AppError::InvalidInput(format!(
+                "{} is on a network share",
+                root.display()
+            )));
+        }
+
+        let regex = env_file_regex()?;
+        let patterns = DiscoveryPatterns::default();
+        let started = Instant::now();
+        let mut entries = 0;
+        let mut env_files = 0;
+        let mut truncated = false;
+        let walk = WalkDir::new(&root)
+            .follow_links(false)
+            .into_iter()
+            .filter_entry(|entry| !is_ignored_dir(entry))
+            .filter_map(Result::ok);
+        for entry in walk {
+            if entries >= ESTIMATE_ENTRY_LIMIT || started.elapsed() >= ESTIMATE_TIME_LIMIT {
+                truncated = true;
+                break;
+            }
+            entries += 1;
+            let name = entry.file_name().to_string_lossy();
+            if entry.file_type().is_file() && is_env_file_name(&name, &regex, &patterns) {
+                env_files += 1;
+            }
+        }
+
+        Ok(DropTarget {
+            root_path: encode_path(&root),
+            network,
+            entries,
+            env_files,
+            truncated,
+        })
+    })
+    .await
+}
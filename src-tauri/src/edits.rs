@@ -0,0 +1,158 @@
+use crate::ipc_path::decode_path;
+use crate::lint::key_prefix;
+use crate::{
+    ensure_allowed_path, parse_env_text, read_env_text, run_blocking, serialize_for_write,
+    write_contents, AppError, AppState, EnvLine, WriteOptions,
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditResult {
+    path: String,
+    changed_keys: Vec<String>,
+}
+
+/// Keys sharing a namespace such as `DATABASE_*`.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrefixGroup {
+    prefix: String,
+    keys: Vec<String>,
+}
+
+/// Groups keys by prefix; a prefix used by a single key is not a group.
+pub fn prefix_groups(lines: &[EnvLine]) -> Vec<PrefixGroup> {
+    let mut groups: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for line in lines {
+        if let EnvLine::Kv { key, .. } = line {
+            if let Some(prefix) = key_prefix(key) {
+                let keys = groups.entry(prefix).or_default();
+                if !keys.contains(key) {
+                    keys.push(key.clone());
+                }
+            }
+        }
+    }
+    groups
+        .into_iter()
+        .filter(|(_, keys)| keys.len() > 1)
+        .map(|(prefix, keys)| PrefixGroup {
+            prefix: prefix.to_string(),
+            keys,
+        })
+        .collect()
+}
+
+/// Adds or strips the `export ` prefix on every key, or only on `keys` when given.
+#[tauri::command]
+pub async fn set_export_prefix(
+    app: AppHandle,
+    path: String,
+    enabled: bool,
+    keys: Option<Vec<String>>,
+    options: WriteOptions,
+) -> Result<EditResult, AppError> {
+    run_blocking(app, move |_, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+
+        let contents = read_env_text(&state, &path_buf)?;
+        let (mut lines, layout) = parse_env_text(&contents);
+        let mut changed_keys = Vec::new();
+
+        for line in lines.iter_mut() {
+            let EnvLine::Kv {
+                key,
+                has_export,
+                raw,
+                ..
+            } = line
+            else {
+                continue;
+            };
+            let selected = keys.as_ref().map(|keys| keys.contains(key)).unwrap_or(true);
+            if selected && *has_export != enabled {
+                *has_export = enabled;
+                *raw = None;
+                changed_keys.push(key.clone());
+            }
+        }
+
+        if !changed_keys.is_empty() {
+            let content = serialize_for_write(&state, &path_buf, &lines, layout, &options)?;
+            write_contents(&state, &path_buf, &content, &options)?;
+        }
+
+        Ok(EditResult { path, changed_keys })
+    })
+    .await
+}
+
+/// Renames every key in the `old` namespace to `new` (`DB_HOST` → `DATABASE_HOST`).
+/// Fails without writing if a renamed key would collide with an existing one.
+#[tauri::command]
+pub async fn rename_prefix(
+    app: AppHandle,
+    path: String,
+    old: String,
+    new: String,
+    options: WriteOptions,
+) -> Result<EditResult, AppError> {
+    run_blocking(app, move |_, state| {
+        let old = format!("{}_", old.trim_end_matches('_'));
+        let new = format!("{}_", new.trim_end_matches('_'));
+        let valid = Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*$").map_err(|_| AppError::RegexError)?;
+        if old == "_" || !valid.is_match(&new) {
+            return Err(AppError::InvalidInput(format!("Invalid prefix: {}", new)));
+        }
+
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        let contents = read_env_text(&state, &path_buf)?;
+        let (mut lines, layout) = parse_env_text(&contents);
+
+        let existing: Vec<String> = lines
+            .iter()
+            .filter_map(|line| match line {
+                EnvLine::Kv { key, .. } if !key.starts_with(&old) => Some(key.clone()),
+                _ => None,
+            })
+            .collect();
+        let mut changed_keys = Vec::new();
+        for line in lines.iter_mut() {
+            let (EnvLine::Kv { key, raw, .. }
+            | EnvLine::Unset { key, raw, .. }
+            | EnvLine::Append { key, raw, .. }) = line
+            else {
+                continue;
+            };
+            let Some(rest) = key.strip_prefix(&old) else {
+                continue;
+            };
+            let renamed = format!("{}{}", new, rest);
+            if existing.contains(&renamed) {
+                return Err(AppError::InvalidInput(format!(
+                    "{} already exists; rename it first",
+                    renamed
+                )));
+            }
+            *key = renamed;
+            *raw = None;
+            if !changed_keys.contains(key) {
+                changed_keys.push(key.clone());
+            }
+        }
+
+        if !changed_keys.is_empty() {
+            let content = serialize_for_write(&state, &path_buf, &lines, layout, &options)?;
+            write_contents(&state, &path_buf, &content, &options)?;
+        }
+
+        Ok(EditResult { path, changed_keys })
+    })
+    .await
+}
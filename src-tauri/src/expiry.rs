@@ -0,0 +1,159 @@
+use crate::ipc_path::decode_path;
+use crate::{ensure_allowed_path, hash_path, logging, run_blocking, store, AppError, AppState};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::thread;
+use std::time::Duration;
+use tauri::api::notification::Notification;
+use tauri::{AppHandle, Manager};
+
+const EXPIRY_FILE: &str = "expiry.json";
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const REMINDER_DAYS: i64 = 7;
+const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretExpiry {
+    path: String,
+    expires_at: i64,
+    /// Set once a reminder was shown, so each expiry notifies only once.
+    #[serde(default)]
+    notified_at: Option<i64>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiringSecret {
+    path: String,
+    key: String,
+    expires_at: i64,
+    days_left: i64,
+    expired: bool,
+}
+
+/// Expiry dates per file id, then per key.
+type Expiries = BTreeMap<String, BTreeMap<String, SecretExpiry>>;
+
+fn expiring_within(expiries: &Expiries, days: i64, now: i64) -> Vec<ExpiringSecret> {
+    let horizon = now + days * DAY_MILLIS;
+    let mut secrets: Vec<ExpiringSecret> = expiries
+        .values()
+        .flat_map(|keys| keys.iter())
+        .filter(|(_, expiry)| expiry.expires_at <= horizon)
+        .map(|(key, expiry)| ExpiringSecret {
+            path: expiry.path.clone(),
+            key: key.clone(),
+            expires_at: expiry.expires_at,
+            days_left: (expiry.expires_at - now).div_euclid(DAY_MILLIS),
+            expired: expiry.expires_at <= now,
+        })
+        .collect();
+    secrets.sort_by_key(|secret| secret.expires_at);
+    secrets
+}
+
+fn notify_due(app: &AppHandle) -> Result<(), AppError> {
+    let now = Utc::now().timestamp_millis();
+    let horizon = now + REMINDER_DAYS * DAY_MILLIS;
+    let state = app.state::<AppState>();
+    let due = store::update(app, &state, EXPIRY_FILE, |expiries: &mut Expiries| {
+        let mut due = Vec::new();
+        for keys in expiries.values_mut() {
+            for (key, expiry) in keys.iter_mut() {
+                if expiry.notified_at.is_none() && expiry.expires_at <= horizon {
+                    expiry.notified_at = Some(now);
+                    due.push((key.clone(), expiry.expires_at));
+                }
+            }
+        }
+        due
+    })?;
+
+    let identifier = app.config().tauri.bundle.identifier.clone();
+    for (key, expires_at) in due {
+        let body = if expires_at <= now {
+            format!("{} has expired and should be rotated", key)
+        } else {
+            let days = (expires_at - now).div_euclid(DAY_MILLIS);
+            format!("{} expires in {} day(s)", key, days)
+        };
+        let _ = Notification::new(&identifier)
+            .title("Secret rotation due")
+            .body(body)
+            .show();
+    }
+    Ok(())
+}
+
+/// Checks stored expiries hourly and shows a system notification for secrets that
+/// expire within a week.
+pub fn spawn(app: AppHandle) {
+    thread::spawn(move || loop {
+        if let Err(error) = notify_due(&app) {
+            tracing::warn!(
+                "failed to check secret expiry: {}",
+                logging::redacted(&error)
+            );
+        }
+        thread::sleep(CHECK_INTERVAL);
+    });
+}
+
+/// Sets the expiry (unix millis) for a key; `None` removes it. Changing the date
+/// re-arms the reminder.
+#[tauri::command]
+pub async fn set_secret_expiry(
+    app: AppHandle,
+    path: String,
+    key: String,
+    expires_at: Option<i64>,
+) -> Result<Option<SecretExpiry>, AppError> {
+    run_blocking(app, move |app, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        let file_id = hash_path(&path_buf);
+
+        store::update(&app, &state, EXPIRY_FILE, |expiries: &mut Expiries| {
+            let file_expiries = expiries.entry(file_id.clone()).or_default();
+            let expiry = match expires_at {
+                Some(expires_at) => {
+                    let expiry = SecretExpiry {
+                        path,
+                        expires_at,
+                        notified_at: None,
+                    };
+                    file_expiries.insert(key, expiry.clone());
+                    Some(expiry)
+                }
+                None => {
+                    file_expiries.remove(&key);
+                    None
+                }
+            };
+            if file_expiries.is_empty() {
+                expiries.remove(&file_id);
+            }
+            expiry
+        })
+    })
+    .await
+}
+
+/// Lists secrets that expire within `days`, including ones already expired.
+#[tauri::command]
+pub async fn list_expiring_secrets(
+    app: AppHandle,
+    days: u32,
+) -> Result<Vec<ExpiringSecret>, AppError> {
+    run_blocking(app, move |app, _| {
+        let expiries: Expiries = store::load(&app, EXPIRY_FILE)?;
+        Ok(expiring_within(
+            &expiries,
+            i64::from(days),
+            Utc::now().timestamp_millis(),
+        ))
+    })
+    .await
+}
@@ -0,0 +1,146 @@
+use crate::ipc_path::decode_path;
+use crate::{ensure_allowed_path, normalize_path, run_blocking, AppError, AppState};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::AppHandle;
+
+/// Editors that can be launched on a file. Only known launchers are accepted so the
+/// webview cannot run arbitrary programs.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Editor {
+    /// The application registered for the file type.
+    #[default]
+    System,
+    Vscode,
+    Cursor,
+    Sublime,
+    Zed,
+}
+
+impl Editor {
+    fn launcher(self) -> Option<&'static str> {
+        let name = match self {
+            Editor::System => return None,
+            Editor::Vscode => "code",
+            Editor::Cursor => "cursor",
+            Editor::Sublime => "subl",
+            Editor::Zed => "zed",
+        };
+        Some(name)
+    }
+}
+
+fn allowed_file(state: &AppState, path: &str) -> Result<PathBuf, AppError> {
+    let path_buf = decode_path(path);
+    ensure_allowed_path(state, &path_buf)?;
+    normalize_path(&path_buf)
+}
+
+fn spawn(command: &mut Command, what: &str) -> Result<(), AppError> {
+    command
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| AppError::io(e.kind().into(), format!("Could not open {}: {}", what, e)))
+}
+
+#[cfg(target_os = "macos")]
+fn system_open(path: &Path) -> Command {
+    let mut command = Command::new("open");
+    command.arg(path);
+    command
+}
+
+#[cfg(target_os = "windows")]
+fn system_open(path: &Path) -> Command {
+    let mut command = Command::new("explorer");
+    command.arg(path);
+    command
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn system_open(path: &Path) -> Command {
+    let mut command = Command::new("xdg-open");
+    command.arg(path);
+    command
+}
+
+#[cfg(target_os = "macos")]
+fn reveal_command(path: &Path) -> Command {
+    let mut command = Command::new("open");
+    command.arg("-R").arg(path);
+    command
+}
+
+#[cfg(target_os = "windows")]
+fn reveal_command(path: &Path) -> Command {
+    let mut select = std::ffi::OsString::from("/select,");
+    select.push(path);
+    let mut command = Command::new("explorer");
+    command.arg(select);
+    command
+}
+
+/// Asks the desktop's file manager to select the file over D-Bus; most Linux file
+/// managers implement `org.freedesktop.FileManager1`.
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn reveal_command(path: &Path) -> Command {
+    let uri = format!(
+        "file://{}",
+        urlencoding::encode(&path.to_string_lossy()).replace("%2F", "/")
+    );
+    let mut command = Command::new("dbus-send");
+    command.args([
+        "--session",
+        "--dest=org.freedesktop.FileManager1",
+        "--type=method_call",
+        "/org/freedesktop/FileManager1",
+        "org.freedesktop.FileManager1.ShowItems",
+        &format!("array:string:{}", uri),
+        "string:",
+    ]);
+    command
+}
+
+/// Shows the file selected in Finder, Explorer or the Linux file manager. Falls back
+/// to opening its folder when the file manager cannot select items.
+#[tauri::command]
+pub async fn reveal_in_file_manager(app: AppHandle, path: String) -> Result<(), AppError> {
+    run_blocking(app, move |_, state| {
+        let path = allowed_file(&state, &path)?;
+        // Explorer exits with 1 even when it selected the file.
+        let revealed = reveal_command(&path)
+            .status()
+            .map(|status| status.success() || cfg!(target_os = "windows"))
+            .unwrap_or(false);
+        if revealed {
+            return Ok(());
+        }
+        let folder = path.parent().unwrap_or(&path);
+        spawn(&mut system_open(folder), "the file manager")
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn open_in_external_editor(
+    app: AppHandle,
+    path: String,
+    editor: Option<Editor>,
+) -> Result<(), AppError> {
+    run_blocking(app, move |_, state| {
+        let path = allowed_file(&state, &path)?;
+        let editor = editor.unwrap_or_default();
+        match editor.launcher() {
+            // Editor launchers are `.cmd` shims on Windows, which need the shell.
+            Some(launcher) if cfg!(target_os = "windows") => spawn(
+                Command::new("cmd").arg("/C").arg(launcher).arg(&path),
+                launcher,
+            ),
+            Some(launcher) => spawn(Command::new(launcher).arg(&path), launcher),
+            None => spawn(&mut system_open(&path), "the file"),
+        }
+    })
+    .await
+}
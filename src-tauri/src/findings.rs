@@ -0,0 +1,180 @@
+use crate::io_error::IoCode;
+use crate::ipc_path::decode_path;
+use crate::lint::{lint_paths, Diagnostic, Severity};
+use crate::report::EnvReport;
+use crate::{app_lock, files_in_group, run_blocking, secrets, AppError, AppState};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const ROOT_BASE_ID: &str = "ROOT";
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FindingsFormat {
+    Sarif,
+    Json,
+}
+
+/// Allowed files grouped by folder, so project-wide lint rules stay within a project.
+fn projects(state: &AppState) -> BTreeMap<PathBuf, Vec<PathBuf>> {
+    let allowed = state.allowed_files.read();
+    let mut projects: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+    for path in allowed.iter() {
+        let folder = path.parent().unwrap_or(path).to_path_buf();
+        projects.entry(folder).or_default().push(path.clone());
+    }
+    for files in projects.values_mut() {
+        files.sort();
+    }
+    projects
+}
+
+fn collect(state: &AppState, paths: Vec<PathBuf>) -> Result<Vec<Diagnostic>, AppError> {
+    let mut diagnostics = lint_paths(state, paths.clone());
+    diagnostics.extend(secrets::scan_paths(state, paths)?);
+    Ok(diagnostics)
+}
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    }
+}
+
+/// Forward-slash path with each segment percent-encoded; drive letters stay as is.
+fn uri_path(path: &Path) -> String {
+    path.to_string_lossy()
+        .trim_start_matches(r"\\?\")
+        .replace('\\', "/")
+        .split('/')
+        .map(|segment| {
+            if segment.ends_with(':') {
+                segment.to_string()
+            } else {
+                urlencoding::encode(segment).into_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn file_uri(path: &Path) -> String {
+    format!("file:///{}", uri_path(path).trim_start_matches('/'))
+}
+
+/// A URI relative to the scanned root when the file is inside it, else a file URI.
+fn artifact_location(path: &str, root: Option<&Path>) -> Value {
+    let path = decode_path(path);
+    match root.and_then(|root| path.strip_prefix(root).ok()) {
+        Some(relative) => json!({ "uri": uri_path(relative), "uriBaseId": ROOT_BASE_ID }),
+        None => json!({ "uri": file_uri(&path) }),
+    }
+}
+
+fn to_sarif(diagnostics: &[Diagnostic], root: Option<&Path>) -> Value {
+    let mut rules: BTreeMap<&str, Severity> = BTreeMap::new();
+    for diagnostic in diagnostics {
+        rules
+            .entry(diagnostic.rule.as_str())
+            .or_insert(diagnostic.severity);
+    }
+    let rules: Vec<Value> = rules
+        .into_iter()
+        .map(|(rule, severity)| {
+            json!({
+                "id": rule,
+                "defaultConfiguration": { "level": sarif_level(severity) },
+            })
+        })
+        .collect();
+    let results: Vec<Value> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let mut location = json!({
+                "artifactLocation": artifact_location(&diagnostic.path, root),
+            });
+            if let Some(line) = diagnostic.line {
+                location["region"] = json!({ "startLine": line });
+            }
+            json!({
+                "ruleId": diagnostic.rule,
+                "level": sarif_level(diagnostic.severity),
+                "message": { "text": diagnostic.message },
+                "locations": [{ "physicalLocation": location }],
+            })
+        })
+        .collect();
+
+    let mut run = json!({
+        "tool": {
+            "driver": {
+                "name": "EnvShelf",
+                "version": env!("CARGO_PKG_VERSION"),
+                "rules": rules,
+            }
+        },
+        "results": results,
+    });
+    if let Some(root) = root {
+        let mut base_ids = Map::new();
+        base_ids.insert(
+            ROOT_BASE_ID.to_string(),
+            json!({ "uri": format!("{}/", file_uri(root)) }),
+        );
+        run["originalUriBaseIds"] = Value::Object(base_ids);
+    }
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [run],
+    })
+}
+
+/// Exports lint and secret-scan findings for one project, or for every scanned
+/// project when `group_id` is omitted, as SARIF 2.1.0 or a plain JSON array.
+#[tauri::command]
+pub async fn export_findings(
+    app: AppHandle,
+    group_id: Option<String>,
+    format: FindingsFormat,
+) -> Result<EnvReport, AppError> {
+    run_blocking(app, move |_, state| {
+        app_lock::ensure_unlocked(&state)?;
+        let diagnostics = match &group_id {
+            Some(group_id) => collect(&state, files_in_group(&state, group_id)?)?,
+            None => {
+                let mut diagnostics = Vec::new();
+                for paths in projects(&state).into_values() {
+                    diagnostics.extend(collect(&state, paths)?);
+                }
+                diagnostics
+            }
+        };
+        let root = state.root_path.read().clone();
+
+        let (value, file_name) = match format {
+            FindingsFormat::Sarif => (
+                to_sarif(&diagnostics, root.as_deref()),
+                "envshelf-findings.sarif",
+            ),
+            FindingsFormat::Json => (
+                serde_json::to_value(&diagnostics)
+                    .map_err(|e| AppError::io(IoCode::InvalidData, e.to_string()))?,
+                "envshelf-findings.json",
+            ),
+        };
+        let content = serde_json::to_string_pretty(&value)
+            .map_err(|e| AppError::io(IoCode::InvalidData, e.to_string()))?;
+        Ok(EnvReport {
+            file_name: file_name.to_string(),
+            content,
+        })
+    })
+    .await
+}
@@ -0,0 +1,180 @@
+use crate::ipc_path::decode_path;
+use crate::{
+    ensure_allowed_path, parse_env_text, read_env_text, run_blocking, serialize_env_line,
+    serialize_with_layout, unified_diff, write_contents, AppError, AppState, EnvLine, LinePosition,
+    WriteOptions, WritePreview,
+};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteChar {
+    #[default]
+    Keep,
+    Double,
+    Single,
+}
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FormatStyle {
+    pub quotes: QuoteChar,
+    /// Writes `KEY = value` instead of `KEY=value`.
+    pub spaced_separator: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormatResult {
+    preview: WritePreview,
+    written: bool,
+}
+
+pub fn quote_char_of(value: &str) -> Option<QuoteChar> {
+    let value = value.trim();
+    if value.len() < 2 {
+        return None;
+    }
+    if value.starts_with('"') && value.ends_with('"') {
+        Some(QuoteChar::Double)
+    } else if value.starts_with('\'') && value.ends_with('\'') {
+        Some(QuoteChar::Single)
+    } else {
+        None
+    }
+}
+
+/// Switches quoting only when the value means the same afterwards: escapes and `$`
+/// expansion behave differently inside single and double quotes, so those are kept.
+fn convert_quotes(value: &str, target: QuoteChar) -> String {
+    let current = quote_char_of(value);
+    let inner = match current {
+        Some(_) => &value[1..value.len() - 1],
+        None => value,
+    };
+    let convertible = match (current, target) {
+        (_, QuoteChar::Keep) => false,
+        (Some(from), to) if from == to => false,
+        _ if value.starts_with('`') => false,
+        (Some(QuoteChar::Single), QuoteChar::Double) => !inner.contains(['"', '\\', '$']),
+        (None, QuoteChar::Double) => !inner.contains(['"', '\\']),
+        (Some(QuoteChar::Double), QuoteChar::Single) => !inner.contains(['\'', '\\', '$']),
+        (None, QuoteChar::Single) => !inner.contains(['\'', '$']),
+        _ => false,
+    };
+    if !convertible {
+        return value.to_string();
+    }
+    let quote = if target == QuoteChar::Double {
+        '"'
+    } else {
+        '\''
+    };
+    format!("{}{}{}", quote, inner, quote)
+}
+
+/// Normalizes one line: trailing whitespace is dropped and key/value lines are rebuilt
+/// with the chosen separator and quoting. Comments keep their text.
+pub fn format_line(line: &EnvLine, style: FormatStyle) -> EnvLine {
+    match line {
+        EnvLine::Blank { position } => EnvLine::Blank {
+            position: position.clone(),
+        },
+        EnvLine::Comment { raw, position } => EnvLine::Comment {
+            raw: raw.trim_end().to_string(),
+            position: position.clone(),
+        },
+        EnvLine::Invalid {
+            raw,
+            reason,
+            severity,
+            message,
+            position,
+        } => EnvLine::Invalid {
+            raw: raw.trim_end().to_string(),
+            reason: *reason,
+            severity: *severity,
+            message: message.clone(),
+            position: position.clone(),
+        },
+        EnvLine::Unset { key, position, .. } => EnvLine::Unset {
+            key: key.clone(),
+            raw: None,
+            position: LinePosition::line(position.line_number),
+        },
+        EnvLine::Append {
+            key,
+            value,
+            inline_comment,
+            position,
+            ..
+        } => EnvLine::Append {
+            key: key.clone(),
+            value: convert_quotes(value.trim(), style.quotes),
+            inline_comment: inline_comment.clone(),
+            raw: None,
+            position: LinePosition::line(position.line_number),
+        },
+        EnvLine::Kv {
+            key,
+            value,
+            has_export,
+            inline_comment,
+            description,
+            value_type,
+            dangerous,
+            position,
+            ..
+        } => EnvLine::Kv {
+            key: key.clone(),
+            value: convert_quotes(value.trim(), style.quotes),
+            has_export: *has_export,
+            separator: if style.spaced_separator { " = " } else { "=" }.to_string(),
+            inline_comment: inline_comment.clone(),
+            description: description.clone(),
+            value_type: *value_type,
+            dangerous: *dangerous,
+            raw: None,
+            position: LinePosition::line(position.line_number),
+        },
+    }
+}
+
+/// The text `line` would have after formatting, used for lint suggestions.
+pub fn formatted_text(line: &EnvLine, style: FormatStyle) -> String {
+    serialize_env_line(&format_line(line, style))
+}
+
+/// Formats a whole file while keeping comments and line order. Returns a diff preview
+/// and writes the result only when `options` is provided.
+#[tauri::command]
+pub async fn format_env_file(
+    app: AppHandle,
+    path: String,
+    style: Option<FormatStyle>,
+    options: Option<WriteOptions>,
+) -> Result<FormatResult, AppError> {
+    run_blocking(app, move |_, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        let style = style.unwrap_or_default();
+
+        let contents = read_env_text(&state, &path_buf)?;
+        let (lines, layout) = parse_env_text(&contents);
+        let lines: Vec<EnvLine> = lines.iter().map(|line| format_line(line, style)).collect();
+        let formatted = serialize_with_layout(&lines, layout);
+        let preview = unified_diff(&path, &contents, &formatted);
+
+        let written = match options {
+            Some(options) if preview.changed => {
+                write_contents(&state, &path_buf, &formatted, &options)?;
+                true
+            }
+            _ => false,
+        };
+
+        Ok(FormatResult { preview, written })
+    })
+    .await
+}
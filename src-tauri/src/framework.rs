@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Framework {
+    NextJs,
+    Vite,
+    Cra,
+    Rails,
+    Django,
+    Laravel,
+    Spring,
+    Generic,
+}
+
+fn file_contains(path: &Path, needle: &str) -> bool {
+    fs::read_to_string(path)
+        .map(|contents| contents.contains(needle))
+        .unwrap_or(false)
+}
+
+fn has_any(folder: &Path, names: &[&str]) -> bool {
+    names.iter().any(|name| folder.join(name).exists())
+}
+
+/// Detects the framework of a project folder from well-known marker files.
+pub fn detect_framework(folder: &Path) -> Option<Framework> {
+    if has_any(
+        folder,
+        &["next.config.js", "next.config.mjs", "next.config.ts"],
+    ) {
+        return Some(Framework::NextJs);
+    }
+    if has_any(
+        folder,
+        &[
+            "vite.config.js",
+            "vite.config.mjs",
+            "vite.config.ts",
+            "vite.config.mts",
+        ],
+    ) {
+        return Some(Framework::Vite);
+    }
+    if file_contains(&folder.join("package.json"), "\"react-scripts\"") {
+        return Some(Framework::Cra);
+    }
+    if folder.join("manage.py").is_file() {
+        return Some(Framework::Django);
+    }
+    if folder.join("artisan").is_file() && folder.join("composer.json").is_file() {
+        return Some(Framework::Laravel);
+    }
+    if folder.join("Gemfile").is_file() && has_any(folder, &["config/application.rb", "bin/rails"])
+    {
+        return Some(Framework::Rails);
+    }
+    let spring_build = ["pom.xml", "build.gradle", "build.gradle.kts"]
+        .iter()
+        .any(|name| file_contains(&folder.join(name), "spring-boot"));
+    if spring_build {
+        return Some(Framework::Spring);
+    }
+    None
+}
+
+/// Env file names in precedence order (highest first) for a framework and mode.
+/// `None` for frameworks that do not merge env files in a defined order: Django and
+/// Spring Boot read no `.env` files themselves, and Laravel reads `.env.<APP_ENV>`
+/// instead of `.env` rather than on top of it.
+pub fn load_order(framework: Framework, mode: &str) -> Option<Vec<String>> {
+    let mode_local = format!(".env.{}.local", mode);
+    let mode_file = format!(".env.{}", mode);
+    // Next.js, CRA and dotenv-rails skip `.env.local` in test so results stay reproducible.
+    let include_local = mode != "test";
+
+    let order: Vec<String> = match framework {
+        Framework::Vite => vec![mode_local, mode_file, ".env.local".to_string()],
+        Framework::NextJs | Framework::Cra | Framework::Rails | Framework::Generic => {
+            let mut order = vec![mode_local];
+            if include_local {
+                order.push(".env.local".to_string());
+            }
+            order.push(mode_file);
+            order
+        }
+        Framework::Django | Framework::Laravel | Framework::Spring => return None,
+    };
+    Some(
+        order
+            .into_iter()
+            .chain(std::iter::once(".env".to_string()))
+            .collect(),
+    )
+}
@@ -0,0 +1,133 @@
+use crate::run_blocking;
+use crate::AppError;
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine as _,
+};
+use rand::{distributions::Alphanumeric, rngs::OsRng, Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+const DEFAULT_SECRET_BYTES: usize = 32;
+const MAX_SECRET_BYTES: usize = 1024;
+const DEFAULT_BCRYPT_COST: u32 = 12;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GeneratorKind {
+    Hex,
+    Base64,
+    Base64Url,
+    Uuid,
+    Bcrypt,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratorOptions {
+    #[serde(default)]
+    bytes: Option<usize>,
+    #[serde(default)]
+    username: Option<String>,
+    #[serde(default)]
+    password: Option<String>,
+    #[serde(default)]
+    cost: Option<u32>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedValue {
+    kind: GeneratorKind,
+    value: String,
+    /// Plain-text password when the generator had to create one (bcrypt without input).
+    plaintext: Option<String>,
+}
+
+fn random_bytes(count: usize) -> Result<Vec<u8>, AppError> {
+    if count == 0 || count > MAX_SECRET_BYTES {
+        return Err(AppError::InvalidInput(format!(
+            "Byte length must be between 1 and {}",
+            MAX_SECRET_BYTES
+        )));
+    }
+    let mut buffer = vec![0u8; count];
+    OsRng.fill_bytes(&mut buffer);
+    Ok(buffer)
+}
+
+fn uuid_v4() -> String {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+fn bcrypt_entry(options: &GeneratorOptions) -> Result<(String, Option<String>), AppError> {
+    let (password, plaintext) = match &options.password {
+        Some(password) if !password.is_empty() => (password.clone(), None),
+        _ => {
+            let generated: String = OsRng
+                .sample_iter(&Alphanumeric)
+                .take(24)
+                .map(char::from)
+                .collect();
+            (generated.clone(), Some(generated))
+        }
+    };
+    let cost = options.cost.unwrap_or(DEFAULT_BCRYPT_COST);
+    let hash = bcrypt::hash_with_result(&password, cost)
+        .map_err(|e| AppError::InvalidInput(e.to_string()))?
+        .format_for_version(bcrypt::Version::TwoY);
+    let value = match &options.username {
+        Some(username) if !username.is_empty() => format!("{}:{}", username, hash),
+        _ => hash,
+    };
+    Ok((value, plaintext))
+}
+
+pub fn generate(
+    kind: GeneratorKind,
+    options: &GeneratorOptions,
+) -> Result<GeneratedValue, AppError> {
+    let bytes = options.bytes.unwrap_or(DEFAULT_SECRET_BYTES);
+    let (value, plaintext) = match kind {
+        GeneratorKind::Hex => (
+            random_bytes(bytes)?
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect::<String>(),
+            None,
+        ),
+        GeneratorKind::Base64 => (STANDARD.encode(random_bytes(bytes)?), None),
+        GeneratorKind::Base64Url => (URL_SAFE_NO_PAD.encode(random_bytes(bytes)?), None),
+        GeneratorKind::Uuid => (uuid_v4(), None),
+        GeneratorKind::Bcrypt => bcrypt_entry(options)?,
+    };
+    Ok(GeneratedValue {
+        kind,
+        value,
+        plaintext,
+    })
+}
+
+#[tauri::command]
+pub async fn generate_value(
+    app: AppHandle,
+    kind: GeneratorKind,
+    options: Option<GeneratorOptions>,
+) -> Result<GeneratedValue, AppError> {
+    run_blocking(app, move |_, _| {
+        generate(kind, &options.unwrap_or_default())
+    })
+    .await
+}
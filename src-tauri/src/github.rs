@@ -0,0 +1,122 @@
+use crate::direnv::shell_quote;
+use crate::interop::{read_values, ExportOutput};
+use crate::io_error::IoCode;
+use crate::ipc_path::decode_path;
+use crate::{run_blocking, AppError, AppState};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use crypto_box::PublicKey;
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use tauri::AppHandle;
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GithubSecretsFormat {
+    /// `gh secret set` command lines.
+    #[default]
+    Cli,
+    /// Sealed-box encrypted bodies for `PUT /repos/{owner}/{repo}/actions/secrets/{name}`.
+    Rest,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GithubSecretsOptions {
+    format: GithubSecretsFormat,
+    /// `owner/repo`; the CLI falls back to the current repository when omitted.
+    repo: Option<String>,
+    /// Deployment environment for environment-level secrets.
+    environment: Option<String>,
+    /// Base64 repository public key and its id, from `GET .../actions/secrets/public-key`.
+    public_key: Option<String>,
+    key_id: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GithubSecretsExport {
+    #[serde(flatten)]
+    output: ExportOutput,
+    missing: Vec<String>,
+}
+
+fn cli_line(key: &str, value: &str, options: &GithubSecretsOptions) -> String {
+    let mut line = format!("printf '%s' {} | gh secret set {}", shell_quote(value), key);
+    if let Some(repo) = &options.repo {
+        line.push_str(&format!(" --repo {}", shell_quote(repo)));
+    }
+    if let Some(environment) = &options.environment {
+        line.push_str(&format!(" --env {}", shell_quote(environment)));
+    }
+    line.push('\n');
+    line
+}
+
+fn parse_public_key(encoded: &str) -> Result<PublicKey, AppError> {
+    let bytes = STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| AppError::InvalidInput(format!("Invalid public key: {}", e)))?;
+    let bytes: [u8; 32] = bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| AppError::InvalidInput("Public key must be 32 bytes".to_string()))?;
+    Ok(PublicKey::from(bytes))
+}
+
+/// Renders `keys` from an env file as GitHub Actions secrets, either as `gh secret
+/// set` commands or as encrypted REST bodies. Values are read from stdin by `gh` so
+/// they never appear in process arguments.
+#[tauri::command]
+pub async fn export_github_secrets(
+    app: AppHandle,
+    path: String,
+    keys: Vec<String>,
+    options: Option<GithubSecretsOptions>,
+) -> Result<GithubSecretsExport, AppError> {
+    run_blocking(app, move |_, state| {
+        let options = options.unwrap_or_default();
+        let values = read_values(&state, &decode_path(&path), Some(&keys))?;
+        let (selected, missing): (Vec<String>, Vec<String>) = keys
+            .into_iter()
+            .partition(|key| values.iter().any(|(existing, _)| existing == key));
+        let selected: Vec<&(String, String)> = values
+            .iter()
+            .filter(|(key, _)| selected.contains(key))
+            .collect();
+
+        let content = match options.format {
+            GithubSecretsFormat::Cli => selected
+                .iter()
+                .map(|(key, value)| cli_line(key, value, &options))
+                .collect(),
+            GithubSecretsFormat::Rest => {
+                let (Some(public_key), Some(key_id)) = (&options.public_key, &options.key_id)
+                else {
+                    return Err(AppError::InvalidInput(
+                        "The repository public key and key id are required".to_string(),
+                    ));
+                };
+                let public_key = parse_public_key(public_key)?;
+                let mut bodies = Map::new();
+                for (key, value) in &selected {
+                    let sealed = public_key.seal(&mut OsRng, value.as_bytes()).map_err(|_| {
+                        AppError::CryptoError("Failed to encrypt secret".to_string())
+                    })?;
+                    bodies.insert(
+                        key.clone(),
+                        json!({ "encrypted_value": STANDARD.encode(sealed), "key_id": key_id }),
+                    );
+                }
+                serde_json::to_string_pretty(&Value::Object(bodies))
+                    .map_err(|e| AppError::io(IoCode::InvalidData, e.to_string()))?
+            }
+        };
+
+        Ok(GithubSecretsExport {
+            output: ExportOutput::new(content, selected.len()),
+            missing,
+        })
+    })
+    .await
+}
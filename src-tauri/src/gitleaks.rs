@@ -0,0 +1,277 @@
+use crate::ipc_path::decode_path;
+use crate::lint::Severity;
+use crate::secrets::{CompiledRule, SecretRule};
+use crate::{app_lock, run_blocking, store, AppError, AppState};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+use tauri::{AppHandle, Manager, State};
+
+const GITLEAKS_FILE: &str = "gitleaks-settings.json";
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GitleaksSettings {
+    /// A gitleaks `.toml` config; re-read whenever the file changes.
+    config_path: Option<String>,
+    /// Use only the config's rules instead of adding them to the built-in ones.
+    replace_builtin: bool,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Allowlist {
+    regexes: Vec<String>,
+    stopwords: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Rule {
+    id: String,
+    #[serde(default)]
+    description: String,
+    regex: Option<String>,
+    #[serde(default)]
+    keywords: Vec<String>,
+    #[serde(default)]
+    allowlist: Option<Allowlist>,
+    #[serde(default)]
+    allowlists: Vec<Allowlist>,
+}
+
+/// The parts of a gitleaks config the scanner understands. `[extend]`, path rules,
+/// entropy thresholds and secret groups are ignored.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Config {
+    rules: Vec<Rule>,
+    allowlist: Option<Allowlist>,
+    allowlists: Vec<Allowlist>,
+}
+
+#[derive(Default)]
+pub struct GitleaksRules {
+    settings: GitleaksSettings,
+    pub rules: Vec<CompiledRule>,
+    /// Rule ids that could not be used, e.g. Go regex syntax Rust does not support.
+    skipped: Vec<String>,
+    loaded_at: Option<SystemTime>,
+}
+
+impl GitleaksRules {
+    pub fn replaces_builtin(&self) -> bool {
+        self.settings.replace_builtin && !self.rules.is_empty()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitleaksStatus {
+    config_path: Option<String>,
+    replace_builtin: bool,
+    rule_count: usize,
+    skipped: Vec<String>,
+}
+
+fn status(rules: &GitleaksRules) -> GitleaksStatus {
+    GitleaksStatus {
+        config_path: rules.settings.config_path.clone(),
+        replace_builtin: rules.settings.replace_builtin,
+        rule_count: rules.rules.len(),
+        skipped: rules.skipped.clone(),
+    }
+}
+
+/// Allowlist regexes plus stopwords, which gitleaks also matches against the secret.
+fn allowlist_regexes<'a>(lists: impl Iterator<Item = &'a Allowlist>) -> Option<Vec<Regex>> {
+    let mut regexes = Vec::new();
+    for list in lists {
+        for pattern in &list.regexes {
+            regexes.push(Regex::new(pattern).ok()?);
+        }
+        for word in &list.stopwords {
+            regexes.push(Regex::new(&format!("(?i){}", regex::escape(word))).ok()?);
+        }
+    }
+    Some(regexes)
+}
+
+fn parse(contents: &str) -> Result<(Vec<CompiledRule>, Vec<String>), AppError> {
+    let config: Config = toml::from_str(contents)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid gitleaks config: {}", e)))?;
+    let global: Vec<&Allowlist> = config
+        .allowlist
+        .iter()
+        .chain(config.allowlists.iter())
+        .collect();
+
+    let mut rules = Vec::new();
+    let mut skipped = Vec::new();
+    for rule in config.rules {
+        let Some(pattern) = rule.regex.clone() else {
+            skipped.push(rule.id);
+            continue;
+        };
+        let lists = global
+            .iter()
+            .copied()
+            .chain(rule.allowlist.iter())
+            .chain(rule.allowlists.iter());
+        let compiled = allowlist_regexes(lists).and_then(|allowlist| {
+            let description = if rule.description.is_empty() {
+                rule.id.clone()
+            } else {
+                rule.description.clone()
+            };
+            CompiledRule::new(SecretRule {
+                id: rule.id.clone(),
+                description,
+                pattern,
+                severity: Severity::Error,
+            })
+            .ok()
+            .map(|compiled| compiled.with_context(&rule.keywords, allowlist))
+        });
+        match compiled {
+            Some(compiled) => rules.push(compiled),
+            None => skipped.push(rule.id),
+        }
+    }
+    Ok((rules, skipped))
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// Reloads the configured file when it changed since it was last read.
+pub fn refresh(rules: &mut GitleaksRules) -> Result<(), AppError> {
+    let Some(config_path) = rules.settings.config_path.clone() else {
+        return Ok(());
+    };
+    let path = decode_path(&config_path);
+    let current = modified(&path);
+    if current.is_some() && current == rules.loaded_at {
+        return Ok(());
+    }
+    let (compiled, skipped) = parse(&fs::read_to_string(&path)?)?;
+    rules.rules = compiled;
+    rules.skipped = skipped;
+    rules.loaded_at = current;
+    Ok(())
+}
+
+pub fn resume(app: &AppHandle) -> Result<(), AppError> {
+    let settings: GitleaksSettings = store::load(app, GITLEAKS_FILE)?;
+    let state = app.state::<AppState>();
+    let mut loaded = state.secret_rules.write();
+    loaded.gitleaks = GitleaksRules {
+        settings,
+        ..GitleaksRules::default()
+    };
+    refresh(&mut loaded.gitleaks)
+}
+
+#[tauri::command]
+pub async fn get_gitleaks_status(state: State<'_, AppState>) -> Result<GitleaksStatus, AppError> {
+    Ok(status(&state.secret_rules.read().gitleaks))
+}
+
+/// Uses the rules of a gitleaks config for secret scanning, so the app flags what CI
+/// flags. Passing no path stops using it.
+#[tauri::command]
+pub async fn set_gitleaks_config(
+    app: AppHandle,
+    config_path: Option<String>,
+    replace_builtin: Option<bool>,
+) -> Result<GitleaksStatus, AppError> {
+    run_blocking(app, move |app, state| {
+        app_lock::ensure_unlocked(&state)?;
+        let mut gitleaks = GitleaksRules {
+            settings: GitleaksSettings {
+                config_path,
+                replace_builtin: replace_builtin.unwrap_or(false),
+            },
+            ..GitleaksRules::default()
+        };
+        refresh(&mut gitleaks)?;
+        store::save(&app, GITLEAKS_FILE, &gitleaks.settings)?;
+        let status = status(&gitleaks);
+        state.secret_rules.write().gitleaks = gitleaks;
+        Ok(status)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_env_lines;
+    use crate::secrets::scan_lines;
+
+    const CONFIG: &str = r#"
+[allowlist]
+stopwords = ["example"]
+
+[[rules]]
+id = "acme-token"
+description = "ACME token"
+regex = '''acme_[a-z0-9]{8}'''
+keywords = ["TOKEN"]
+
+[[rules]]
+id = "no-description"
+regex = '''zz_[0-9]{4}'''
+[rules.allowlist]
+regexes = ['''zz_0000''']
+
+[[rules]]
+id = "lookahead"
+regex = '''x(?=y)'''
+
+[[rules]]
+id = "path-only"
+path = '''\.pem$'''
+"#;
+
+    fn flagged_keys(text: &str) -> Vec<String> {
+        let (rules, _) = parse(CONFIG).unwrap();
+        scan_lines(".env", &parse_env_lines(text), &rules)
+            .into_iter()
+            .filter_map(|diagnostic| diagnostic.key)
+            .collect()
+    }
+
+    #[test]
+    fn skips_rules_the_scanner_cannot_run() {
+        let (rules, skipped) = parse(CONFIG).unwrap();
+        let ids: Vec<&str> = rules
+            .iter()
+            .map(|compiled| compiled.rule.id.as_str())
+            .collect();
+        assert_eq!(ids, ["acme-token", "no-description"]);
+        assert_eq!(rules[1].rule.description, "no-description");
+        assert_eq!(skipped, ["lookahead", "path-only"]);
+    }
+
+    #[test]
+    fn applies_keywords_and_allowlists() {
+        assert_eq!(
+            flagged_keys(concat!(
+                "ACME_TOKEN=acme_abcd1234\n",
+                "OTHER=acme_abcd1234\n",
+                "DEMO_TOKEN=acme_example1\n",
+                "Z=zz_1234\n",
+                "Z0=zz_0000",
+            )),
+            ["ACME_TOKEN", "Z"]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_toml() {
+        assert!(parse("[[rules]\nid =").is_err());
+    }
+}
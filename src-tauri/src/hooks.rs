@@ -0,0 +1,143 @@
+use crate::ipc_path::encode_path;
+use crate::{files_in_group, run_blocking, AppError, AppState, EXAMPLE_SUFFIXES};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/// First line after the shebang; marks hooks this app may overwrite or remove.
+const HOOK_MARKER: &str = "# Installed by env-shelf: blocks commits of local env files.";
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookStatus {
+    hook_path: String,
+    installed: bool,
+}
+
+/// The git directory of the repository containing `folder`. Worktrees and
+/// submodules use a `.git` file pointing at the real directory.
+fn git_dir(folder: &Path) -> Result<PathBuf, AppError> {
+    for dir in folder.ancestors() {
+        let candidate = dir.join(".git");
+        if candidate.is_dir() {
+            return Ok(candidate);
+        }
+        if candidate.is_file() {
+            let contents = fs::read_to_string(&candidate)?;
+            let target = contents
+                .trim()
+                .strip_prefix("gitdir:")
+                .map(str::trim)
+                .ok_or_else(|| AppError::InvalidInput("Unrecognized .git file".to_string()))?;
+            return Ok(dir.join(target));
+        }
+    }
+    Err(AppError::InvalidInput(
+        "The project is not inside a git repository".to_string(),
+    ))
+}
+
+fn hook_path(state: &AppState, group_id: &str) -> Result<PathBuf, AppError> {
+    let folder = files_in_group(state, group_id)?
+        .first()
+        .and_then(|path| path.parent())
+        .map(Path::to_path_buf)
+        .ok_or(AppError::GroupNotFound)?;
+    Ok(git_dir(&folder)?.join("hooks").join("pre-commit"))
+}
+
+fn is_own_hook(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .map(|contents| contents.contains(HOOK_MARKER))
+        .unwrap_or(false)
+}
+
+/// Matches the file names discovery treats as env files; example files stay allowed.
+fn hook_script() -> String {
+    let examples: Vec<&str> = EXAMPLE_SUFFIXES
+        .iter()
+        .map(|suffix| suffix.trim_start_matches('.'))
+        .collect();
+    format!(
+        r#"#!/bin/sh
+{marker}
+blocked=$(git diff --cached --name-only --diff-filter=ACR \
+  | grep -E '(^|/)(\.env(\..+)?|\.envrc|\.flaskenv|[^/]+\.env)$' \
+  | grep -vE '\.({examples})$')
+if [ -n "$blocked" ]; then
+  echo "Refusing to commit env files:" >&2
+  echo "$blocked" | sed 's/^/  /' >&2
+  echo "Unstage them, or use --no-verify to commit anyway." >&2
+  exit 1
+fi
+"#,
+        marker = HOOK_MARKER,
+        examples = examples.join("|")
+    )
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<(), AppError> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<(), AppError> {
+    Ok(())
+}
+
+/// Writes a pre-commit hook that rejects staged env files other than examples.
+/// An existing hook is only replaced if this app installed it.
+#[tauri::command]
+pub async fn install_precommit_hook(
+    app: AppHandle,
+    group_id: String,
+) -> Result<HookStatus, AppError> {
+    run_blocking(app, move |_, state| {
+        let path = hook_path(&state, &group_id)?;
+        if path.exists() && !is_own_hook(&path) {
+            return Err(AppError::InvalidInput(format!(
+                "A pre-commit hook already exists at {}",
+                path.display()
+            )));
+        }
+        if let Some(hooks_dir) = path.parent() {
+            fs::create_dir_all(hooks_dir)?;
+        }
+        fs::write(&path, hook_script())?;
+        make_executable(&path)?;
+        Ok(HookStatus {
+            hook_path: encode_path(&path),
+            installed: true,
+        })
+    })
+    .await
+}
+
+/// Removes the hook installed by `install_precommit_hook`; other hooks are left alone.
+#[tauri::command]
+pub async fn uninstall_precommit_hook(
+    app: AppHandle,
+    group_id: String,
+) -> Result<HookStatus, AppError> {
+    run_blocking(app, move |app, state| {
+        let path = hook_path(&state, &group_id)?;
+        if path.exists() {
+            if !is_own_hook(&path) {
+                return Err(AppError::InvalidInput(format!(
+                    "The pre-commit hook at {} was not installed by this app",
+                    path.display()
+                )));
+            }
+            fs::remove_file(&path)?;
+        }
+        Ok(HookStatus {
+            hook_path: encode_path(&path),
+            installed: false,
+        })
+    })
+    .await
+}
@@ -0,0 +1,147 @@
+use crate::ipc_path::decode_path;
+use crate::quoting::{decode_value, encode_literal};
+use crate::variants::insert_after_last_kv;
+use crate::{
+    ensure_allowed_path, find_kv_value, has_command_substitution, has_directives, infer_value_type,
+    parse_env_lines, parse_env_text, read_env_text, serialize_for_write, set_kv_value,
+    write_contents, AppError, AppState, EnvLine, LinePosition, WriteOptions,
+};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Outcome of importing key/value pairs from another tool into a local env file.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportResult {
+    path: String,
+    added: Vec<String>,
+    updated: Vec<String>,
+    unchanged: Vec<String>,
+    /// Keys that differ locally and were kept because `overwrite` was not set.
+    skipped: Vec<String>,
+    written: bool,
+}
+
+/// Rendered output of an exporter, ready to copy or save.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportOutput {
+    content: String,
+    key_count: usize,
+}
+
+impl ExportOutput {
+    pub fn new(content: String, key_count: usize) -> Self {
+        ExportOutput { content, key_count }
+    }
+}
+
+/// Decoded key/value pairs of parsed lines, applied top to bottom as a shell would:
+/// later definitions win but keep the position of the first one, `unset` drops a key
+/// and `KEY+=` appends. Appending to a key the file has not set starts from an empty
+/// value; the app's own environment never leaks into the result.
+pub fn decoded_values(lines: &[EnvLine]) -> Vec<(String, String)> {
+    let mut values: Vec<(String, String)> = Vec::new();
+    for line in lines {
+        match line {
+            EnvLine::Kv { key, value, .. } => {
+                let decoded = decode_value(value);
+                match values.iter_mut().find(|(existing, _)| existing == key) {
+                    Some(entry) => entry.1 = decoded,
+                    None => values.push((key.clone(), decoded)),
+                }
+            }
+            EnvLine::Append { key, value, .. } => {
+                let decoded = decode_value(value);
+                match values.iter_mut().find(|(existing, _)| existing == key) {
+                    Some(entry) => entry.1.push_str(&decoded),
+                    None => values.push((key.clone(), decoded)),
+                }
+            }
+            EnvLine::Unset { key, .. } => values.retain(|(existing, _)| existing != key),
+            _ => {}
+        }
+    }
+    values
+}
+
+/// Reads an allowed env file and returns its decoded values for exporters, limited
+/// to `keys` when given. Keys missing from the file are ignored.
+pub fn read_values(
+    state: &AppState,
+    path: &Path,
+    keys: Option<&[String]>,
+) -> Result<Vec<(String, String)>, AppError> {
+    ensure_allowed_path(state, path)?;
+    let contents = read_env_text(state, path)?;
+    let mut values = decoded_values(&parse_env_lines(&contents));
+    if let Some(keys) = keys {
+        values.retain(|(key, _)| keys.contains(key));
+    }
+    Ok(values)
+}
+
+pub fn new_kv_line(key: &str, value: String) -> EnvLine {
+    EnvLine::Kv {
+        key: key.to_string(),
+        value_type: infer_value_type(&value),
+        dangerous: has_command_substitution(&value),
+        value,
+        has_export: false,
+        separator: "=".to_string(),
+        inline_comment: None,
+        description: None,
+        raw: None,
+        position: LinePosition::default(),
+    }
+}
+
+/// Merges literal values into an allowed env file. Differing keys are replaced only
+/// with `overwrite`. Nothing is written unless `options` is provided.
+pub fn import_values(
+    state: &AppState,
+    path: &str,
+    entries: Vec<(String, String)>,
+    overwrite: bool,
+    options: Option<WriteOptions>,
+) -> Result<ImportResult, AppError> {
+    let path_buf = &decode_path(path);
+    ensure_allowed_path(state, path_buf)?;
+    let (mut lines, layout) = parse_env_text(&read_env_text(state, path_buf)?);
+
+    let mut result = ImportResult {
+        path: path.to_string(),
+        added: Vec::new(),
+        updated: Vec::new(),
+        unchanged: Vec::new(),
+        skipped: Vec::new(),
+        written: false,
+    };
+    for (key, value) in entries {
+        if has_directives(&lines, &key) {
+            result.skipped.push(key);
+            continue;
+        }
+        match find_kv_value(&lines, &key).map(decode_value) {
+            None => {
+                insert_after_last_kv(&mut lines, new_kv_line(&key, encode_literal(&value)));
+                result.added.push(key);
+            }
+            Some(current) if current == value => result.unchanged.push(key),
+            Some(_) if !overwrite => result.skipped.push(key),
+            Some(_) => {
+                set_kv_value(&mut lines, &key, &encode_literal(&value));
+                result.updated.push(key);
+            }
+        }
+    }
+
+    if let Some(options) = options {
+        if !result.added.is_empty() || !result.updated.is_empty() {
+            let content = serialize_for_write(state, path_buf, &lines, layout, &options)?;
+            write_contents(state, path_buf, &content, &options)?;
+            result.written = true;
+        }
+    }
+    Ok(result)
+}
@@ -0,0 +1,223 @@
+use crate::dialects::dialect_for;
+use crate::ipc_path::decode_path;
+use crate::quoting::Dialect;
+use crate::{
+    ensure_allowed_path, parse_env_lines, read_env_text, run_blocking, AppError, AppState, EnvLine,
+};
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::OnceLock;
+use tauri::AppHandle;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterpolationNode {
+    key: String,
+    line: usize,
+    references: Vec<String>,
+    referenced_by: Vec<String>,
+    /// The value runs a command on expansion; it must not be expanded without
+    /// an explicit confirmation.
+    dangerous: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnresolvedReference {
+    key: String,
+    reference: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterpolationGraph {
+    path: String,
+    nodes: Vec<InterpolationNode>,
+    unresolved: Vec<UnresolvedReference>,
+    cycles: Vec<Vec<String>>,
+}
+
+fn reference_regex() -> Option<&'static Regex> {
+    static REFERENCE_REGEX: OnceLock<Option<Regex>> = OnceLock::new();
+    REFERENCE_REGEX
+        .get_or_init(|| {
+            Regex::new(
+                r"\$\{([A-Za-z_][A-Za-z0-9_]*)(?:[:?+\-][^}]*)?\}|\$([A-Za-z_][A-Za-z0-9_]*)",
+            )
+            .ok()
+        })
+        .as_ref()
+}
+
+/// Returns the variable names referenced by a raw value. Single-quoted values and
+/// escaped dollars (`\$`) are literal in dotenv, so they yield no references.
+pub fn extract_references(value: &str) -> Vec<String> {
+    extract_references_for(value, Dialect::Dotenv)
+}
+
+/// Like `extract_references`, following the expansion rules of `dialect`:
+/// phpdotenv and python-dotenv only expand the braced `${VAR}` form.
+pub fn extract_references_for(value: &str, dialect: Dialect) -> Vec<String> {
+    let braced_only = matches!(dialect, Dialect::Phpdotenv | Dialect::Python);
+    let trimmed = value.trim();
+    if trimmed.starts_with('\'') {
+        return Vec::new();
+    }
+    let Some(regex) = reference_regex() else {
+        return Vec::new();
+    };
+    let mut references = Vec::new();
+    for caps in regex.captures_iter(trimmed) {
+        let Some(whole) = caps.get(0) else {
+            continue;
+        };
+        if trimmed[..whole.start()].ends_with('\\') {
+            continue;
+        }
+        if let Some(name) = caps.get(1).or_else(|| caps.get(2).filter(|_| !braced_only)) {
+            let name = name.as_str().to_string();
+            if !references.contains(&name) {
+                references.push(name);
+            }
+        }
+    }
+    references
+}
+
+/// Points the `$VAR` and `${VAR}` references in a raw value at the new names in
+/// `renames` (old name to new). Literal text (single quotes, `\$`) is left as is.
+pub fn rename_references(value: &str, renames: &HashMap<String, String>) -> String {
+    let Some(regex) = reference_regex() else {
+        return value.to_string();
+    };
+    if value.trim().starts_with('\'') {
+        return value.to_string();
+    }
+    regex
+        .replace_all(value, |caps: &Captures| {
+            let (Some(whole), Some(name)) = (caps.get(0), caps.get(1).or_else(|| caps.get(2)))
+            else {
+                return caps[0].to_string();
+            };
+            let new = match renames.get(name.as_str()) {
+                Some(new) if !value[..whole.start()].ends_with('\\') => new,
+                _ => return whole.as_str().to_string(),
+            };
+            format!(
+                "{}{}{}",
+                &value[whole.start()..name.start()],
+                new,
+                &value[name.end()..whole.end()]
+            )
+        })
+        .into_owned()
+}
+
+fn find_cycles(edges: &BTreeMap<String, Vec<String>>) -> Vec<Vec<String>> {
+    fn visit(
+        key: &str,
+        edges: &BTreeMap<String, Vec<String>>,
+        stack: &mut Vec<String>,
+        done: &mut BTreeSet<String>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        if let Some(position) = stack.iter().position(|entry| entry == key) {
+            cycles.push(stack[position..].to_vec());
+            return;
+        }
+        if done.contains(key) {
+            return;
+        }
+        stack.push(key.to_string());
+        for next in edges.get(key).into_iter().flatten() {
+            if edges.contains_key(next) {
+                visit(next, edges, stack, done, cycles);
+            }
+        }
+        stack.pop();
+        done.insert(key.to_string());
+    }
+
+    let mut cycles = Vec::new();
+    let mut done = BTreeSet::new();
+    for key in edges.keys() {
+        visit(key, edges, &mut Vec::new(), &mut done, &mut cycles);
+    }
+    cycles
+}
+
+pub fn build_graph(path: String, lines: &[EnvLine], dialect: Dialect) -> InterpolationGraph {
+    let mut edges: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut line_numbers: BTreeMap<String, usize> = BTreeMap::new();
+    let mut dangerous_keys: BTreeSet<String> = BTreeSet::new();
+    for (index, line) in lines.iter().enumerate() {
+        if let EnvLine::Kv {
+            key,
+            value,
+            dangerous,
+            ..
+        } = line
+        {
+            edges.insert(key.clone(), extract_references_for(value, dialect));
+            line_numbers.insert(key.clone(), index + 1);
+            if *dangerous {
+                dangerous_keys.insert(key.clone());
+            } else {
+                dangerous_keys.remove(key);
+            }
+        }
+    }
+
+    let mut referenced_by: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    let mut unresolved = Vec::new();
+    for (key, references) in &edges {
+        for reference in references {
+            if edges.contains_key(reference) {
+                referenced_by
+                    .entry(reference.as_str())
+                    .or_default()
+                    .push(key.clone());
+            } else {
+                unresolved.push(UnresolvedReference {
+                    key: key.clone(),
+                    reference: reference.clone(),
+                });
+            }
+        }
+    }
+
+    let nodes = edges
+        .iter()
+        .map(|(key, references)| InterpolationNode {
+            key: key.clone(),
+            line: line_numbers.get(key).copied().unwrap_or(0),
+            references: references.clone(),
+            referenced_by: referenced_by.get(key.as_str()).cloned().unwrap_or_default(),
+            dangerous: dangerous_keys.contains(key),
+        })
+        .collect();
+
+    InterpolationGraph {
+        path,
+        nodes,
+        unresolved,
+        cycles: find_cycles(&edges),
+    }
+}
+
+#[tauri::command]
+pub async fn interpolation_graph(
+    app: AppHandle,
+    path: String,
+) -> Result<InterpolationGraph, AppError> {
+    run_blocking(app, move |_, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+
+        let contents = read_env_text(&state, &path_buf)?;
+        let dialect = dialect_for(&state, &path_buf);
+        Ok(build_graph(path, &parse_env_lines(&contents), dialect))
+    })
+    .await
+}
@@ -0,0 +1,137 @@
+use crate::ipc_path::encode_path;
+use crate::AppError;
+use serde::Serialize;
+use std::fmt;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+
+/// What went wrong with a file or process operation, stable across releases so the
+/// frontend can branch on it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IoCode {
+    NotFound,
+    PermissionDenied,
+    AlreadyExists,
+    /// Content or app data that could not be read back or encoded.
+    InvalidData,
+    /// Something the app depends on, like its data directory, is not there right now.
+    Unavailable,
+    Other,
+}
+
+impl From<ErrorKind> for IoCode {
+    fn from(kind: ErrorKind) -> Self {
+        match kind {
+            ErrorKind::NotFound => IoCode::NotFound,
+            ErrorKind::PermissionDenied => IoCode::PermissionDenied,
+            ErrorKind::AlreadyExists => IoCode::AlreadyExists,
+            ErrorKind::InvalidData | ErrorKind::InvalidInput | ErrorKind::UnexpectedEof => {
+                IoCode::InvalidData
+            }
+            ErrorKind::Interrupted | ErrorKind::TimedOut | ErrorKind::WouldBlock => {
+                IoCode::Unavailable
+            }
+            _ => IoCode::Other,
+        }
+    }
+}
+
+impl IoCode {
+    fn hint(self) -> Option<&'static str> {
+        match self {
+            IoCode::NotFound => Some("The file may have been moved or deleted; rescan the folder."),
+            IoCode::PermissionDenied => {
+                Some("Check that your user account may read and write this file.")
+            }
+            IoCode::AlreadyExists => Some("Another file already uses this name."),
+            IoCode::Unavailable => Some("Try again in a moment."),
+            IoCode::InvalidData | IoCode::Other => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IoFailure {
+    code: IoCode,
+    message: String,
+    /// The file the operation was working on, when known.
+    path: Option<String>,
+    /// Name of the underlying `io::ErrorKind`, e.g. `PermissionDenied`.
+    kind: Option<String>,
+    hint: Option<String>,
+}
+
+impl IoFailure {
+    pub fn code(&self) -> IoCode {
+        self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    pub fn kind(&self) -> Option<&str> {
+        self.kind.as_deref()
+    }
+}
+
+impl fmt::Display for IoFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "{} ({})", self.message, path),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl From<io::Error> for IoFailure {
+    fn from(error: io::Error) -> Self {
+        let code = IoCode::from(error.kind());
+        IoFailure {
+            code,
+            message: error.to_string(),
+            path: None,
+            kind: Some(format!("{:?}", error.kind())),
+            hint: code.hint().map(str::to_string),
+        }
+    }
+}
+
+impl AppError {
+    /// An I/O error that did not come from an `io::Error`, such as a settings file that
+    /// no longer parses.
+    pub fn io(code: IoCode, message: impl Into<String>) -> Self {
+        AppError::IoError(IoFailure {
+            code,
+            message: message.into(),
+            path: None,
+            kind: None,
+            hint: code.hint().map(str::to_string),
+        })
+    }
+
+    /// Records which file an I/O error was about; other errors are returned unchanged.
+    pub fn with_path(mut self, path: &Path) -> Self {
+        if let AppError::IoError(failure) = &mut self {
+            failure.path = Some(encode_path(path));
+        }
+        self
+    }
+}
+
+/// Attaches the path an `io::Result` was working on when converting it to `AppError`.
+pub trait PathContext<T> {
+    fn at(self, path: &Path) -> Result<T, AppError>;
+}
+
+impl<T> PathContext<T> for io::Result<T> {
+    fn at(self, path: &Path) -> Result<T, AppError> {
+        self.map_err(|error| AppError::from(error).with_path(path))
+    }
+}
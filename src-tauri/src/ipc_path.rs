@@ -0,0 +1,62 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+/// Marks a path that is not valid Unicode. The rest is base64 of its raw bytes (UTF-16
+/// code units on Windows). NUL cannot occur in a real path, so plain paths never
+/// collide with it.
+const ENCODED_PREFIX: &str = "\0base64:";
+
+#[cfg(unix)]
+fn os_bytes(value: &OsStr) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+    value.as_bytes().to_vec()
+}
+
+#[cfg(unix)]
+fn os_string_from_bytes(bytes: Vec<u8>) -> Option<OsString> {
+    use std::os::unix::ffi::OsStringExt;
+    Some(OsString::from_vec(bytes))
+}
+
+#[cfg(windows)]
+fn os_bytes(value: &OsStr) -> Vec<u8> {
+    use std::os::windows::ffi::OsStrExt;
+    value.encode_wide().flat_map(u16::to_le_bytes).collect()
+}
+
+#[cfg(windows)]
+fn os_string_from_bytes(bytes: Vec<u8>) -> Option<OsString> {
+    use std::os::windows::ffi::OsStringExt;
+    if bytes.len() % 2 != 0 {
+        return None;
+    }
+    let wide: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    Some(OsString::from_wide(&wide))
+}
+
+/// Converts a path to a string for the frontend. Unicode paths pass through unchanged;
+/// others are encoded so they survive the round trip back into `decode_path`.
+pub fn encode_path(path: &Path) -> String {
+    match path.to_str() {
+        Some(text) => text.to_string(),
+        None => format!(
+            "{}{}",
+            ENCODED_PREFIX,
+            URL_SAFE_NO_PAD.encode(os_bytes(path.as_os_str()))
+        ),
+    }
+}
+
+/// Reads a path received from the frontend, undoing `encode_path`.
+pub fn decode_path(value: &str) -> PathBuf {
+    value
+        .strip_prefix(ENCODED_PREFIX)
+        .and_then(|encoded| URL_SAFE_NO_PAD.decode(encoded).ok())
+        .and_then(os_string_from_bytes)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(value))
+}
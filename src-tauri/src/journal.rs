@@ -0,0 +1,275 @@
+use crate::io_error::IoCode;
+use crate::ipc_path::{decode_path, encode_path};
+use crate::{
+    app_lock, ensure_allowed_path, hash_content, hash_path, run_blocking, store, unified_diff,
+    write_contents, AppError, AppState, WriteOptions, WritePreview,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tauri::AppHandle;
+
+const JOURNAL_DIR: &str = "journal";
+
+/// Set once on startup; writes are not journaled when the app data directory is
+/// unavailable.
+static JOURNAL_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// An intended write. The new content itself stays in the staged temp file next to the
+/// target, so the journal never holds a copy of the values.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JournalEntry {
+    id: String,
+    path: String,
+    temp_path: String,
+    content_hash: String,
+    previous_hash: Option<String>,
+    created_at: i64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JournalState {
+    /// The file still has its content from before the write.
+    Pending,
+    /// The write reached the disk before the interruption.
+    Applied,
+    /// The file changed in some other way since.
+    Diverged,
+    /// The staged content is gone or was changed, so the write cannot be re-applied.
+    Lost,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingWrite {
+    id: String,
+    path: String,
+    created_at: i64,
+    state: JournalState,
+    preview: WritePreview,
+}
+
+fn entry_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{}.json", id))
+}
+
+/// Creates the journal directory; called on app setup.
+pub fn init(app: &AppHandle) -> Result<(), AppError> {
+    let dir = store::app_data_dir(app)?.join(JOURNAL_DIR);
+    fs::create_dir_all(&dir)?;
+    let _ = JOURNAL_PATH.set(dir);
+    Ok(())
+}
+
+/// Durably records an intended write of `content`, staged at `temp_path`, before it
+/// touches the target file. Returns the entry id to pass to `complete` once the file is
+/// in place.
+pub fn record(path: &Path, temp_path: &Path, content: &str) -> Result<Option<String>, AppError> {
+    let Some(dir) = JOURNAL_PATH.get() else {
+        return Ok(None);
+    };
+    let created_at = Utc::now().timestamp_millis();
+    let id = format!("{}-{}", created_at, &hash_path(path)[..12]);
+    let entry = JournalEntry {
+        id: id.clone(),
+        path: encode_path(path),
+        temp_path: encode_path(temp_path),
+        content_hash: hash_content(content.as_bytes()),
+        previous_hash: fs::read(path).ok().map(|bytes| hash_content(&bytes)),
+        created_at,
+    };
+    let json =
+        serde_json::to_vec(&entry).map_err(|e| AppError::io(IoCode::InvalidData, e.to_string()))?;
+    let mut file = fs::File::create(entry_path(dir, &id))?;
+    file.write_all(&json)?;
+    file.sync_all()?;
+    Ok(Some(id))
+}
+
+/// Drops the journal entry of a finished (or rolled back) write.
+pub fn complete(id: Option<String>) {
+    if let (Some(dir), Some(id)) = (JOURNAL_PATH.get(), id) {
+        let _ = fs::remove_file(entry_path(dir, &id));
+    }
+}
+
+fn load_entry(id: &str) -> Result<JournalEntry, AppError> {
+    let dir = JOURNAL_PATH
+        .get()
+        .ok_or_else(|| AppError::io(IoCode::Unavailable, "Journal is unavailable"))?;
+    if id.contains(['/', '\\', '.']) {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid journal id: {}",
+            id
+        )));
+    }
+    let contents = fs::read_to_string(entry_path(dir, id))
+        .map_err(|_| AppError::InvalidInput(format!("Journal entry not found: {}", id)))?;
+    serde_json::from_str(&contents).map_err(|e| AppError::io(IoCode::InvalidData, e.to_string()))
+}
+
+/// The staged content of `entry`, if it is still what was journaled.
+fn read_staged(entry: &JournalEntry) -> Option<String> {
+    let content = fs::read_to_string(decode_path(&entry.temp_path)).ok()?;
+    (hash_content(content.as_bytes()) == entry.content_hash).then_some(content)
+}
+
+/// Removes the journal entry and its staged temp file.
+fn discard(entry: JournalEntry) {
+    let _ = fs::remove_file(decode_path(&entry.temp_path));
+    complete(Some(entry.id));
+}
+
+fn pending_write(entry: JournalEntry) -> PendingWrite {
+    let current = fs::read(decode_path(&entry.path)).ok();
+    let current_hash = current.as_deref().map(hash_content);
+    let current_text = current
+        .map(|bytes| String::from_utf8_lossy(&bytes).to_string())
+        .unwrap_or_default();
+    let (state, content) = if current_hash.as_deref() == Some(entry.content_hash.as_str()) {
+        (JournalState::Applied, current_text.clone())
+    } else {
+        match read_staged(&entry) {
+            Some(content) if current_hash == entry.previous_hash => {
+                (JournalState::Pending, content)
+            }
+            Some(content) => (JournalState::Diverged, content),
+            None => (JournalState::Lost, current_text.clone()),
+        }
+    };
+    PendingWrite {
+        preview: unified_diff(&entry.path, &current_text, &content),
+        id: entry.id,
+        path: entry.path,
+        created_at: entry.created_at,
+        state,
+    }
+}
+
+fn entries() -> Result<Vec<JournalEntry>, AppError> {
+    let Some(dir) = JOURNAL_PATH.get() else {
+        return Ok(Vec::new());
+    };
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Ok(contents) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if let Ok(entry) = serde_json::from_str::<JournalEntry>(&contents) {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+/// Temp files holding the content of journaled writes; the startup cleanup keeps them
+/// until the write is re-applied or discarded.
+pub fn staged_files() -> HashSet<PathBuf> {
+    entries()
+        .unwrap_or_default()
+        .iter()
+        .map(|entry| decode_path(&entry.temp_path))
+        .collect()
+}
+
+/// Lists writes that were journaled but never completed, e.g. after a crash mid-save.
+#[tauri::command]
+pub async fn list_pending_writes(app: AppHandle) -> Result<Vec<PendingWrite>, AppError> {
+    run_blocking(app, move |_, state| {
+        app_lock::ensure_unlocked(&state)?;
+        let mut pending: Vec<PendingWrite> = entries()?.into_iter().map(pending_write).collect();
+        pending.sort_by_key(|write| write.created_at);
+        Ok(pending)
+    })
+    .await
+}
+
+/// Re-applies an interrupted write. The file must be allowed again (after a scan).
+#[tauri::command]
+pub async fn reapply_pending_write(
+    app: AppHandle,
+    id: String,
+    options: WriteOptions,
+) -> Result<(), AppError> {
+    run_blocking(app, move |_, state| {
+        let entry = load_entry(&id)?;
+        let path = decode_path(&entry.path);
+        ensure_allowed_path(&state, &path)?;
+        let content = read_staged(&entry).ok_or_else(|| {
+            AppError::io(IoCode::NotFound, "The staged content of this write is gone")
+                .with_path(&path)
+        })?;
+        write_contents(&state, &path, &content, &options)?;
+        discard(entry);
+        Ok(())
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn discard_pending_write(app: AppHandle, id: String) -> Result<(), AppError> {
+    run_blocking(app, move |_, state| {
+        app_lock::ensure_unlocked(&state)?;
+        discard(load_entry(&id)?);
+        Ok(())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &Path, temp_path: &Path, content: &str) -> JournalEntry {
+        JournalEntry {
+            id: "1-abc".to_string(),
+            path: encode_path(path),
+            temp_path: encode_path(temp_path),
+            content_hash: hash_content(content.as_bytes()),
+            previous_hash: fs::read(path).ok().map(|bytes| hash_content(&bytes)),
+            created_at: 1,
+        }
+    }
+
+    #[test]
+    fn reports_where_an_interrupted_write_stopped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env");
+        let temp_path = dir.path().join(".env.tmp");
+        fs::write(&path, "A=1\n").unwrap();
+        fs::write(&temp_path, "A=2\n").unwrap();
+        let entry = entry(&path, &temp_path, "A=2\n");
+        let state = |entry: &JournalEntry| pending_write(entry.clone()).state;
+
+        assert!(state(&entry) == JournalState::Pending);
+        assert_eq!(read_staged(&entry).as_deref(), Some("A=2\n"));
+
+        fs::write(&path, "A=3\n").unwrap();
+        assert!(state(&entry) == JournalState::Diverged);
+
+        fs::write(&temp_path, "A=4\n").unwrap();
+        assert!(state(&entry) == JournalState::Lost);
+        assert!(read_staged(&entry).is_none());
+
+        fs::rename(&temp_path, &path).unwrap();
+        fs::write(&path, "A=2\n").unwrap();
+        assert!(state(&entry) == JournalState::Applied);
+    }
+
+    #[test]
+    fn the_journal_holds_no_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env");
+        let temp_path = dir.path().join(".env.tmp");
+        fs::write(&path, "A=1\n").unwrap();
+        let json = serde_json::to_string(&entry(&path, &temp_path, "SECRET=hunter2\n")).unwrap();
+        assert!(!json.contains("hunter2"));
+    }
+}
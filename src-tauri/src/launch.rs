@@ -0,0 +1,130 @@
+use crate::ipc_path::{decode_path, encode_path};
+use crate::{app_lock, normalize_path, scan_env_files, AppError, AppState, ScanResult};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::thread;
+use tauri::{AppHandle, Manager, State};
+
+const SCHEME: &str = "envshelf";
+pub const OPEN_PATH_EVENT: &str = "open-path";
+
+/// A path handed to the app from the command line or an `envshelf://open?path=` link.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenRequest {
+    root_path: String,
+    scan: Option<ScanResult>,
+    error: Option<String>,
+}
+
+/// Pulls the path out of `envshelf://open?path=<percent-encoded path>`.
+fn path_from_link(link: &str) -> Option<String> {
+    let query = link
+        .strip_prefix(SCHEME)?
+        .strip_prefix("://open")?
+        .trim_start_matches('/')
+        .strip_prefix('?')?;
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(name, _)| *name == "path")
+        .and_then(|(_, value)| urlencoding::decode(&value.replace('+', " ")).ok())
+        .map(|value| value.into_owned())
+}
+
+/// Resolves an argument to the directory to scan; a file opens its folder.
+fn resolve_root(argument: &str) -> Result<PathBuf, AppError> {
+    let raw = if argument.starts_with(SCHEME) {
+        path_from_link(argument)
+            .ok_or_else(|| AppError::InvalidInput(format!("Unsupported link: {}", argument)))?
+    } else {
+        argument.to_string()
+    };
+    let path = normalize_path(&decode_path(&raw)).map_err(|_| AppError::InvalidRootPath)?;
+    let root = if path.is_file() {
+        path.parent().map(Path::to_path_buf).unwrap_or(path)
+    } else {
+        path
+    };
+    if !root.is_dir() {
+        return Err(AppError::InvalidRootPath);
+    }
+    Ok(root)
+}
+
+pub fn focus_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn store_request(app: &AppHandle, request: &OpenRequest) {
+    *app.state::<AppState>().open_request.write() = Some(request.clone());
+}
+
+/// Validates the path, scans it in the background and focuses the window. The result
+/// is emitted and also kept until the frontend takes it, since on startup the event
+/// fires before anything is listening.
+pub fn open(app: &AppHandle, argument: &str) {
+    focus_main_window(app);
+    let app = app.clone();
+    let argument = argument.to_string();
+    thread::spawn(move || {
+        let state = app.state::<AppState>();
+        let outcome = app_lock::ensure_unlocked(&state)
+            .and_then(|_| resolve_root(&argument))
+            .and_then(|root| {
+                let root_path = encode_path(&root);
+                tauri::async_runtime::block_on(scan_env_files(
+                    app.clone(),
+                    root_path,
+                    None,
+                    None,
+                    None,
+                ))
+            });
+        let request = match outcome {
+            Ok(scan) => OpenRequest {
+                root_path: scan.root_path.clone(),
+                scan: Some(scan),
+                error: None,
+            },
+            Err(error) => OpenRequest {
+                root_path: argument,
+                scan: None,
+                error: Some(error.to_string()),
+            },
+        };
+        store_request(&app, &request);
+        let _ = app.emit_all(OPEN_PATH_EVENT, request);
+    });
+}
+
+/// Opens the first non-flag argument from the launch command line, if any.
+pub fn open_from_args(app: &AppHandle) {
+    if let Some(argument) = std::env::args().skip(1).find(|arg| !arg.starts_with('-')) {
+        open(app, &argument);
+    }
+}
+
+/// Registers the `envshelf://` scheme; links arriving while the app runs are opened
+/// like command line arguments.
+pub fn register_scheme(app: &AppHandle) {
+    let handle = app.clone();
+    let registered = tauri_plugin_deep_link::register(SCHEME, move |link| {
+        open(&handle, &link);
+    });
+    if let Err(error) = registered {
+        tracing::error!("failed to register {} links: {}", SCHEME, error);
+    }
+}
+
+/// Returns and clears the request from the latest launch argument or link.
+#[tauri::command]
+pub async fn take_open_request(
+    state: State<'_, AppState>,
+) -> Result<Option<OpenRequest>, AppError> {
+    Ok(state.open_request.write().take())
+}
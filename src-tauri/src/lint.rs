@@ -0,0 +1,836 @@
+use crate::dialects::dialect_for;
+use crate::format::{formatted_text, quote_char_of, FormatStyle, QuoteChar};
+use crate::interpolation::{extract_references, extract_references_for};
+use crate::ipc_path::{decode_path, encode_path};
+use crate::quoting::{encode_value, Dialect, QuotePolicy, QuoteStyle};
+use crate::{
+    app_lock, ensure_allowed_path, files_in_group, is_example_file, parse_env_lines, read_env_text,
+    run_blocking, unquote, AppError, AppState, EnvLine, LinePosition, ParseReason, ValueType,
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tauri::AppHandle;
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl Severity {
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    pub rule: String,
+    pub severity: Severity,
+    pub message: String,
+    pub path: String,
+    pub line: Option<usize>,
+    pub key: Option<String>,
+    pub suggestion: Option<String>,
+}
+
+impl Diagnostic {
+    fn new(rule: &str, severity: Severity, path: &str, message: String) -> Self {
+        Diagnostic {
+            rule: rule.to_string(),
+            severity,
+            message,
+            path: path.to_string(),
+            line: None,
+            key: None,
+            suggestion: None,
+        }
+    }
+
+    fn at(mut self, line: usize, key: &str) -> Self {
+        self.line = Some(line);
+        self.key = Some(key.to_string());
+        self
+    }
+
+    fn suggest(mut self, suggestion: String) -> Self {
+        self.suggestion = Some(suggestion);
+        self
+    }
+}
+
+fn assignment_regex() -> Option<&'static Regex> {
+    static ASSIGNMENT_REGEX: OnceLock<Option<Regex>> = OnceLock::new();
+    ASSIGNMENT_REGEX
+        .get_or_init(|| Regex::new(r"^\s*(?:export\s+)?([^=\s#]+)\s*=").ok())
+        .as_ref()
+}
+
+/// Uppercases a key and replaces anything outside `[A-Z0-9_]` with `_`.
+fn normalize_key(key: &str) -> String {
+    let mut normalized: String = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if normalized.starts_with(|c: char| c.is_ascii_digit()) {
+        normalized.insert(0, '_');
+    }
+    normalized
+}
+
+fn lint_key_names(path: &str, lines: &[EnvLine], diagnostics: &mut Vec<Diagnostic>) {
+    for line in lines {
+        let line_number = line.position().line_number;
+        match line {
+            EnvLine::Kv { key, .. } if key.chars().any(|c| c.is_ascii_lowercase()) => {
+                diagnostics.push(
+                    Diagnostic::new(
+                        "key-lowercase",
+                        Severity::Warning,
+                        path,
+                        format!("{} contains lowercase letters", key),
+                    )
+                    .at(line_number, key)
+                    .suggest(normalize_key(key)),
+                );
+            }
+            EnvLine::Invalid {
+                raw,
+                reason,
+                severity,
+                message,
+                ..
+            } => {
+                let caps = (*reason == ParseReason::InvalidKey)
+                    .then(|| assignment_regex().and_then(|regex| regex.captures(raw)))
+                    .flatten();
+                let Some(caps) = caps else {
+                    let mut diagnostic =
+                        Diagnostic::new("parse-error", *severity, path, message.clone());
+                    diagnostic.line = Some(line_number);
+                    diagnostics.push(diagnostic);
+                    continue;
+                };
+                let key = caps.get(1).map(|m| m.as_str()).unwrap_or("");
+                let (rule, message) = if key.starts_with(|c: char| c.is_ascii_digit()) {
+                    ("key-leading-digit", format!("{} starts with a digit", key))
+                } else {
+                    (
+                        "key-invalid-chars",
+                        format!(
+                            "{} contains characters other than letters, digits and _",
+                            key
+                        ),
+                    )
+                };
+                diagnostics.push(
+                    Diagnostic::new(rule, Severity::Error, path, message)
+                        .at(line_number, key)
+                        .suggest(normalize_key(key)),
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The namespace of a key: the part before the first `_`, at least two characters.
+pub fn key_prefix(key: &str) -> Option<&str> {
+    key.split_once('_')
+        .map(|(prefix, _)| prefix)
+        .filter(|prefix| prefix.len() >= 2)
+}
+
+/// True when `short` looks like an abbreviation of `long` (e.g. DB / DATABASE).
+fn is_abbreviation(short: &str, long: &str) -> bool {
+    if short.len() >= long.len() || short.chars().next() != long.chars().next() {
+        return false;
+    }
+    let mut remaining = long.chars();
+    short.chars().all(|c| remaining.any(|l| l == c))
+}
+
+/// Flags prefix families such as `DB_*` next to `DATABASE_*` within one project and
+/// suggests the more common spelling.
+fn lint_prefixes(files: &[(String, Vec<EnvLine>)], diagnostics: &mut Vec<Diagnostic>) {
+    let mut usage: BTreeMap<String, Vec<(String, usize, String)>> = BTreeMap::new();
+    for (path, lines) in files {
+        for line in lines {
+            if let EnvLine::Kv { key, .. } = line {
+                if let Some(prefix) = key_prefix(key) {
+                    usage.entry(prefix.to_uppercase()).or_default().push((
+                        path.clone(),
+                        line.position().line_number,
+                        key.clone(),
+                    ));
+                }
+            }
+        }
+    }
+
+    let prefixes: Vec<&String> = usage.keys().collect();
+    for short in &prefixes {
+        for long in &prefixes {
+            if !is_abbreviation(short, long) {
+                continue;
+            }
+            let (preferred, other) = if usage[*long].len() >= usage[*short].len() {
+                (long, short)
+            } else {
+                (short, long)
+            };
+            for (path, line, key) in &usage[*other] {
+                let suggestion = format!("{}{}", preferred, &key[other.len()..]);
+                diagnostics.push(
+                    Diagnostic::new(
+                        "key-inconsistent-prefix",
+                        Severity::Info,
+                        path,
+                        format!(
+                            "{} uses prefix {}_ while the project also uses {}_",
+                            key, other, preferred
+                        ),
+                    )
+                    .at(*line, key)
+                    .suggest(suggestion),
+                );
+            }
+        }
+    }
+}
+
+/// Edit distance counting an adjacent transposition as one edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+/// Two keys close enough to be a misspelling of each other. Keys differing only in
+/// digits (`REPLICA_1` / `REPLICA_2`) are numbered siblings, not typos.
+fn is_probable_typo(a: &str, b: &str) -> bool {
+    let shorter = a.len().min(b.len());
+    if shorter < 5 {
+        return false;
+    }
+    let numbered = a.len() == b.len()
+        && a.chars()
+            .zip(b.chars())
+            .all(|(x, y)| x == y || (x.is_ascii_digit() && y.is_ascii_digit()));
+    let limit = if shorter >= 12 { 2 } else { 1 };
+    !numbered && edit_distance(a, b) <= limit
+}
+
+/// Flags keys that are one or two edits away from another key in the project, such
+/// as `DATABSE_URL` next to `DATABASE_URL`. The key used in fewer files is reported
+/// with the other as suggestion; when both are equally common, both are reported.
+fn lint_typos(files: &[(String, Vec<EnvLine>)], diagnostics: &mut Vec<Diagnostic>) {
+    let mut usage: BTreeMap<&str, Vec<(&str, usize)>> = BTreeMap::new();
+    for (path, lines) in files {
+        for line in lines {
+            if let EnvLine::Kv { key, .. } = line {
+                usage
+                    .entry(key.as_str())
+                    .or_default()
+                    .push((path.as_str(), line.position().line_number));
+            }
+        }
+    }
+    let file_count = |key: &str| {
+        let mut paths: Vec<&str> = usage[key].iter().map(|(path, _)| *path).collect();
+        paths.dedup();
+        paths.len()
+    };
+
+    let keys: Vec<&str> = usage.keys().copied().collect();
+    for (position, &first) in keys.iter().enumerate() {
+        for &second in &keys[position + 1..] {
+            if !is_probable_typo(first, second) {
+                continue;
+            }
+            let (first_count, second_count) = (file_count(first), file_count(second));
+            let reported: Vec<(&str, &str)> = if first_count < second_count {
+                vec![(first, second)]
+            } else if second_count < first_count {
+                vec![(second, first)]
+            } else {
+                vec![(first, second), (second, first)]
+            };
+            let tied = reported.len() > 1;
+            for (typo, other) in reported {
+                for (path, line) in &usage[typo] {
+                    let mut diagnostic = Diagnostic::new(
+                        "key-probable-typo",
+                        Severity::Warning,
+                        path,
+                        format!("{} looks like a misspelling of {}", typo, other),
+                    )
+                    .at(*line, typo);
+                    if !tied {
+                        diagnostic = diagnostic.suggest(other.to_string());
+                    }
+                    diagnostics.push(diagnostic);
+                }
+            }
+        }
+    }
+}
+
+fn raw_text(line: &EnvLine) -> Option<&str> {
+    match line {
+        EnvLine::Comment { raw, .. } | EnvLine::Invalid { raw, .. } => Some(raw),
+        EnvLine::Kv { raw, .. } | EnvLine::Unset { raw, .. } | EnvLine::Append { raw, .. } => {
+            raw.as_deref()
+        }
+        EnvLine::Blank { .. } => None,
+    }
+}
+
+fn lint_whitespace(path: &str, lines: &[EnvLine], diagnostics: &mut Vec<Diagnostic>) {
+    let style = FormatStyle::default();
+    for line in lines {
+        let Some(raw) = raw_text(line) else {
+            continue;
+        };
+        let line_number = line.position().line_number;
+        let key = match line {
+            EnvLine::Kv { key, .. } => key.as_str(),
+            _ => "",
+        };
+        let mut push = |rule: &str, message: String| {
+            let mut diagnostic = Diagnostic::new(rule, Severity::Warning, path, message)
+                .suggest(formatted_text(line, style));
+            diagnostic.line = Some(line_number);
+            diagnostic.key = Some(key.to_string()).filter(|key| !key.is_empty());
+            diagnostics.push(diagnostic);
+        };
+
+        if raw.ends_with([' ', '\t']) {
+            push(
+                "trailing-whitespace",
+                "Line has trailing whitespace".to_string(),
+            );
+        }
+        if raw.trim_end().contains('\t') {
+            push("tab-character", "Line contains a tab character".to_string());
+        }
+        if let EnvLine::Kv { separator, .. } = line {
+            if separator != "=" {
+                push(
+                    "spaces-around-equals",
+                    format!("{} has whitespace around =", key),
+                );
+            }
+        }
+    }
+}
+
+/// Flags quoted values that use the less common quote character of the file.
+fn lint_quoting(path: &str, lines: &[EnvLine], diagnostics: &mut Vec<Diagnostic>) {
+    let quoted: Vec<(usize, &EnvLine, QuoteChar)> = lines
+        .iter()
+        .filter_map(|line| match line {
+            EnvLine::Kv { value, .. } => {
+                quote_char_of(value).map(|quote| (line.position().line_number, line, quote))
+            }
+            _ => None,
+        })
+        .collect();
+    let doubles = quoted
+        .iter()
+        .filter(|(_, _, quote)| *quote == QuoteChar::Double)
+        .count();
+    let singles = quoted.len() - doubles;
+    if doubles == 0 || singles == 0 {
+        return;
+    }
+    let (preferred, name) = if doubles >= singles {
+        (QuoteChar::Double, "double")
+    } else {
+        (QuoteChar::Single, "single")
+    };
+    let style = FormatStyle {
+        quotes: preferred,
+        ..FormatStyle::default()
+    };
+
+    for (line_number, line, quote) in quoted {
+        if quote == preferred {
+            continue;
+        }
+        let EnvLine::Kv { key, .. } = line else {
+            continue;
+        };
+        diagnostics.push(
+            Diagnostic::new(
+                "inconsistent-quoting",
+                Severity::Info,
+                path,
+                format!(
+                    "{} is quoted differently; this file mostly uses {} quotes",
+                    key, name
+                ),
+            )
+            .at(line_number, key)
+            .suggest(formatted_text(line, style)),
+        );
+    }
+}
+
+const SECRET_KEY_MARKERS: [&str; 7] = [
+    "SECRET",
+    "TOKEN",
+    "PASSWORD",
+    "PASSWD",
+    "PRIVATE_KEY",
+    "API_KEY",
+    "APIKEY",
+];
+const MIN_SECRET_LENGTH: usize = 16;
+const MIN_SECRET_ENTROPY_BITS: f64 = 64.0;
+const COMMON_WEAK_SECRETS: [&str; 12] = [
+    "dev", "test", "secret", "password", "changeme", "admin", "root", "local", "example", "123456",
+    "qwerty", "letmein",
+];
+
+pub fn looks_like_secret_key(key: &str) -> bool {
+    let upper = key.to_ascii_uppercase();
+    SECRET_KEY_MARKERS
+        .iter()
+        .any(|marker| upper.contains(marker))
+}
+
+/// Total Shannon entropy of `value` in bits, from its own character frequencies.
+fn entropy_bits(value: &str) -> f64 {
+    let mut counts: BTreeMap<char, usize> = BTreeMap::new();
+    for ch in value.chars() {
+        *counts.entry(ch).or_default() += 1;
+    }
+    let length = value.chars().count() as f64;
+    let per_char: f64 = counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / length;
+            -p * p.log2()
+        })
+        .sum();
+    per_char * length
+}
+
+/// Flags secret-looking keys whose values are short, low-entropy or well-known dev
+/// defaults. Empty values and values built from other variables are left alone.
+fn lint_weak_secrets(
+    path: &str,
+    lines: &[EnvLine],
+    dialect: Dialect,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let is_example = decode_path(path)
+        .file_name()
+        .map(|name| is_example_file(&name.to_string_lossy()))
+        .unwrap_or(false);
+    if is_example {
+        return;
+    }
+    for line in lines {
+        let EnvLine::Kv { key, value, .. } = line else {
+            continue;
+        };
+        let secret = unquote(value);
+        if secret.is_empty()
+            || !looks_like_secret_key(key)
+            || !extract_references_for(value, dialect).is_empty()
+        {
+            continue;
+        }
+        let lower = secret.to_ascii_lowercase();
+        let reason =
+            if lower.len() < 24 && COMMON_WEAK_SECRETS.iter().any(|weak| lower.contains(weak)) {
+                "uses a common default value"
+            } else if secret.chars().count() < MIN_SECRET_LENGTH {
+                "is shorter than 16 characters"
+            } else if entropy_bits(secret) < MIN_SECRET_ENTROPY_BITS {
+                "has low entropy"
+            } else {
+                continue;
+            };
+        diagnostics.push(
+            Diagnostic::new(
+                "weak-secret",
+                Severity::Warning,
+                path,
+                format!("{} looks like a secret but {}", key, reason),
+            )
+            .at(line.position().line_number, key),
+        );
+    }
+}
+
+fn percent_regex() -> Option<&'static Regex> {
+    static PERCENT_REGEX: OnceLock<Option<Regex>> = OnceLock::new();
+    PERCENT_REGEX
+        .get_or_init(|| Regex::new(r"%\(([A-Za-z_][A-Za-z0-9_]*)\)s").ok())
+        .as_ref()
+}
+
+/// python-dotenv reads quoted values across lines; the lines after the opening one
+/// are blanked so line-based rules do not report them. Indices stay unchanged.
+fn mask_continuations(lines: &[EnvLine]) -> Vec<EnvLine> {
+    let mut masked = lines.to_vec();
+    let mut open_quote: Option<char> = None;
+    for line in masked.iter_mut() {
+        if let Some(quote) = open_quote {
+            let text = match line {
+                EnvLine::Kv { raw: Some(raw), .. }
+                | EnvLine::Comment { raw, .. }
+                | EnvLine::Invalid { raw, .. } => raw.clone(),
+                _ => String::new(),
+            };
+            if text.trim_end().ends_with(quote) {
+                open_quote = None;
+            }
+            *line = EnvLine::Blank {
+                position: LinePosition::line(line.position().line_number),
+            };
+            continue;
+        }
+        if let EnvLine::Kv { value, .. } = line {
+            let trimmed = value.trim();
+            open_quote = trimmed
+                .chars()
+                .next()
+                .filter(|&quote| matches!(quote, '"' | '\''))
+                .filter(|&quote| trimmed.len() == 1 || !trimmed.ends_with(quote));
+        }
+    }
+    masked
+}
+
+/// Flags values the file's dialect would load differently from plain dotenv.
+fn lint_dialect(
+    path: &str,
+    lines: &[EnvLine],
+    dialect: Dialect,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let name = match dialect {
+        Dialect::Phpdotenv => "phpdotenv",
+        Dialect::Python => "python-dotenv",
+        _ => return,
+    };
+    let style = QuoteStyle {
+        policy: QuotePolicy::Always,
+        dialect,
+    };
+    for line in lines {
+        let EnvLine::Kv { key, value, .. } = line else {
+            continue;
+        };
+        let unquoted = quote_char_of(value).is_none();
+        if dialect == Dialect::Phpdotenv && unquoted && value.trim().contains(char::is_whitespace) {
+            let mut diagnostic = Diagnostic::new(
+                "dialect-unquoted-whitespace",
+                Severity::Error,
+                path,
+                format!(
+                    "phpdotenv rejects the unquoted value of {} because it contains whitespace",
+                    key
+                ),
+            )
+            .at(line.position().line_number, key);
+            if let Ok(quoted) = encode_value(value, style) {
+                diagnostic = diagnostic.suggest(format!("{}={}", key, quoted));
+            }
+            diagnostics.push(diagnostic);
+        }
+        let expanded = extract_references_for(value, dialect);
+        for reference in extract_references(value) {
+            if !expanded.contains(&reference) {
+                diagnostics.push(
+                    Diagnostic::new(
+                        "dialect-unbraced-reference",
+                        Severity::Warning,
+                        path,
+                        format!(
+                            "{} does not expand ${} in {}; use ${{{}}}",
+                            name, reference, key, reference
+                        ),
+                    )
+                    .at(line.position().line_number, key),
+                );
+            }
+        }
+        if dialect == Dialect::Python {
+            let percent = percent_regex().and_then(|regex| regex.captures(value));
+            if let Some(reference) = percent.and_then(|caps| caps.get(1)) {
+                diagnostics.push(
+                    Diagnostic::new(
+                        "dialect-percent-reference",
+                        Severity::Info,
+                        path,
+                        format!(
+                            "python-dotenv loads %({})s in {} literally; use ${{{}}} to interpolate",
+                            reference.as_str(),
+                            key,
+                            reference.as_str()
+                        ),
+                    )
+                    .at(line.position().line_number, key),
+                );
+            }
+        }
+    }
+}
+
+/// Values that run a command when sourced; the parser marks them `dangerous`.
+fn lint_command_substitution(path: &str, lines: &[EnvLine], diagnostics: &mut Vec<Diagnostic>) {
+    for line in lines {
+        if let EnvLine::Kv {
+            key,
+            dangerous: true,
+            ..
+        } = line
+        {
+            diagnostics.push(
+                Diagnostic::new(
+                    "command-substitution",
+                    Severity::Warning,
+                    path,
+                    format!(
+                        "{} contains a command substitution that runs when the file is sourced",
+                        key
+                    ),
+                )
+                .at(line.position().line_number, key),
+            );
+        }
+    }
+}
+
+/// The type a key's name promises, for the few naming conventions that are
+/// unambiguous: `*_PORT`, `*_URL`/`*_URI` and on/off switches.
+fn expected_value_type(key: &str) -> Option<ValueType> {
+    let key = key.to_ascii_uppercase();
+    let first = key.split('_').next().unwrap_or("");
+    let last = key.rsplit('_').next().unwrap_or("");
+    match (first, last) {
+        (_, "PORT") => Some(ValueType::Integer),
+        (_, "URL" | "URI") => Some(ValueType::Url),
+        ("ENABLE" | "DISABLE" | "IS", _) | (_, "ENABLED" | "DISABLED" | "DEBUG") => {
+            Some(ValueType::Boolean)
+        }
+        _ => None,
+    }
+}
+
+/// Values whose inferred type contradicts the one their key implies, such as
+/// `PORT=abc`. Empty and interpolated values are left alone.
+fn lint_value_types(path: &str, lines: &[EnvLine], diagnostics: &mut Vec<Diagnostic>) {
+    for line in lines {
+        let EnvLine::Kv {
+            key,
+            value,
+            value_type,
+            ..
+        } = line
+        else {
+            continue;
+        };
+        let Some(expected) = expected_value_type(key) else {
+            continue;
+        };
+        if *value_type == expected
+            || *value_type == ValueType::Empty
+            || !extract_references(value).is_empty()
+        {
+            continue;
+        }
+        // 0 and 1 are common spellings of a switch.
+        if expected == ValueType::Boolean && matches!(unquote(value), "0" | "1") {
+            continue;
+        }
+        diagnostics.push(
+            Diagnostic::new(
+                "value-type",
+                Severity::Warning,
+                path,
+                format!(
+                    "{} should be {} but looks like {}",
+                    key,
+                    article(expected.as_str()),
+                    article(value_type.as_str())
+                ),
+            )
+            .at(line.position().line_number, key),
+        );
+    }
+}
+
+fn article(word: &str) -> String {
+    match word.chars().next() {
+        Some('a' | 'e' | 'i' | 'o' | 'u') if word != "url" => format!("an {}", word),
+        _ => format!("a {}", word),
+    }
+}
+
+pub fn lint_lines(path: &str, lines: &[EnvLine], dialect: Dialect) -> Vec<Diagnostic> {
+    let masked;
+    let lines = if dialect == Dialect::Python {
+        masked = mask_continuations(lines);
+        &masked
+    } else {
+        lines
+    };
+    let mut diagnostics = Vec::new();
+    lint_key_names(path, lines, &mut diagnostics);
+    lint_whitespace(path, lines, &mut diagnostics);
+    lint_quoting(path, lines, &mut diagnostics);
+    lint_weak_secrets(path, lines, dialect, &mut diagnostics);
+    lint_dialect(path, lines, dialect, &mut diagnostics);
+    lint_command_substitution(path, lines, &mut diagnostics);
+    lint_value_types(path, lines, &mut diagnostics);
+    diagnostics
+}
+
+fn load_lines(state: &AppState, path: &Path) -> Result<Vec<EnvLine>, AppError> {
+    Ok(parse_env_lines(&read_env_text(state, path)?))
+}
+
+#[tauri::command]
+pub async fn lint_file(app: AppHandle, path: String) -> Result<Vec<Diagnostic>, AppError> {
+    run_blocking(app, move |_, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        let lines = load_lines(&state, &path_buf)?;
+        Ok(lint_lines(&path, &lines, dialect_for(&state, &path_buf)))
+    })
+    .await
+}
+
+/// Lints files together, including rules that need the whole project. A file that
+/// cannot be read is reported as a `load-error` instead of failing the others.
+pub fn lint_paths(state: &AppState, paths: Vec<PathBuf>) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        let display = encode_path(&path);
+        match load_lines(state, &path) {
+            Ok(lines) => files.push((display, lines)),
+            Err(error) => diagnostics.push(Diagnostic::new(
+                "load-error",
+                Severity::Error,
+                &display,
+                format!("Could not read the file: {}", error),
+            )),
+        }
+    }
+
+    diagnostics.extend(
+        files.iter().flat_map(|(path, lines)| {
+            lint_lines(path, lines, dialect_for(state, &decode_path(path)))
+        }),
+    );
+    lint_prefixes(&files, &mut diagnostics);
+    lint_typos(&files, &mut diagnostics);
+    diagnostics
+}
+
+/// Lints every file of a project group.
+#[tauri::command]
+pub async fn lint_project(app: AppHandle, group_id: String) -> Result<Vec<Diagnostic>, AppError> {
+    run_blocking(app, move |_, state| {
+        app_lock::ensure_unlocked(&state)?;
+        Ok(lint_paths(&state, files_in_group(&state, &group_id)?))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value_type_keys(text: &str) -> Vec<String> {
+        let mut diagnostics = Vec::new();
+        lint_value_types(".env", &parse_env_lines(text), &mut diagnostics);
+        diagnostics
+            .into_iter()
+            .filter_map(|diagnostic| diagnostic.key)
+            .collect()
+    }
+
+    #[test]
+    fn flags_values_that_contradict_the_key_name() {
+        assert_eq!(
+            value_type_keys("PORT=abc\nAPI_URL=localhost\nDEBUG=maybe"),
+            ["PORT", "API_URL", "DEBUG"]
+        );
+    }
+
+    #[test]
+    fn accepts_matching_empty_and_interpolated_values() {
+        let text = "PORT=8080\nDB_PORT=\"5432\"\nAPI_URL=https://example.com\n\
+                    ENABLE_CACHE=true\nDEBUG=1\nREDIS_PORT=\nAPP_PORT=${PORT}\nNAME=abc";
+        assert!(value_type_keys(text).is_empty());
+    }
+
+    #[test]
+    fn reports_the_line_number_the_parser_recorded() {
+        let lines = parse_env_lines("CERT=\"a\nb\nc\"\nport=1\n");
+        let diagnostics = lint_lines(".env", &lines, Dialect::Dotenv);
+        let lowercase = diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic.rule == "key-lowercase")
+            .expect("lowercase key");
+        assert_eq!(lowercase.line, Some(4));
+    }
+
+    #[test]
+    fn an_unreadable_file_does_not_stop_the_others() {
+        let dir = tempfile::tempdir().unwrap();
+        let readable = dir.path().join(".env");
+        std::fs::write(&readable, "port=1\n").unwrap();
+        let missing = dir.path().join(".env.missing");
+
+        let diagnostics = lint_paths(&AppState::default(), vec![missing, readable]);
+        let rules: Vec<&str> = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.rule.as_str())
+            .collect();
+        assert_eq!(rules, ["load-error", "key-lowercase"]);
+    }
+}
@@ -0,0 +1,246 @@
+use crate::io_error::{IoCode, PathContext};
+use crate::ipc_path::{decode_path, encode_path};
+use crate::{hash_path, run_blocking, store, AppError, AppState};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tauri::{AppHandle, Invoke, InvokeMessage};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, Registry};
+
+const LOG_SETTINGS_FILE: &str = "log-settings.json";
+const LOG_FILE_PREFIX: &str = "envshelf";
+const LOG_FILE_SUFFIX: &str = "log";
+/// One file per day; older ones are deleted.
+const MAX_LOG_FILES: usize = 7;
+const DEFAULT_RECENT_LINES: usize = 200;
+
+/// Set once on startup; nothing is written when the log directory is unavailable.
+static LOGGER: OnceLock<Logger> = OnceLock::new();
+
+struct Logger {
+    dir: PathBuf,
+    level: reload::Handle<LevelFilter, Registry>,
+}
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    fn filter(self) -> LevelFilter {
+        match self {
+            LogLevel::Error => LevelFilter::ERROR,
+            LogLevel::Warn => LevelFilter::WARN,
+            LogLevel::Info => LevelFilter::INFO,
+            LogLevel::Debug => LevelFilter::DEBUG,
+            LogLevel::Trace => LevelFilter::TRACE,
+        }
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LogSettings {
+    level: LogLevel,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentLogs {
+    /// Folder holding the log files, for attaching them to a bug report.
+    dir: String,
+    /// Oldest first.
+    lines: Vec<String>,
+}
+
+/// Opens the daily log file in the app log directory; called first on app setup.
+pub fn init(app: &AppHandle) -> Result<(), AppError> {
+    let settings: LogSettings = store::load(app, LOG_SETTINGS_FILE)?;
+    let dir = app
+        .path_resolver()
+        .app_log_dir()
+        .ok_or_else(|| AppError::io(IoCode::Unavailable, "Log directory is unavailable"))?;
+    fs::create_dir_all(&dir).at(&dir)?;
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix(LOG_FILE_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(&dir)
+        .map_err(|e| AppError::io(IoCode::Other, e.to_string()))?;
+    let (level, handle) = reload::Layer::new(settings.level.filter());
+    tracing_subscriber::registry()
+        .with(level)
+        .with(fmt::layer().with_writer(appender).with_ansi(false))
+        .try_init()
+        .map_err(|e| AppError::io(IoCode::Other, e.to_string()))?;
+    let _ = LOGGER.set(Logger { dir, level: handle });
+    Ok(())
+}
+
+/// Logs name the file but not where it lives: the folder becomes a short hash, which
+/// still tells two projects apart in a bug report.
+fn redact(path: &Path) -> String {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match path.parent() {
+        Some(folder) if !folder.as_os_str().is_empty() => {
+            format!("{}/{}", &hash_path(folder)[..8], name)
+        }
+        _ => name,
+    }
+}
+
+/// An error's message for the log, with any path redacted like command arguments.
+pub fn redacted(error: &AppError) -> String {
+    match error {
+        AppError::IoError(io) => match io.path() {
+            Some(path) => format!("{} ({})", io.message(), redact(&decode_path(path))),
+            None => io.message().to_string(),
+        },
+        error => error.to_string(),
+    }
+}
+
+fn is_path_argument(name: &str) -> bool {
+    name == "path" || name == "paths" || name.ends_with("Path") || name.ends_with("Paths")
+}
+
+fn path_arguments(payload: &Value) -> Vec<String> {
+    let Value::Object(arguments) = payload else {
+        return Vec::new();
+    };
+    arguments
+        .iter()
+        .filter(|(name, _)| is_path_argument(name))
+        .flat_map(|(_, value)| match value {
+            Value::String(path) => vec![path.as_str()],
+            Value::Array(paths) => paths.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        })
+        .map(|path| redact(&decode_path(path)))
+        .collect()
+}
+
+/// Logs a command call with the (redacted) paths it names. Other arguments can hold
+/// values or passwords and are never logged.
+fn invocation(message: &InvokeMessage) {
+    tracing::info!(
+        command = message.command(),
+        paths = ?path_arguments(message.payload()),
+        "command invoked"
+    );
+}
+
+/// Wraps the command handler so every call is logged before it runs.
+pub fn logged(
+    handler: impl Fn(Invoke) + Send + Sync + 'static,
+) -> impl Fn(Invoke) + Send + Sync + 'static {
+    move |invoke| {
+        invocation(&invoke.message);
+        handler(invoke)
+    }
+}
+
+/// Logs an error on its way to the window. IO errors keep their code and kind, with the
+/// path redacted like command arguments.
+pub fn failure(error: &AppError) {
+    match error {
+        AppError::IoError(io) => {
+            let path = io
+                .path()
+                .map(|path| redact(&decode_path(path)))
+                .unwrap_or_default();
+            tracing::warn!(
+                code = ?io.code(),
+                kind = io.kind().unwrap_or_default(),
+                path = %path,
+                "command failed: {}",
+                io.message()
+            );
+        }
+        error => tracing::warn!("command failed: {}", error),
+    }
+}
+
+fn log_files(dir: &Path) -> Result<Vec<PathBuf>, AppError> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .at(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().starts_with(LOG_FILE_PREFIX))
+                .unwrap_or(false)
+        })
+        .collect();
+    // Daily files end in the date, so name order is age order.
+    files.sort();
+    Ok(files)
+}
+
+/// The last `lines` log lines (200 by default), across days when today's file is short.
+#[tauri::command]
+pub async fn get_recent_logs(app: AppHandle, lines: Option<usize>) -> Result<RecentLogs, AppError> {
+    run_blocking(app, move |_, _| {
+        let logger = LOGGER
+            .get()
+            .ok_or_else(|| AppError::io(IoCode::Unavailable, "Logging is unavailable"))?;
+        let wanted = lines.unwrap_or(DEFAULT_RECENT_LINES);
+        let mut recent: Vec<String> = Vec::new();
+        for path in log_files(&logger.dir)?.iter().rev() {
+            if recent.len() >= wanted {
+                break;
+            }
+            let contents = fs::read_to_string(path).at(path)?;
+            let file_lines: Vec<&str> = contents.lines().collect();
+            let keep = file_lines.len().min(wanted - recent.len());
+            let older = file_lines[file_lines.len() - keep..]
+                .iter()
+                .map(|line| line.to_string());
+            recent.splice(0..0, older);
+        }
+        Ok(RecentLogs {
+            dir: encode_path(&logger.dir),
+            lines: recent,
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn set_log_level(app: AppHandle, level: LogLevel) -> Result<LogSettings, AppError> {
+    run_blocking(app, move |app, state| {
+        let settings = store::update(
+            &app,
+            &state,
+            LOG_SETTINGS_FILE,
+            |settings: &mut LogSettings| {
+                settings.level = level;
+                settings.clone()
+            },
+        )?;
+        if let Some(logger) = LOGGER.get() {
+            logger
+                .level
+                .reload(level.filter())
+                .map_err(|e| AppError::io(IoCode::Other, e.to_string()))?;
+        }
+        Ok(settings)
+    })
+    .await
+}
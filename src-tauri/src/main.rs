@@ -1,30 +1,128 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use chrono::Local;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use io_error::{IoCode, IoFailure, PathContext};
+use lint::Severity;
+use parking_lot::RwLock;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::{BTreeMap, HashSet};
+use similar::{ChangeTag, TextDiff};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    Mutex,
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    mpsc, Arc, OnceLock,
 };
-use tauri::State;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::api::dialog::blocking::FileDialogBuilder;
+use tauri::{AppHandle, Manager, State};
 use thiserror::Error;
 use walkdir::WalkDir;
 
+mod access;
+mod app_lock;
+mod backups;
+mod batch;
+mod bundle;
+mod changes;
+mod cleanup;
+mod cli;
+mod clipboard;
+mod codec;
+mod crypto;
+mod definitions;
+mod diagnostics;
+mod dialects;
+mod direnv;
+mod dropped;
+mod edits;
+mod expiry;
+mod external;
+mod findings;
+mod format;
+mod framework;
+mod generators;
+mod github;
+mod gitleaks;
+mod hooks;
+mod interop;
+mod interpolation;
+mod io_error;
+mod ipc_path;
+mod journal;
+mod launch;
+mod lint;
+mod logging;
+mod mounts;
+mod notes;
+mod onepassword;
+mod ordering;
+mod palette;
+mod parse_mode;
+mod pins;
+mod placeholders;
+mod preflight;
+mod process_env;
+mod provenance;
+mod quoting;
+mod report;
+mod runner;
+mod schema;
+mod ssm;
+mod search;
+mod secret_tools;
+mod secrets;
+mod snippets;
+mod sorting;
+mod stats;
+mod store;
+mod stream_parse;
+mod systemd;
+mod templates;
+mod tfvars;
+mod tray;
+mod value_index;
+mod variants;
+mod vault;
+mod vercel;
+mod watcher;
+
+const DEFAULT_MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+const IGNORE_FILE_NAME: &str = ".envshelfignore";
+const EXAMPLE_SUFFIXES: [&str; 5] = [".example", ".sample", ".template", ".defaults", ".dist"];
+const CASE_INSENSITIVE_FS: bool = cfg!(any(target_os = "macos", target_os = "windows"));
+/// A directory listing slower than this is reported and not descended into.
+const SLOW_DIR_TIMEOUT: Duration = Duration::from_secs(3);
+const CANCEL_POLL: Duration = Duration::from_millis(100);
+
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct EnvFileRef {
     id: String,
+    /// Written with `ipc_path::encode_path`; the frontend passes it back unchanged.
     absolute_path: String,
     file_name: String,
     folder_path: String,
     size: u64,
     modified_at: i64,
+    content_hash: String,
+    key_count: usize,
+    comment_count: usize,
+    has_parse_errors: bool,
+    /// Whether the current content came from the app or from another program.
+    modified_by: provenance::Provenance,
+    app_written_at: Option<i64>,
+    /// Listed by the scan but not read, e.g. because it is above the size limit. Such
+    /// files are left out of the allow-list and cannot be opened.
+    skipped: bool,
+    skip_reason: Option<String>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -34,21 +132,183 @@ struct ProjectGroup {
     name: String,
     root_path: String,
     env_files: Vec<EnvFileRef>,
+    framework: Option<framework::Framework>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(tag = "kind", rename_all = "lowercase")]
 enum EnvLine {
-    Blank,
-    Comment { raw: String },
+    Blank {
+        #[serde(flatten)]
+        position: LinePosition,
+    },
+    Comment {
+        raw: String,
+        #[serde(flatten)]
+        position: LinePosition,
+    },
     Kv {
         key: String,
         value: String,
         #[serde(rename = "hasExport")]
         has_export: bool,
+        #[serde(default = "default_separator")]
+        separator: String,
+        #[serde(rename = "inlineComment", default)]
+        inline_comment: Option<String>,
+        #[serde(default)]
+        description: Option<String>,
+        #[serde(rename = "valueType", default)]
+        value_type: ValueType,
+        /// The value contains `$(command)` or backtick substitution, which runs a
+        /// command when the file is sourced by a shell or Ruby dotenv.
+        #[serde(default)]
+        dangerous: bool,
         raw: Option<String>,
+        #[serde(flatten)]
+        position: LinePosition,
     },
-    Unknown { raw: String },
+    /// `unset KEY`: removes a key set by the lines or files before it.
+    Unset {
+        key: String,
+        raw: Option<String>,
+        #[serde(flatten)]
+        position: LinePosition,
+    },
+    /// `KEY+=value`: adds to the end of the value set before it, as in shells.
+    Append {
+        key: String,
+        value: String,
+        #[serde(rename = "inlineComment", default)]
+        inline_comment: Option<String>,
+        raw: Option<String>,
+        #[serde(flatten)]
+        position: LinePosition,
+    },
+    /// A line that is not blank, a comment or an assignment. It is written back as is
+    /// and its keys cannot be edited.
+    Invalid {
+        raw: String,
+        reason: ParseReason,
+        severity: Severity,
+        /// What is wrong and how to fix it, for showing next to the line.
+        message: String,
+        #[serde(flatten)]
+        position: LinePosition,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum ParseReason {
+    /// There is an `=`, but what comes before it is not a key name.
+    InvalidKey,
+    MissingEquals,
+    /// A quote opens before any `=` and is not closed on the same line, as in the
+    /// later lines of a multi-line value.
+    UnterminatedQuote,
+}
+
+impl EnvLine {
+    fn position(&self) -> &LinePosition {
+        match self {
+            EnvLine::Blank { position }
+            | EnvLine::Comment { position, .. }
+            | EnvLine::Kv { position, .. }
+            | EnvLine::Unset { position, .. }
+            | EnvLine::Append { position, .. }
+            | EnvLine::Invalid { position, .. } => position,
+        }
+    }
+}
+
+/// Where a parsed line came from, so the UI can highlight and jump without parsing
+/// again. Lines added or rebuilt since the file was read have no spans.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct LinePosition {
+    /// 1-based; 0 for lines that were not read from the file.
+    line_number: usize,
+    key_span: Option<TextSpan>,
+    value_span: Option<TextSpan>,
+}
+
+impl LinePosition {
+    fn line(line_number: usize) -> Self {
+        LinePosition {
+            line_number,
+            ..LinePosition::default()
+        }
+    }
+}
+
+/// A half-open range within one line, as UTF-8 byte offsets and as char offsets.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TextSpan {
+    start: usize,
+    end: usize,
+    start_char: usize,
+    end_char: usize,
+}
+
+impl TextSpan {
+    fn of(line: &str, start: usize, end: usize) -> Self {
+        let start_char = line[..start].chars().count();
+        TextSpan {
+            start,
+            end,
+            start_char,
+            end_char: start_char + line[start..end].chars().count(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ValueType {
+    Empty,
+    Boolean,
+    Integer,
+    Float,
+    Url,
+    Path,
+    Base64,
+    Json,
+    #[default]
+    String,
+}
+
+impl ValueType {
+    fn as_str(self) -> &'static str {
+        match self {
+            ValueType::Empty => "empty",
+            ValueType::Boolean => "boolean",
+            ValueType::Integer => "integer",
+            ValueType::Float => "float",
+            ValueType::Url => "url",
+            ValueType::Path => "path",
+            ValueType::Base64 => "base64",
+            ValueType::Json => "json",
+            ValueType::String => "string",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum LineEnding {
+    #[default]
+    Lf,
+    Crlf,
+}
+
+/// File-level formatting that is not captured by individual lines.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct TextLayout {
+    trailing_newline: bool,
+    line_ending: LineEnding,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -56,6 +316,37 @@ enum EnvLine {
 struct EnvDocument {
     file: EnvFileRef,
     lines: Vec<EnvLine>,
+    #[serde(flatten)]
+    layout: TextLayout,
+    #[serde(default)]
+    prefix_groups: Vec<edits::PrefixGroup>,
+    /// Filled only when the file was read in strict mode.
+    #[serde(default)]
+    deviations: Vec<parse_mode::Deviation>,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SlowReason {
+    /// A network share; nested shares are skipped, a network root is still scanned.
+    Network,
+    /// Listing the directory took longer than `SLOW_DIR_TIMEOUT`; it was skipped.
+    Timeout,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SlowPath {
+    path: String,
+    reason: SlowReason,
+}
+
+/// An entry the scan could not read, e.g. a folder without permission.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SkippedPath {
+    path: String,
+    reason: String,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -63,23 +354,118 @@ struct EnvDocument {
 struct ScanResult {
     root_path: String,
     groups: Vec<ProjectGroup>,
+    #[serde(default)]
+    slow_paths: Vec<SlowPath>,
+    #[serde(default)]
+    skipped_paths: Vec<SkippedPath>,
+    /// Set on a rescan of the same root.
+    #[serde(default)]
+    diff: Option<changes::ScanDiff>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct WriteOptions {
     create_backup: bool,
+    #[serde(default)]
+    expected_hash: Option<String>,
+    #[serde(default)]
+    expected_mtime: Option<i64>,
+    /// Re-encodes edited values with this policy and dialect before writing.
+    #[serde(default)]
+    quote_style: Option<quoting::QuoteStyle>,
+}
+
+/// Which families of file names the scanner picks up.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct DiscoveryPatterns {
+    /// `.env`, `.env.local`, `.env.production`, ...
+    dotenv: bool,
+    /// `.env.example`, `.env.sample`, `.env.template`, `.env.defaults`, `.env.dist`
+    examples: bool,
+    /// direnv `.envrc`
+    envrc: bool,
+    /// Flask `.flaskenv`
+    flaskenv: bool,
+    /// docker-compose style `*.env` (e.g. `web.env`)
+    suffix_env: bool,
+}
+
+impl Default for DiscoveryPatterns {
+    fn default() -> Self {
+        DiscoveryPatterns {
+            dotenv: true,
+            examples: true,
+            envrc: true,
+            flaskenv: true,
+            suffix_env: true,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileStatus {
+    path: String,
+    exists: bool,
+    content_hash: Option<String>,
+    modified_at: Option<i64>,
+    size: Option<u64>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WritePreview {
+    path: String,
+    changed: bool,
+    additions: usize,
+    deletions: usize,
+    diff: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum KeyChangeKind {
+    Added,
+    Updated,
+    Removed,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyChange {
+    key: String,
+    change: KeyChangeKind,
+    before: Option<String>,
+    after: Option<String>,
 }
 
 #[derive(Default)]
 struct AppState {
-    root_path: Mutex<Option<PathBuf>>,
-    allowed_files: Mutex<HashSet<PathBuf>>,
+    root_path: RwLock<Option<PathBuf>>,
+    allowed_files: RwLock<HashSet<PathBuf>>,
+    /// Files picked individually outside the scanned root; kept across rescans.
+    single_files: RwLock<HashSet<PathBuf>>,
     cancel_scan: AtomicBool,
+    max_file_bytes: AtomicU64,
+    store_lock: RwLock<()>,
+    auto_backup: RwLock<Option<backups::AutoBackup>>,
+    watched_files: RwLock<HashMap<PathBuf, watcher::WatchedFile>>,
+    dialects: RwLock<HashMap<PathBuf, quoting::Dialect>>,
+    app_lock: RwLock<app_lock::LockState>,
+    access: RwLock<access::AccessState>,
+    scan_snapshot: RwLock<changes::ScanSnapshot>,
+    open_request: RwLock<Option<launch::OpenRequest>>,
+    palette_index: RwLock<palette::PaletteIndex>,
+    value_index: RwLock<value_index::ValueIndex>,
+    secret_rules: RwLock<secrets::SecretRules>,
+    running_processes: runner::RunningProcesses,
+    last_scan_duration: RwLock<Option<Duration>>,
 }
 
 #[derive(Error, Debug, Serialize)]
-#[serde(tag = "type", content = "message")]
+#[serde(tag = "type", content = "message", remote = "Self")]
 enum AppError {
     #[error("Invalid root path")]
     InvalidRootPath,
@@ -88,30 +474,172 @@ enum AppError {
     #[error("Scan canceled")]
     ScanCanceled,
     #[error("IO error: {0}")]
-    IoError(String),
+    IoError(IoFailure),
     #[error("Regex error")]
     RegexError,
+    #[error("Invalid schema: {0}")]
+    SchemaError(String),
+    #[error("Project group not found")]
+    GroupNotFound,
+    #[error("Key not found: {0}")]
+    KeyNotFound(String),
+    #[error("Clipboard error: {0}")]
+    ClipboardError(String),
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("File is too large")]
+    FileTooLarge { size: u64, limit: u64 },
+    #[error("File is not a text file")]
+    NotText,
+    #[error("Crypto error: {0}")]
+    CryptoError(String),
+    #[error("Bundle error: {0}")]
+    BundleError(String),
+    #[error("File was modified on disk")]
+    Conflict {
+        #[serde(rename = "currentContent")]
+        current_content: String,
+        #[serde(rename = "currentHash")]
+        current_hash: String,
+        #[serde(rename = "modifiedAt")]
+        modified_at: i64,
+    },
+    #[error("App is locked")]
+    Locked,
+    #[error("File access expired")]
+    AccessExpired,
+}
+
+/// Every error a command returns is serialized once on its way to the window, which
+/// makes this the place failures are logged.
+impl Serialize for AppError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        logging::failure(self);
+        AppError::serialize(self, serializer)
+    }
 }
 
 impl From<std::io::Error> for AppError {
     fn from(value: std::io::Error) -> Self {
-        AppError::IoError(value.to_string())
+        AppError::IoError(IoFailure::from(value))
+    }
+}
+
+/// Runs the body of a command on the blocking thread pool. File access, key
+/// derivation and child processes would otherwise hold one of the async runtime's
+/// workers and stall every other command queued behind it.
+async fn run_blocking<T, F>(app: AppHandle, work: F) -> Result<T, AppError>
+where
+    T: Send + 'static,
+    F: FnOnce(AppHandle, State<'_, AppState>) -> Result<T, AppError> + Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(move || work(app.clone(), app.state::<AppState>()))
+        .await
+        .map_err(|error| AppError::io(IoCode::Other, format!("Command failed: {}", error)))?
+}
+
+/// Path spelling used for comparisons and ids. macOS and Windows volumes are
+/// case-insensitive by default, so `C:\Repo\.env` and `c:\repo\.ENV` share a key there.
+fn path_key(path: &Path) -> String {
+    let display = path.to_string_lossy();
+    if CASE_INSENSITIVE_FS {
+        display.to_lowercase()
+    } else {
+        display.into_owned()
+    }
+}
+
+fn path_starts_with(path: &Path, base: &Path) -> bool {
+    if CASE_INSENSITIVE_FS {
+        Path::new(&path_key(path)).starts_with(path_key(base))
+    } else {
+        path.starts_with(base)
+    }
+}
+
+/// Whether `set` holds `path`, or on case-insensitive platforms a differently-cased
+/// entry that resolves to the same file.
+fn contains_path(set: &HashSet<PathBuf>, path: &Path) -> bool {
+    if set.contains(path) {
+        return true;
     }
+    CASE_INSENSITIVE_FS
+        && set.iter().any(|entry| {
+            path_key(entry) == path_key(path)
+                && same_file::is_same_file(entry, path).unwrap_or(false)
+        })
 }
 
 fn hash_path(path: &Path) -> String {
     let mut hasher = Sha256::new();
-    hasher.update(path.to_string_lossy().as_bytes());
+    hasher.update(path_key(path).as_bytes());
     format!("{:x}", hasher.finalize())
 }
 
+fn hash_content(content: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content);
+    format!("{:x}", hasher.finalize())
+}
+
+fn modified_millis(metadata: &fs::Metadata) -> i64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|dur| dur.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn max_file_bytes(state: &AppState) -> u64 {
+    match state.max_file_bytes.load(Ordering::SeqCst) {
+        0 => DEFAULT_MAX_FILE_BYTES,
+        limit => limit,
+    }
+}
+
+/// Reads an env file as text, refusing files above the configured size ceiling and
+/// content that looks binary (NUL bytes or invalid UTF-8).
+fn read_env_text(state: &AppState, path: &Path) -> Result<String, AppError> {
+    let size = fs::metadata(path).at(path)?.len();
+    let limit = max_file_bytes(state);
+    if size > limit {
+        return Err(AppError::FileTooLarge { size, limit });
+    }
+    let bytes = fs::read(path).at(path)?;
+    let sniff_len = bytes.len().min(BINARY_SNIFF_BYTES);
+    if bytes[..sniff_len].contains(&0) {
+        return Err(AppError::NotText);
+    }
+    String::from_utf8(bytes).map_err(|_| AppError::NotText)
+}
+
 fn normalize_path(path: &Path) -> Result<PathBuf, AppError> {
-    let canonical = path.canonicalize().map_err(AppError::from)?;
+    let canonical = path.canonicalize().at(path)?;
     Ok(canonical)
 }
 
-fn is_env_file_name(name: &str, regex: &Regex) -> bool {
-    regex.is_match(name)
+fn is_example_file(name: &str) -> bool {
+    EXAMPLE_SUFFIXES.iter().any(|suffix| name.ends_with(suffix))
+}
+
+fn env_file_regex() -> Result<Regex, AppError> {
+    Regex::new(r"^\.env(\..+)?$").map_err(|_| AppError::RegexError)
+}
+
+fn is_env_file_name(name: &str, regex: &Regex, patterns: &DiscoveryPatterns) -> bool {
+    if regex.is_match(name) {
+        return if is_example_file(name) {
+            patterns.examples
+        } else {
+            patterns.dotenv
+        };
+    }
+    match name {
+        direnv::ENVRC_FILE_NAME => patterns.envrc,
+        ".flaskenv" => patterns.flaskenv,
+        _ => patterns.suffix_env && name.ends_with(".env"),
+    }
 }
 
 fn is_ignored_dir(entry: &walkdir::DirEntry) -> bool {
@@ -135,79 +663,287 @@ fn is_ignored_dir(entry: &walkdir::DirEntry) -> bool {
         .unwrap_or(false)
 }
 
+/// Symlinks, and on Windows any reparse point such as an NTFS junction. The scan never
+/// descends into these, which also rules out cycles.
+#[cfg(windows)]
+fn is_link_like(entry: &walkdir::DirEntry) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x400;
+    entry.path_is_symlink()
+        || entry
+            .metadata()
+            .map(|metadata| metadata.file_attributes() & FILE_ATTRIBUTE_REPARSE_POINT != 0)
+            .unwrap_or(false)
+}
+
+#[cfg(not(windows))]
+fn is_link_like(entry: &walkdir::DirEntry) -> bool {
+    entry.path_is_symlink()
+}
+
+/// Returns the allowed files whose folder hashes to `group_id`, sorted by path.
+fn files_in_group(state: &AppState, group_id: &str) -> Result<Vec<PathBuf>, AppError> {
+    let mut files: Vec<PathBuf> = state
+        .allowed_files
+        .read()
+        .iter()
+        .filter(|path| {
+            path.parent()
+                .map(|folder| hash_path(folder) == group_id)
+                .unwrap_or(false)
+        })
+        .cloned()
+        .collect();
+    if files.is_empty() {
+        return Err(AppError::GroupNotFound);
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn build_exclude_set(globs: &[String]) -> Result<GlobSet, AppError> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in globs {
+        let glob = Glob::new(pattern).map_err(|error| AppError::InvalidInput(error.to_string()))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|error| AppError::InvalidInput(error.to_string()))
+}
+
+/// Loads `.envshelfignore` (gitignore syntax) from the scan root, if present.
+fn load_ignore_file(root: &Path) -> Result<Gitignore, AppError> {
+    let path = root.join(IGNORE_FILE_NAME);
+    if !path.is_file() {
+        return Ok(Gitignore::empty());
+    }
+    let mut builder = GitignoreBuilder::new(root);
+    if let Some(error) = builder.add(&path) {
+        return Err(AppError::InvalidInput(error.to_string()));
+    }
+    builder
+        .build()
+        .map_err(|error| AppError::InvalidInput(error.to_string()))
+}
+
+fn is_single_file(state: &AppState, normalized: &Path) -> bool {
+    contains_path(&state.single_files.read(), normalized)
+}
+
 fn ensure_allowed_path(state: &AppState, path: &Path) -> Result<(), AppError> {
-    let root_guard = state.root_path.lock().map_err(|_| AppError::InvalidRootPath)?;
-    let root = root_guard.clone().ok_or(AppError::InvalidRootPath)?;
+    app_lock::ensure_unlocked(state)?;
+    access::ensure_active(state)?;
     let normalized = normalize_path(path)?;
-    if !normalized.starts_with(&root) {
-        return Err(AppError::PathNotAllowed);
+    if !is_single_file(state, &normalized) {
+        let root = state
+            .root_path
+            .read()
+            .clone()
+            .ok_or(AppError::InvalidRootPath)?;
+        // The parent is resolved as given, before a symlinked file is followed, so a
+        // folder link or `..` leading out of the root is refused as well as a target
+        // that resolves outside it.
+        let parent_inside = path
+            .parent()
+            .and_then(|parent| normalize_path(parent).ok())
+            .map(|parent| path_starts_with(&parent, &root))
+            .unwrap_or(false);
+        if !parent_inside || !path_starts_with(&normalized, &root) {
+            return Err(AppError::PathNotAllowed);
+        }
     }
-    let allowed_guard = state.allowed_files.lock().map_err(|_| AppError::PathNotAllowed)?;
-    if !allowed_guard.contains(&normalized) {
+    let allowed = contains_path(&state.allowed_files.read(), &normalized);
+    if !allowed {
+        if access::was_revoked(state, &normalized) {
+            return Err(AppError::AccessExpired);
+        }
         return Err(AppError::PathNotAllowed);
     }
     Ok(())
 }
 
 #[tauri::command]
-fn cancel_scan(state: State<'_, AppState>) -> Result<(), AppError> {
+async fn cancel_scan(state: State<'_, AppState>) -> Result<(), AppError> {
     state.cancel_scan.store(true, Ordering::SeqCst);
     Ok(())
 }
 
 #[tauri::command]
-fn scan_env_files(state: State<'_, AppState>, root_path: String) -> Result<ScanResult, AppError> {
-    let root = normalize_path(Path::new(&root_path))?;
-    state.cancel_scan.store(false, Ordering::SeqCst);
+async fn set_max_file_size(state: State<'_, AppState>, max_bytes: u64) -> Result<(), AppError> {
+    if max_bytes == 0 {
+        return Err(AppError::InvalidInput(
+            "Maximum file size must be greater than zero".to_string(),
+        ));
+    }
+    state.max_file_bytes.store(max_bytes, Ordering::SeqCst);
+    Ok(())
+}
 
-    let regex = Regex::new(r"^\.env(\..+)?$").map_err(|_| AppError::RegexError)?;
+/// Env files found under a root, before any app state is updated.
+struct WalkResult {
+    groups: Vec<ProjectGroup>,
+    allowed_files: HashSet<PathBuf>,
+    stamps: HashMap<PathBuf, changes::FileStamp>,
+    slow_paths: Vec<SlowPath>,
+    skipped_paths: Vec<SkippedPath>,
+}
+
+fn slow_path(path: &Path, reason: SlowReason) -> SlowPath {
+    SlowPath {
+        path: ipc_path::encode_path(path),
+        reason,
+    }
+}
+
+fn walk_root(
+    root: &Path,
+    patterns: &DiscoveryPatterns,
+    excludes: &GlobSet,
+    ignore: &Gitignore,
+    max_bytes: u64,
+    cancel: &AtomicBool,
+) -> Result<WalkResult, AppError> {
+    let regex = env_file_regex()?;
 
     let mut groups: BTreeMap<PathBuf, Vec<EnvFileRef>> = BTreeMap::new();
     let mut allowed_files: HashSet<PathBuf> = HashSet::new();
+    let mut stamps = HashMap::new();
+    let network_mounts = mounts::network_mounts_under(root);
+    let mut slow_paths: Vec<SlowPath> = network_mounts
+        .iter()
+        .map(|mount| slow_path(mount, SlowReason::Network))
+        .collect();
+    if mounts::is_network_path(root) {
+        slow_paths.insert(0, slow_path(root, SlowReason::Network));
+    }
+    let mut skipped_paths = Vec::new();
 
-    for entry in WalkDir::new(&root)
+    let mut walker = WalkDir::new(root)
         .follow_links(false)
         .into_iter()
-        .filter_entry(|e| !is_ignored_dir(e))
-    {
-        if state.cancel_scan.load(Ordering::SeqCst) {
+        .filter_entry(|e| {
+            !is_ignored_dir(e)
+                && (e.depth() == 0 || !is_link_like(e))
+                && !network_mounts.iter().any(|mount| e.path() == mount)
+                && !ignore.matched(e.path(), e.file_type().is_dir()).is_ignore()
+                && !e
+                    .path()
+                    .strip_prefix(root)
+                    .map(|relative| excludes.is_match(relative))
+                    .unwrap_or(false)
+        });
+    loop {
+        let started = Instant::now();
+        let Some(entry) = walker.next() else {
+            break;
+        };
+        if cancel.load(Ordering::SeqCst) {
             return Err(AppError::ScanCanceled);
         }
-        let entry = entry.map_err(|e| AppError::IoError(e.to_string()))?;
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(error) if error.depth() > 0 => {
+                if let Some(path) = error.path() {
+                    skipped_paths.push(SkippedPath {
+                        path: ipc_path::encode_path(path),
+                        reason: error.to_string(),
+                    });
+                }
+                continue;
+            }
+            Err(error) => {
+                let path = error.path().unwrap_or(root).to_path_buf();
+                return Err(AppError::from(std::io::Error::from(error)).with_path(&path));
+            }
+        };
+        if started.elapsed() >= SLOW_DIR_TIMEOUT {
+            let folder = entry.path().parent().unwrap_or(root);
+            slow_paths.push(slow_path(folder, SlowReason::Timeout));
+            walker.skip_current_dir();
+            continue;
+        }
         if !entry.file_type().is_file() {
             continue;
         }
         let file_name = entry.file_name().to_string_lossy();
-        if !is_env_file_name(&file_name, &regex) {
+        if !is_env_file_name(&file_name, &regex, patterns) {
             continue;
         }
 
         let path = entry.path().to_path_buf();
-        let metadata = fs::metadata(&path)?;
-        let modified = metadata.modified().ok();
-        let modified_at = modified
-            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
-            .map(|dur| dur.as_millis() as i64)
-            .unwrap_or(0);
-
-        let folder = path.parent().unwrap_or(&root).to_path_buf();
+        // Oversized files are listed without being read, so a mis-named dump cannot
+        // stall the scan.
+        let read = fs::metadata(&path).and_then(|metadata| {
+            let bytes = if metadata.len() > max_bytes {
+                None
+            } else {
+                Some(fs::read(&path)?)
+            };
+            Ok((metadata, bytes))
+        });
+        let (metadata, bytes) = match read {
+            Ok(read) => read,
+            Err(error) => {
+                skipped_paths.push(SkippedPath {
+                    path: ipc_path::encode_path(&path),
+                    reason: error.to_string(),
+                });
+                continue;
+            }
+        };
+        let modified_at = modified_millis(&metadata);
+        let skip_reason = bytes.is_none().then(|| {
+            format!(
+                "File is {} bytes, above the {} byte limit",
+                metadata.len(),
+                max_bytes
+            )
+        });
+        let bytes = bytes.unwrap_or_default();
+        let content_hash = hash_content(&bytes);
+        let summary = match std::str::from_utf8(&bytes) {
+            Ok(contents) => ParseSummary::of(&parse_env_lines(contents)),
+            Err(_) => ParseSummary {
+                has_parse_errors: true,
+                ..ParseSummary::default()
+            },
+        };
+
+        let folder = path.parent().unwrap_or(root).to_path_buf();
+        let normalized = normalize_path(&path)?;
+        stamps.insert(
+            normalized.clone(),
+            changes::FileStamp::new(&metadata, content_hash.clone()),
+        );
+        let (modified_by, app_written_at) = provenance::of(&normalized, &content_hash);
         let env_ref = EnvFileRef {
             id: hash_path(&path),
-            absolute_path: path.to_string_lossy().to_string(),
+            absolute_path: ipc_path::encode_path(&path),
             file_name: file_name.to_string(),
-            folder_path: folder.to_string_lossy().to_string(),
+            folder_path: ipc_path::encode_path(&folder),
             size: metadata.len(),
             modified_at,
+            content_hash,
+            key_count: summary.key_count,
+            comment_count: summary.comment_count,
+            has_parse_errors: summary.has_parse_errors,
+            modified_by,
+            app_written_at,
+            skipped: skip_reason.is_some(),
+            skip_reason,
         };
 
+        if !env_ref.skipped {
+            allowed_files.insert(normalized);
+        }
         groups.entry(folder).or_default().push(env_ref);
-        allowed_files.insert(normalize_path(&path)?);
     }
 
     let mut result_groups: Vec<ProjectGroup> = groups
         .into_iter()
         .map(|(folder, mut files)| {
-            files.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+            files.sort_by(|a, b| sorting::natural_cmp(&a.file_name, &b.file_name));
             let name = folder
                 .file_name()
                 .map(|n| n.to_string_lossy().to_string())
@@ -215,73 +951,555 @@ fn scan_env_files(state: State<'_, AppState>, root_path: String) -> Result<ScanR
             ProjectGroup {
                 id: hash_path(&folder),
                 name,
-                root_path: folder.to_string_lossy().to_string(),
+                root_path: ipc_path::encode_path(&folder),
                 env_files: files,
+                framework: framework::detect_framework(&folder),
             }
         })
         .collect();
 
-    result_groups.sort_by(|a, b| a.name.cmp(&b.name));
-
-    let mut root_guard = state.root_path.lock().map_err(|_| AppError::InvalidRootPath)?;
-    *root_guard = Some(root.clone());
+    sorting::sort_groups(&mut result_groups, sorting::GroupSort::Name);
 
-    let mut allowed_guard = state.allowed_files.lock().map_err(|_| AppError::PathNotAllowed)?;
-    *allowed_guard = allowed_files;
-
-    Ok(ScanResult {
-        root_path: root.to_string_lossy().to_string(),
+    Ok(WalkResult {
         groups: result_groups,
+        allowed_files,
+        stamps,
+        slow_paths,
+        skipped_paths,
     })
 }
 
-fn parse_env_lines(raw: &str) -> Vec<EnvLine> {
-    let kv_regex = Regex::new(r"^\s*(export\s+)?([A-Za-z_][A-Za-z0-9_]*)\s*=\s*(.*)$")
-        .unwrap_or_else(|_| Regex::new("$").unwrap());
-
-    raw.split('\n')
-        .map(|line| {
-            let trimmed = line.trim();
-            let line = line.trim_end_matches('\r');
-            if trimmed.is_empty() {
-                EnvLine::Blank
-            } else if trimmed.starts_with('#') || trimmed.starts_with(';') {
-                EnvLine::Comment {
-                    raw: line.to_string(),
-                }
-            } else if let Some(caps) = kv_regex.captures(line) {
-                let has_export = caps.get(1).is_some();
-                let key = caps.get(2).map(|m| m.as_str()).unwrap_or("");
-                let value = caps.get(3).map(|m| m.as_str()).unwrap_or("");
-                EnvLine::Kv {
-                    key: key.to_string(),
-                    value: value.to_string(),
-                    has_export,
-                    raw: Some(line.to_string()),
+/// Runs `walk_root` on a worker thread so cancelling returns at once even while the
+/// walk is stuck in a blocking read; the abandoned worker stops at its next entry.
+fn walk_root_interruptible(
+    root: &Path,
+    patterns: &DiscoveryPatterns,
+    excludes: &GlobSet,
+    ignore: &Gitignore,
+    max_bytes: u64,
+    cancel: &AtomicBool,
+) -> Result<WalkResult, AppError> {
+    let abandon = Arc::new(AtomicBool::new(false));
+    let (sender, receiver) = mpsc::channel();
+    let worker = (
+        root.to_path_buf(),
+        patterns.clone(),
+        excludes.clone(),
+        ignore.clone(),
+        abandon.clone(),
+    );
+    thread::spawn(move || {
+        let (root, patterns, excludes, ignore, abandon) = worker;
+        let _ = sender.send(walk_root(
+            &root, &patterns, &excludes, &ignore, max_bytes, &abandon,
+        ));
+    });
+    loop {
+        match receiver.recv_timeout(CANCEL_POLL) {
+            Ok(result) => return result,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                if cancel.load(Ordering::SeqCst) {
+                    abandon.store(true, Ordering::SeqCst);
+                    return Err(AppError::ScanCanceled);
                 }
-            } else {
-                EnvLine::Unknown {
-                    raw: line.to_string(),
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return Err(AppError::io(IoCode::Other, "Scan stopped unexpectedly"));
+            }
+        }
+    }
+}
+
+#[tauri::command]
+async fn scan_env_files(
+    app: AppHandle,
+    root_path: String,
+    patterns: Option<DiscoveryPatterns>,
+    exclude_globs: Option<Vec<String>>,
+    sort: Option<sorting::GroupSort>,
+) -> Result<ScanResult, AppError> {
+    run_blocking(app, move |app, state| {
+        app_lock::ensure_unlocked(&state)?;
+        let started = Instant::now();
+        let root = normalize_path(&ipc_path::decode_path(&root_path))?;
+        let patterns = patterns.unwrap_or_default();
+        let excludes = build_exclude_set(&exclude_globs.unwrap_or_default())?;
+        state.cancel_scan.store(false, Ordering::SeqCst);
+
+        let ignore = load_ignore_file(&root)?;
+        let WalkResult {
+            groups: mut result_groups,
+            mut allowed_files,
+            stamps,
+            slow_paths,
+            skipped_paths,
+        } = walk_root_interruptible(
+            &root,
+            &patterns,
+            &excludes,
+            &ignore,
+            max_file_bytes(&state),
+            &state.cancel_scan,
+        )?;
+        sorting::sort_groups(&mut result_groups, sort.unwrap_or_default());
+
+        *state.root_path.write() = Some(root.clone());
+        allowed_files.extend(state.single_files.read().iter().cloned());
+        *state.allowed_files.write() = allowed_files;
+        access::reset(&state);
+        let mut result = ScanResult {
+            root_path: ipc_path::encode_path(&root),
+            groups: result_groups,
+            slow_paths,
+            skipped_paths,
+            diff: None,
+        };
+        result.diff = changes::record(
+            &state,
+            changes::Discovery {
+                root: root.clone(),
+                patterns,
+                excludes,
+                ignore,
+            },
+            stamps,
+            &result,
+        );
+        palette::rebuild(&state, &result);
+        value_index::rebuild(&state);
+        *state.last_scan_duration.write() = Some(started.elapsed());
+        let scanned: Vec<PathBuf> = state.allowed_files.read().iter().cloned().collect();
+        if let Err(error) = backups::migrate_folder_copies(&app, &scanned) {
+            tracing::warn!(
+                "failed to migrate backup copies: {}",
+                logging::redacted(&error)
+            );
+        }
+        if let Err(error) = cleanup::remember_root(&app, &state, &root) {
+            tracing::warn!("failed to record scan root: {}", logging::redacted(&error));
+        }
+        if let Err(error) = tray::remember_project(&app, &state, &root) {
+            tracing::warn!(
+                "failed to record recent project: {}",
+                logging::redacted(&error)
+            );
+        }
+
+        Ok(result)
+    })
+    .await
+}
+
+fn split_inline_comment(value: &str) -> (String, Option<String>) {
+    let comment_at = |index: usize| {
+        (
+            value[..index].trim_end().to_string(),
+            Some(value[index + 1..].trim().to_string()),
+        )
+    };
+
+    if let Some(quote) = value.chars().next().filter(|c| matches!(c, '"' | '\'' | '`')) {
+        let mut escaped = false;
+        for (index, ch) in value.char_indices().skip(1) {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' && quote == '"' {
+                escaped = true;
+            } else if ch == quote {
+                let rest = &value[index + 1..];
+                let offset = rest.len() - rest.trim_start().len();
+                if rest.trim_start().starts_with('#') {
+                    return comment_at(index + 1 + offset);
                 }
+                return (value.to_string(), None);
+            }
+        }
+        return (value.to_string(), None);
+    }
+
+    let mut previous_is_space = true;
+    for (index, ch) in value.char_indices() {
+        if ch == '#' && previous_is_space {
+            return comment_at(index);
+        }
+        previous_is_space = ch.is_whitespace();
+    }
+    (value.to_string(), None)
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    for quote in ['"', '\'', '`'] {
+        if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+            return &value[1..value.len() - 1];
+        }
+    }
+    value
+}
+
+fn infer_value_type(value: &str) -> ValueType {
+    let value = unquote(value);
+    if value.is_empty() {
+        return ValueType::Empty;
+    }
+    let lower = value.to_ascii_lowercase();
+    if matches!(lower.as_str(), "true" | "false" | "yes" | "no" | "on" | "off") {
+        return ValueType::Boolean;
+    }
+    if value.parse::<i64>().is_ok() {
+        return ValueType::Integer;
+    }
+    if value.parse::<f64>().is_ok() && value.chars().any(|c| c == '.' || c == 'e' || c == 'E') {
+        return ValueType::Float;
+    }
+    if (value.starts_with('{') || value.starts_with('['))
+        && serde_json::from_str::<serde_json::Value>(value).is_ok()
+    {
+        return ValueType::Json;
+    }
+    static URL_REGEX: OnceLock<Option<Regex>> = OnceLock::new();
+    let url_regex =
+        URL_REGEX.get_or_init(|| Regex::new(r"^[A-Za-z][A-Za-z0-9+.\-]*://\S+$").ok());
+    if url_regex.as_ref().map(|re| re.is_match(value)).unwrap_or(false) {
+        return ValueType::Url;
+    }
+    let is_windows_path =
+        value.as_bytes()[0].is_ascii_alphabetic() && value.get(1..3) == Some(":\\");
+    if value.starts_with('/')
+        || value.starts_with("./")
+        || value.starts_with("../")
+        || value.starts_with("~/")
+        || is_windows_path
+    {
+        return ValueType::Path;
+    }
+    let is_base64_charset = value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '='));
+    let has_mixed_chars = value.chars().any(|c| c.is_ascii_digit())
+        && value.chars().any(|c| c.is_ascii_uppercase())
+        && value.chars().any(|c| c.is_ascii_lowercase());
+    if value.len() >= 16 && value.len() % 4 == 0 && is_base64_charset && has_mixed_chars {
+        return ValueType::Base64;
+    }
+    ValueType::String
+}
+
+/// True when a value runs a command on expansion: an unescaped `$(` or backtick
+/// outside single quotes.
+fn has_command_substitution(value: &str) -> bool {
+    let value = value.trim();
+    if value.starts_with('\'') {
+        return false;
+    }
+    let mut chars = value.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => {
+                chars.next();
             }
+            '$' if chars.peek() == Some(&'(') => return true,
+            '`' => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+fn comment_text(raw: &str) -> &str {
+    let trimmed = raw.trim_start();
+    let body = trimmed
+        .strip_prefix('#')
+        .or_else(|| trimmed.strip_prefix(';'))
+        .unwrap_or(trimmed);
+    body.strip_prefix(' ').unwrap_or(body).trim_end()
+}
+
+fn comment_block_before(lines: &[EnvLine], index: usize) -> Option<String> {
+    let block: Vec<&str> = lines[..index]
+        .iter()
+        .rev()
+        .map_while(|line| match line {
+            EnvLine::Comment { raw, .. } => Some(comment_text(raw)),
+            _ => None,
         })
-        .collect()
+        .collect();
+    if block.is_empty() {
+        return None;
+    }
+    Some(block.into_iter().rev().collect::<Vec<_>>().join("\n"))
+}
+
+fn attach_descriptions(lines: &mut [EnvLine]) {
+    for index in 0..lines.len() {
+        let block = comment_block_before(lines, index);
+        if let EnvLine::Kv { description, .. } = &mut lines[index] {
+            *description = block;
+        }
+    }
+}
+
+fn kv_line_regex() -> Regex {
+    Regex::new(r"^\s*(export\s+)?([A-Za-z_][A-Za-z0-9_]*)(\s*=\s*)(.*)$")
+        .unwrap_or_else(|_| Regex::new("$").unwrap())
+}
+
+/// Parses one line without its `\n`; descriptions are attached once all lines are in.
+fn parse_line(line: &str, line_number: usize, kv_regex: &Regex) -> EnvLine {
+    let trimmed = line.trim();
+    let line = line.trim_end_matches('\r');
+    let position = LinePosition::line(line_number);
+    if trimmed.is_empty() {
+        EnvLine::Blank { position }
+    } else if trimmed.starts_with('#') || trimmed.starts_with(';') {
+        EnvLine::Comment {
+            raw: line.to_string(),
+            position,
+        }
+    } else if let Some(caps) = kv_regex.captures(line) {
+        let has_export = caps.get(1).is_some();
+        let key = caps.get(2).map(|m| m.as_str()).unwrap_or("");
+        let separator = caps.get(3).map(|m| m.as_str()).unwrap_or("=");
+        let raw_value = caps.get(4).map(|m| m.as_str()).unwrap_or("");
+        let (value, inline_comment) = split_inline_comment(raw_value);
+        // The value is a prefix of what follows the separator.
+        let value_start = caps.get(4).map(|m| m.start()).unwrap_or(line.len());
+        let position = LinePosition {
+            key_span: caps.get(2).map(|m| TextSpan::of(line, m.start(), m.end())),
+            value_span: Some(TextSpan::of(line, value_start, value_start + value.len())),
+            ..position
+        };
+        let value_type = infer_value_type(&value);
+        let dangerous = has_command_substitution(&value);
+        EnvLine::Kv {
+            key: key.to_string(),
+            value,
+            has_export,
+            separator: separator.to_string(),
+            inline_comment,
+            description: None,
+            value_type,
+            dangerous,
+            raw: Some(line.to_string()),
+            position,
+        }
+    } else if let Some(directive) = parse_directive(line, &position) {
+        directive
+    } else {
+        let (reason, severity, message) = diagnose_line(line);
+        EnvLine::Invalid {
+            raw: line.to_string(),
+            reason,
+            severity,
+            message,
+            position,
+        }
+    }
+}
+
+/// `unset KEY` and `KEY+=value`, the shell directives direnv-style env files use.
+fn parse_directive(line: &str, position: &LinePosition) -> Option<EnvLine> {
+    static UNSET_REGEX: OnceLock<Option<Regex>> = OnceLock::new();
+    static APPEND_REGEX: OnceLock<Option<Regex>> = OnceLock::new();
+    let unset_regex =
+        UNSET_REGEX.get_or_init(|| Regex::new(r"^\s*unset\s+([A-Za-z_][A-Za-z0-9_]*)\s*$").ok());
+    let append_regex =
+        APPEND_REGEX.get_or_init(|| Regex::new(r"^\s*([A-Za-z_][A-Za-z0-9_]*)\+=(.*)$").ok());
+
+    if let Some(caps) = unset_regex.as_ref().and_then(|re| re.captures(line)) {
+        let key = caps.get(1)?;
+        return Some(EnvLine::Unset {
+            key: key.as_str().to_string(),
+            raw: Some(line.to_string()),
+            position: LinePosition {
+                key_span: Some(TextSpan::of(line, key.start(), key.end())),
+                ..position.clone()
+            },
+        });
+    }
+    let caps = append_regex.as_ref()?.captures(line)?;
+    let (key, rest) = (caps.get(1)?, caps.get(2)?);
+    let (value, inline_comment) = split_inline_comment(rest.as_str());
+    Some(EnvLine::Append {
+        key: key.as_str().to_string(),
+        position: LinePosition {
+            key_span: Some(TextSpan::of(line, key.start(), key.end())),
+            value_span: Some(TextSpan::of(line, rest.start(), rest.start() + value.len())),
+            ..position.clone()
+        },
+        value,
+        inline_comment,
+        raw: Some(line.to_string()),
+    })
+}
+
+/// Whether a quote opened in `text` is still open at its end.
+fn has_open_quote(text: &str) -> bool {
+    let mut open: Option<char> = None;
+    let mut escaped = false;
+    for ch in text.chars() {
+        match open {
+            _ if escaped => escaped = false,
+            Some('"') if ch == '\\' => escaped = true,
+            Some(quote) if ch == quote => open = None,
+            None if matches!(ch, '"' | '\'') => open = Some(ch),
+            _ => {}
+        }
+    }
+    open.is_some()
+}
+
+/// Explains why `line`, which is not blank or a comment, is not an assignment either.
+fn diagnose_line(line: &str) -> (ParseReason, Severity, String) {
+    let body = line.trim();
+    let body = body
+        .strip_prefix("export")
+        .filter(|rest| rest.starts_with(char::is_whitespace))
+        .map(str::trim_start)
+        .unwrap_or(body);
+    let (head, has_equals) = match body.split_once('=') {
+        Some((head, _)) => (head.trim(), true),
+        None => (body, false),
+    };
+    if has_open_quote(head) {
+        (
+            ParseReason::UnterminatedQuote,
+            Severity::Warning,
+            "A quote on this line is not closed; if it continues a multi-line value, only \
+             some parsers read it"
+                .to_string(),
+        )
+    } else if !has_equals {
+        (
+            ParseReason::MissingEquals,
+            Severity::Error,
+            "This line has no =; assignments are written KEY=value".to_string(),
+        )
+    } else if head.is_empty() {
+        (
+            ParseReason::InvalidKey,
+            Severity::Error,
+            "Nothing before = names the key".to_string(),
+        )
+    } else {
+        (
+            ParseReason::InvalidKey,
+            Severity::Error,
+            format!(
+                "{} is not a valid key; use letters, digits and _, and do not start with a digit",
+                head
+            ),
+        )
+    }
+}
+
+fn parse_env_lines(raw: &str) -> Vec<EnvLine> {
+    let kv_regex = kv_line_regex();
+    let mut lines: Vec<EnvLine> = raw
+        .split('\n')
+        .enumerate()
+        .map(|(index, line)| parse_line(line, index + 1, &kv_regex))
+        .collect();
+    attach_descriptions(&mut lines);
+    lines
+}
+
+#[derive(Default)]
+struct ParseSummary {
+    key_count: usize,
+    comment_count: usize,
+    has_parse_errors: bool,
+}
+
+impl ParseSummary {
+    fn of(lines: &[EnvLine]) -> Self {
+        let mut summary = ParseSummary::default();
+        for line in lines {
+            match line {
+                EnvLine::Kv { .. } => summary.key_count += 1,
+                EnvLine::Comment { .. } => summary.comment_count += 1,
+                EnvLine::Invalid { .. } => summary.has_parse_errors = true,
+                EnvLine::Blank { .. } | EnvLine::Unset { .. } | EnvLine::Append { .. } => {}
+            }
+        }
+        summary
+    }
+}
+
+fn default_separator() -> String {
+    "=".to_string()
+}
+
+/// Parses a whole file, splitting off the final newline and line-ending style so
+/// they can be restored exactly by `serialize_with_layout`.
+fn parse_env_text(raw: &str) -> (Vec<EnvLine>, TextLayout) {
+    let line_ending = if raw.contains("\r\n") {
+        LineEnding::Crlf
+    } else {
+        LineEnding::Lf
+    };
+    let trailing_newline = raw.ends_with('\n');
+    let body = if trailing_newline {
+        raw.strip_suffix('\n')
+            .map(|rest| rest.strip_suffix('\r').unwrap_or(rest))
+            .unwrap_or(raw)
+    } else {
+        raw
+    };
+    let lines = if body.is_empty() && trailing_newline {
+        Vec::new()
+    } else {
+        parse_env_lines(body)
+    };
+    (
+        lines,
+        TextLayout {
+            trailing_newline,
+            line_ending,
+        },
+    )
 }
 
 #[tauri::command]
-fn read_env_file(state: State<'_, AppState>, path: String) -> Result<EnvDocument, AppError> {
-    let path_buf = PathBuf::from(&path);
-    ensure_allowed_path(&state, &path_buf)?;
-
-    let contents = fs::read_to_string(&path_buf)?;
-    let lines = parse_env_lines(&contents);
-    let metadata = fs::metadata(&path_buf)?;
-    let modified_at = metadata
-        .modified()
-        .ok()
-        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
-        .map(|dur| dur.as_millis() as i64)
-        .unwrap_or(0);
+async fn read_env_file(
+    app: AppHandle,
+    path: String,
+    mode: Option<parse_mode::ParseMode>,
+) -> Result<EnvDocument, AppError> {
+    run_blocking(app, move |app, state| {
+        load_env_document(&app, &state, path, mode)
+    })
+    .await
+}
+
+fn load_env_document(
+    app: &AppHandle,
+    state: &AppState,
+    path: String,
+    mode: Option<parse_mode::ParseMode>,
+) -> Result<EnvDocument, AppError> {
+    let path_buf = ipc_path::decode_path(&path);
+    ensure_allowed_path(state, &path_buf)?;
+    if let Err(error) = tray::remember_file(app, state, &path_buf) {
+        tracing::warn!(
+            "failed to record recent file: {}",
+            logging::redacted(&error)
+        );
+    }
+
+    let metadata = fs::metadata(&path_buf).at(&path_buf)?;
+    let total_bytes = metadata.len();
+    let parsed = stream_parse::parse_file(&path_buf, max_file_bytes(state), |bytes_read| {
+        let progress = stream_parse::ParseProgress {
+            path: path.clone(),
+            bytes_read,
+            total_bytes,
+        };
+        let _ = app.emit_all(stream_parse::PARSE_PROGRESS_EVENT, progress);
+    })?;
+    let lines = parsed.lines;
+    let summary = ParseSummary::of(&lines);
+    let modified_at = modified_millis(&metadata);
 
     let folder = path_buf.parent().unwrap_or(&path_buf).to_path_buf();
     let file_name = path_buf
@@ -289,77 +1507,910 @@ fn read_env_file(state: State<'_, AppState>, path: String) -> Result<EnvDocument
         .map(|name| name.to_string_lossy().to_string())
         .unwrap_or_else(|| path.to_string());
 
+    let content_hash = parsed.content_hash;
+    let (modified_by, app_written_at) = provenance::of(&path_buf, &content_hash);
     let file = EnvFileRef {
         id: hash_path(&path_buf),
-        absolute_path: path_buf.to_string_lossy().to_string(),
+        absolute_path: ipc_path::encode_path(&path_buf),
         file_name,
-        folder_path: folder.to_string_lossy().to_string(),
-        size: metadata.len(),
+        folder_path: ipc_path::encode_path(&folder),
+        size: parsed.size,
         modified_at,
+        content_hash,
+        key_count: summary.key_count,
+        comment_count: summary.comment_count,
+        has_parse_errors: summary.has_parse_errors,
+        modified_by,
+        app_written_at,
+        skipped: false,
+        skip_reason: None,
     };
 
-    Ok(EnvDocument { file, lines })
+    Ok(EnvDocument {
+        file,
+        prefix_groups: edits::prefix_groups(&lines),
+        deviations: parse_mode::deviations(&lines, mode.unwrap_or_default()),
+        lines,
+        layout: parsed.layout,
+    })
+}
+
+/// Opens one env file outside the scanned root. The native file picker decides the
+/// path, starting at `path`, so the webview cannot grant itself access to arbitrary
+/// files. Returns `None` when the picker is dismissed.
+#[tauri::command]
+async fn allow_single_file(
+    app: AppHandle,
+    path: Option<String>,
+) -> Result<Option<EnvDocument>, AppError> {
+    run_blocking(app, move |app, state| {
+        let mut dialog = FileDialogBuilder::new().set_title("Open env file");
+        if let Some(path) = path.as_deref().map(Path::new) {
+            match path.parent() {
+                Some(folder) if path.is_file() => {
+                    dialog = dialog.set_directory(folder);
+                    if let Some(name) = path.file_name() {
+                        dialog = dialog.set_file_name(&name.to_string_lossy());
+                    }
+                }
+                _ => dialog = dialog.set_directory(path),
+            }
+        }
+        let Some(picked) = dialog.pick_file() else {
+            return Ok(None);
+        };
+        let normalized = normalize_path(&picked)?;
+        if !normalized.is_file() {
+            return Err(AppError::InvalidInput("Not a file".to_string()));
+        }
+        app_lock::ensure_unlocked(&state)?;
+        state.single_files.write().insert(normalized.clone());
+        state.allowed_files.write().insert(normalized.clone());
+        load_env_document(&app, &state, ipc_path::encode_path(&normalized), None).map(Some)
+    })
+    .await
+}
+
+/// Status of each file for spotting external changes. Access is checked before the
+/// file is touched, and a file whose size and mtime match the last scan is not read.
+#[tauri::command]
+async fn check_changes(app: AppHandle, paths: Vec<String>) -> Result<Vec<FileStatus>, AppError> {
+    run_blocking(app, move |_, state| {
+        paths
+            .into_iter()
+            .map(|path| {
+                let path_buf = ipc_path::decode_path(&path);
+                match ensure_allowed_path(&state, &path_buf) {
+                    Ok(()) => {}
+                    // A deleted file no longer resolves; it is reported only if it was allowed.
+                    Err(AppError::IoError(_))
+                        if !path_buf.exists()
+                            && contains_path(&state.allowed_files.read(), &path_buf) =>
+                    {
+                        return Ok(FileStatus {
+                            path,
+                            exists: false,
+                            content_hash: None,
+                            modified_at: None,
+                            size: None,
+                        });
+                    }
+                    Err(error) => return Err(error),
+                }
+                let normalized = normalize_path(&path_buf)?;
+                let metadata = fs::metadata(&normalized).at(&normalized)?;
+                let content_hash = match changes::unchanged_hash(&state, &normalized, &metadata) {
+                    Some(content_hash) => content_hash,
+                    None => hash_content(&fs::read(&normalized).at(&normalized)?),
+                };
+                Ok(FileStatus {
+                    path,
+                    exists: true,
+                    content_hash: Some(content_hash),
+                    modified_at: Some(modified_millis(&metadata)),
+                    size: Some(metadata.len()),
+                })
+            })
+            .collect()
+    })
+    .await
+}
+
+fn check_write_conflict(path: &Path, options: &WriteOptions) -> Result<(), AppError> {
+    if options.expected_hash.is_none() && options.expected_mtime.is_none() {
+        return Ok(());
+    }
+    let current = fs::read(path).at(path)?;
+    let current_hash = hash_content(&current);
+    let modified_at = modified_millis(&fs::metadata(path).at(path)?);
+
+    let hash_changed = options
+        .expected_hash
+        .as_ref()
+        .map(|expected| *expected != current_hash)
+        .unwrap_or(false);
+    let mtime_changed = options
+        .expected_mtime
+        .map(|expected| expected != modified_at)
+        .unwrap_or(false);
+
+    if hash_changed || mtime_changed {
+        return Err(AppError::Conflict {
+            current_content: String::from_utf8_lossy(&current).to_string(),
+            current_hash,
+            modified_at,
+        });
+    }
+    Ok(())
+}
+
+fn serialize_env_line(line: &EnvLine) -> String {
+    match line {
+        EnvLine::Blank { .. } => String::new(),
+        EnvLine::Comment { raw, .. } | EnvLine::Invalid { raw, .. } => raw.clone(),
+        EnvLine::Unset { raw: Some(raw), .. } | EnvLine::Append { raw: Some(raw), .. } => {
+            raw.clone()
+        }
+        EnvLine::Unset { key, .. } => format!("unset {}", key),
+        EnvLine::Append {
+            key,
+            value,
+            inline_comment,
+            ..
+        } => {
+            let comment = inline_comment
+                .as_ref()
+                .map(|comment| format!(" # {}", comment))
+                .unwrap_or_default();
+            format!("{}+={}{}", key, value, comment)
+        }
+        EnvLine::Kv {
+            key,
+            value,
+            has_export,
+            separator,
+            inline_comment,
+            raw,
+            ..
+        } => match raw {
+            Some(raw) => raw.clone(),
+            None => {
+                let prefix = if *has_export { "export " } else { "" };
+                let comment = inline_comment
+                    .as_ref()
+                    .map(|comment| format!(" # {}", comment))
+                    .unwrap_or_default();
+                format!("{}{}{}{}{}", prefix, key, separator, value, comment)
+            }
+        },
+    }
+}
+
+fn serialize_env_lines(lines: &[EnvLine]) -> String {
+    let mut output: Vec<String> = Vec::with_capacity(lines.len());
+    for (index, line) in lines.iter().enumerate() {
+        if let EnvLine::Kv {
+            description: Some(description),
+            raw: None,
+            ..
+        } = line
+        {
+            let existing = comment_block_before(lines, index);
+            if existing.as_deref() != Some(description.as_str()) {
+                output.extend(description.lines().map(|text| format!("# {}", text)));
+            }
+        }
+        output.push(serialize_env_line(line));
+    }
+    output.join("\n")
+}
+
+fn serialize_with_layout(lines: &[EnvLine], layout: TextLayout) -> String {
+    let newline = match layout.line_ending {
+        LineEnding::Lf => "\n",
+        LineEnding::Crlf => "\r\n",
+    };
+    let mut output = serialize_env_lines(lines);
+    if layout.line_ending == LineEnding::Crlf {
+        output = output.replace('\n', newline);
+    }
+    if layout.trailing_newline {
+        output.push_str(newline);
+    }
+    output
+}
+
+/// Serializes lines for a write, applying the caller's quote style to edited values.
+/// Without one, files set to a non-default dialect get that dialect's rules.
+fn serialize_for_write(
+    state: &AppState,
+    path: &Path,
+    lines: &[EnvLine],
+    layout: TextLayout,
+    options: &WriteOptions,
+) -> Result<String, AppError> {
+    let style = options.quote_style.or_else(|| {
+        let dialect = dialects::dialect_for(state, path);
+        (dialect != quoting::Dialect::Dotenv).then_some(quoting::QuoteStyle {
+            policy: quoting::QuotePolicy::WhenNeeded,
+            dialect,
+        })
+    });
+    let Some(style) = style else {
+        return Ok(serialize_with_layout(lines, layout));
+    };
+    let mut lines = lines.to_vec();
+    quoting::apply_quote_style(&mut lines, style)?;
+    Ok(serialize_with_layout(&lines, layout))
+}
+
+/// The value of the last definition of `key`; `None` when there is none or an `unset`
+/// follows it. `KEY+=` lines after it are not applied, so callers that edit the key
+/// refuse keys with directives first (`ensure_no_directives`).
+fn find_kv_value<'a>(lines: &'a [EnvLine], key: &str) -> Option<&'a str> {
+    for line in lines.iter().rev() {
+        match line {
+            EnvLine::Kv {
+                key: line_key,
+                value,
+                ..
+            } if line_key == key => return Some(value),
+            EnvLine::Unset { key: line_key, .. } if line_key == key => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// True when `unset` or `+=` lines change `key`, so no single definition holds the
+/// value the file ends up with.
+fn has_directives(lines: &[EnvLine], key: &str) -> bool {
+    lines.iter().any(|line| {
+        matches!(
+            line,
+            EnvLine::Unset { key: line_key, .. } | EnvLine::Append { key: line_key, .. }
+                if line_key == key
+        )
+    })
+}
+
+/// Refuses to edit `key` through one definition while directives also change it.
+fn ensure_no_directives(lines: &[EnvLine], key: &str) -> Result<(), AppError> {
+    if has_directives(lines, key) {
+        return Err(AppError::InvalidInput(format!(
+            "{} is changed by unset or += lines; edit the file directly",
+            key
+        )));
+    }
+    Ok(())
+}
+
+/// Replaces the value of the last definition of `key`, dropping its raw text so the
+/// serializer rebuilds the line. Returns false when the key is not present. Callers
+/// refuse keys with `unset` or `+=` lines first, since those would change the value
+/// written here.
+fn set_kv_value(lines: &mut [EnvLine], key: &str, new_value: &str) -> bool {
+    let Some(index) = lines
+        .iter()
+        .rposition(|line| matches!(line, EnvLine::Kv { key: line_key, .. } if line_key == key))
+    else {
+        return false;
+    };
+    if let EnvLine::Kv {
+        value,
+        value_type,
+        dangerous,
+        raw,
+        position,
+        ..
+    } = &mut lines[index]
+    {
+        *value = new_value.to_string();
+        *value_type = infer_value_type(new_value);
+        *dangerous = has_command_substitution(new_value);
+        *raw = None;
+        position.value_span = None;
+    }
+    true
 }
 
 #[tauri::command]
-fn write_env_file(
-    state: State<'_, AppState>,
+async fn write_env_file(
+    app: AppHandle,
     path: String,
     content: String,
     options: WriteOptions,
 ) -> Result<(), AppError> {
-    let path_buf = PathBuf::from(&path);
-    ensure_allowed_path(&state, &path_buf)?;
+    run_blocking(app, move |_, state| {
+        let path_buf = ipc_path::decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        write_contents(&state, &path_buf, &content, &options)
+    })
+    .await
+}
 
-    if options.create_backup {
-        let timestamp = Local::now().format("%Y%m%d%H%M%S");
-        let file_name = path_buf
-            .file_name()
-            .map(|name| name.to_string_lossy().to_string())
-            .unwrap_or_else(|| "env".to_string());
-        let backup_name = format!(".{}.backup-{}", file_name, timestamp);
-        let backup_path = path_buf
-            .parent()
-            .unwrap_or_else(|| Path::new("."))
-            .join(backup_name);
-        fs::copy(&path_buf, backup_path)?;
-    }
-
-    let temp_name = format!(
-        ".{}.tmp-{}",
-        path_buf
-            .file_name()
-            .map(|name| name.to_string_lossy().to_string())
-            .unwrap_or_else(|| "env".to_string()),
-        Local::now().format("%Y%m%d%H%M%S")
-    );
-    let temp_path = path_buf
-        .parent()
-        .unwrap_or_else(|| Path::new("."))
-        .join(temp_name);
+#[tauri::command]
+async fn write_env_document(
+    app: AppHandle,
+    path: String,
+    document: EnvDocument,
+    options: WriteOptions,
+) -> Result<(), AppError> {
+    run_blocking(app, move |_, state| {
+        let path_buf = ipc_path::decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        let content =
+            serialize_for_write(&state, &path_buf, &document.lines, document.layout, &options)?;
+        write_contents(&state, &path_buf, &content, &options)
+    })
+    .await
+}
 
-    let mut file = fs::OpenOptions::new()
-        .create(true)
+/// Resolves a write target to its canonical path so temp and backup files are named
+/// from where the data actually goes, not from a path with `..` or symlinked parents.
+/// The target must be a regular file.
+fn write_target(path: &Path) -> Result<PathBuf, AppError> {
+    let target = normalize_path(path)?;
+    if !fs::symlink_metadata(&target).at(&target)?.is_file() || target.parent().is_none() {
+        return Err(AppError::PathNotAllowed);
+    }
+    Ok(target)
+}
+
+/// Path of a sibling of `target` named `.<file name>.<suffix>`.
+fn sibling_path(target: &Path, suffix: &str) -> Result<PathBuf, AppError> {
+    let file_name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or(AppError::PathNotAllowed)?;
+    let parent = target.parent().ok_or(AppError::PathNotAllowed)?;
+    Ok(parent.join(format!(".{}.{}", file_name, suffix)))
+}
+
+/// Opens a new file, refusing to reuse an existing entry so a planted symlink at the
+/// temp or backup name cannot redirect the write.
+fn create_new_file(path: &Path) -> Result<fs::File, AppError> {
+    fs::OpenOptions::new()
         .write(true)
-        .truncate(true)
-        .open(&temp_path)?;
-    file.write_all(content.as_bytes())?;
-    file.flush()?;
-    file.sync_all()?;
+        .create_new(true)
+        .open(path)
+        .at(path)
+}
 
-    fs::rename(&temp_path, &path_buf)?;
-    Ok(())
+/// Stores the file's current content in the backup store, where the backup list, diffs
+/// and key history can see it.
+fn write_backup_copy(path: &Path) -> Result<(), AppError> {
+    backups::snapshot_before_write(&write_target(path)?)
+}
+
+/// Writes `content` to a synced temp file next to `path`, ready to be renamed over it.
+fn stage_temp_file(path: &Path, content: &str) -> Result<PathBuf, AppError> {
+    let target = write_target(path)?;
+    let timestamp = Local::now().format("%Y%m%d%H%M%S");
+    let temp_path = sibling_path(&target, &format!("tmp-{}", timestamp))?;
+    if fs::symlink_metadata(&temp_path).is_ok() {
+        fs::remove_file(&temp_path).at(&temp_path)?;
+    }
+
+    let mut file = create_new_file(&temp_path)?;
+    file.write_all(content.as_bytes()).at(&temp_path)?;
+    file.flush().at(&temp_path)?;
+    file.sync_all().at(&temp_path)?;
+    Ok(temp_path)
+}
+
+/// Writes `content` atomically and records it as the app's own write, so provenance and
+/// the file watcher do not report it as an external change.
+fn write_contents(
+    state: &AppState,
+    path: &Path,
+    content: &str,
+    options: &WriteOptions,
+) -> Result<(), AppError> {
+    let target = write_target(path)?;
+    check_write_conflict(&target, options)?;
+
+    if options.create_backup {
+        write_backup_copy(&target)?;
+    }
+
+    let temp_path = stage_temp_file(&target, content)?;
+    let result = journal::record(&target, &temp_path, content).and_then(|journal_id| {
+        let renamed = fs::rename(&temp_path, &target).at(&target);
+        journal::complete(journal_id);
+        renamed
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result?;
+    if let Err(error) = provenance::record(&target, content.as_bytes()) {
+        tracing::warn!("failed to record write: {}", logging::redacted(&error));
+    }
+    watcher::record_write(state, &target, content)
+}
+
+fn kv_map(lines: &[EnvLine]) -> BTreeMap<&str, &str> {
+    lines
+        .iter()
+        .filter_map(|line| match line {
+            EnvLine::Kv { key, value, .. } => Some((key.as_str(), value.as_str())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Key-level diff between two parsed documents, sorted by key.
+fn diff_key_values(before: &[EnvLine], after: &[EnvLine]) -> Vec<KeyChange> {
+    let before_map = kv_map(before);
+    let after_map = kv_map(after);
+    let mut changes = Vec::new();
+    for (key, value) in &before_map {
+        match after_map.get(key) {
+            None => changes.push(KeyChange {
+                key: key.to_string(),
+                change: KeyChangeKind::Removed,
+                before: Some(value.to_string()),
+                after: None,
+            }),
+            Some(next) if next != value => changes.push(KeyChange {
+                key: key.to_string(),
+                change: KeyChangeKind::Updated,
+                before: Some(value.to_string()),
+                after: Some(next.to_string()),
+            }),
+            Some(_) => {}
+        }
+    }
+    for (key, value) in &after_map {
+        if !before_map.contains_key(key) {
+            changes.push(KeyChange {
+                key: key.to_string(),
+                change: KeyChangeKind::Added,
+                before: None,
+                after: Some(value.to_string()),
+            });
+        }
+    }
+    changes.sort_by(|a, b| a.key.cmp(&b.key));
+    changes
+}
+
+fn unified_diff(path: &str, before: &str, after: &str) -> WritePreview {
+    let diff = TextDiff::from_lines(before, after);
+    let mut additions = 0;
+    let mut deletions = 0;
+    for change in diff.iter_all_changes() {
+        match change.tag() {
+            ChangeTag::Insert => additions += 1,
+            ChangeTag::Delete => deletions += 1,
+            ChangeTag::Equal => {}
+        }
+    }
+    let unified = diff
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", path), &format!("b/{}", path))
+        .to_string();
+
+    WritePreview {
+        path: path.to_string(),
+        changed: additions > 0 || deletions > 0,
+        additions,
+        deletions,
+        diff: unified,
+    }
+}
+
+#[tauri::command]
+async fn preview_write(
+    app: AppHandle,
+    path: String,
+    content: String,
+) -> Result<WritePreview, AppError> {
+    run_blocking(app, move |_, state| {
+        let path_buf = ipc_path::decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+
+        let current = read_env_text(&state, &path_buf)?;
+        Ok(unified_diff(&path, &current, &content))
+    })
+    .await
 }
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(code) = cli::run(&args) {
+        std::process::exit(code);
+    }
+    tauri_plugin_deep_link::prepare("com.envshelf.app");
     tauri::Builder::default()
         .manage(AppState::default())
-        .invoke_handler(tauri::generate_handler![
+        .setup(|app| {
+            if let Err(error) = logging::init(&app.handle()) {
+                // Without a log file there is nowhere else to report this.
+                eprintln!("failed to open log file: {}", error);
+            }
+            if let Err(error) = backups::resume(&app.handle()) {
+                tracing::error!(
+                    "failed to resume auto-backup: {}",
+                    logging::redacted(&error)
+                );
+            }
+            if let Err(error) = dialects::resume(&app.handle()) {
+                tracing::error!(
+                    "failed to load dialect settings: {}",
+                    logging::redacted(&error)
+                );
+            }
+            if let Err(error) = app_lock::resume(&app.handle()) {
+                tracing::error!(
+                    "failed to load app lock settings: {}",
+                    logging::redacted(&error)
+                );
+            }
+            if let Err(error) = access::resume(&app.handle()) {
+                tracing::error!(
+                    "failed to load access settings: {}",
+                    logging::redacted(&error)
+                );
+            }
+            if let Err(error) = value_index::resume(&app.handle()) {
+                tracing::error!(
+                    "failed to load value index settings: {}",
+                    logging::redacted(&error)
+                );
+            }
+            if let Err(error) = secrets::resume(&app.handle()) {
+                tracing::error!("failed to load secret rules: {}", logging::redacted(&error));
+            }
+            if let Err(error) = gitleaks::resume(&app.handle()) {
+                tracing::error!(
+                    "failed to load gitleaks config: {}",
+                    logging::redacted(&error)
+                );
+            }
+            if let Err(error) = journal::init(&app.handle()) {
+                tracing::error!(
+                    "failed to open write journal: {}",
+                    logging::redacted(&error)
+                );
+            }
+            if let Err(error) = provenance::init(&app.handle()) {
+                tracing::error!(
+                    "failed to load write provenance: {}",
+                    logging::redacted(&error)
+                );
+            }
+            watcher::spawn(app.handle());
+            expiry::spawn(app.handle());
+            app_lock::spawn(app.handle());
+            access::spawn(app.handle());
+            cleanup::spawn(app.handle());
+            tray::refresh(&app.handle());
+            launch::register_scheme(&app.handle());
+            launch::open_from_args(&app.handle());
+            Ok(())
+        })
+        .system_tray(tray::system_tray())
+        .on_system_tray_event(tray::on_event)
+        .on_window_event(access::on_window_event)
+        .invoke_handler(logging::logged(tauri::generate_handler![
             scan_env_files,
+            changes::list_changed_files,
+            palette::fuzzy_find,
+            value_index::get_value_index_status,
+            value_index::set_value_index_enabled,
+            value_index::find_value_usages,
+            definitions::where_is_key_defined,
+            report::export_report,
+            secrets::scan_secrets,
+            secrets::get_secret_rules,
+            secrets::set_secret_rules,
+            gitleaks::get_gitleaks_status,
+            gitleaks::set_gitleaks_config,
+            process_env::diff_with_process_env,
+            runner::run_with_env,
+            runner::stop_run,
+            systemd::export_systemd_env,
+            systemd::validate_systemd_env,
+            process_env::import_process_env,
+            templates::list_templates,
+            templates::save_template,
+            templates::delete_template,
+            templates::instantiate_template,
+            snippets::list_snippets,
+            snippets::insert_snippet,
+            backups::key_history,
+            logging::get_recent_logs,
+            logging::set_log_level,
+            diagnostics::diagnostics,
+            findings::export_findings,
+            launch::take_open_request,
+            dropped::validate_drop_path,
             read_env_file,
+            allow_single_file,
             write_env_file,
+            preflight::can_write,
+            write_env_document,
+            batch::write_batch,
+            cleanup::cleanup_temp_files,
+            journal::list_pending_writes,
+            journal::reapply_pending_write,
+            journal::discard_pending_write,
+            preview_write,
+            check_changes,
+            set_max_file_size,
+            schema::validate_env,
+            interpolation::interpolation_graph,
+            clipboard::copy_value_secure,
+            generators::generate_value,
+            codec::transform_value,
+            edits::set_export_prefix,
+            direnv::read_envrc,
+            direnv::write_envrc,
+            variants::compare_variants,
+            variants::promote_keys,
+            variants::resolve_effective_env,
+            stats::workspace_stats,
+            pins::pin_key,
+            pins::unpin_key,
+            pins::list_pins,
+            notes::get_notes,
+            notes::set_note,
+            bundle::export_workspace_bundle,
+            bundle::import_workspace_bundle,
+            backups::configure_auto_backup,
+            backups::get_backup_settings,
+            backups::list_backups,
+            backups::diff_against_backup,
+            watcher::watch_open_file,
+            watcher::unwatch_file,
+            search::find_replace,
+            lint::lint_file,
+            lint::lint_project,
+            format::format_env_file,
+            placeholders::find_unfilled_values,
+            expiry::set_secret_expiry,
+            expiry::list_expiring_secrets,
+            vercel::import_vercel_env,
+            vercel::export_vercel_env,
+            ssm::export_ssm_parameters,
+            vault::export_vault_kv,
+            vault::import_vault_kv,
+            secret_tools::import_tool_export,
+            secret_tools::export_for_tool,
+            onepassword::list_op_references,
+            onepassword::resolve_op_references,
+            github::export_github_secrets,
+            tfvars::export_tfvars,
+            dialects::set_file_dialect,
+            dialects::set_project_dialect,
+            dialects::get_dialect,
+            hooks::install_precommit_hook,
+            hooks::uninstall_precommit_hook,
+            app_lock::get_lock_status,
+            app_lock::enable_app_lock,
+            app_lock::disable_app_lock,
+            app_lock::unlock_app,
+            app_lock::lock_app,
+            access::get_access_settings,
+            access::set_access_settings,
+            access::revalidate_access,
+            external::reveal_in_file_manager,
+            external::open_in_external_editor,
+            edits::rename_prefix,
+            ordering::key_order_drift,
+            ordering::reorder_keys_to_match,
             cancel_scan
-        ])
+        ]))
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// A scanned root holding `app/.env`, next to a folder outside it holding `.env`.
+    fn workspace() -> (TempDir, PathBuf, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("root");
+        let outside = dir.path().join("outside");
+        fs::create_dir_all(root.join("app")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(root.join("app/.env"), "A=1\n").unwrap();
+        fs::write(outside.join(".env"), "B=2\n").unwrap();
+        let root = root.canonicalize().unwrap();
+        let outside = outside.canonicalize().unwrap();
+        (dir, root, outside)
+    }
+
+    fn state_for(root: &Path, allowed: &[PathBuf]) -> AppState {
+        let state = AppState::default();
+        *state.root_path.write() = Some(root.to_path_buf());
+        state.allowed_files.write().extend(allowed.iter().cloned());
+        state
+    }
+
+    #[test]
+    fn allows_dot_dot_that_stays_inside_the_root() {
+        let (_dir, root, _) = workspace();
+        let state = state_for(&root, &[root.join("app/.env")]);
+        let path = root.join("app/../app/.env");
+        assert!(ensure_allowed_path(&state, &path).is_ok());
+    }
+
+    #[test]
+    fn refuses_dot_dot_that_leaves_the_root() {
+        let (_dir, root, outside) = workspace();
+        let state = state_for(&root, &[outside.join(".env")]);
+        let path = root.join("app/../../outside/.env");
+        assert!(matches!(
+            ensure_allowed_path(&state, &path),
+            Err(AppError::PathNotAllowed)
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn refuses_symlinked_parent_outside_the_root() {
+        let (_dir, root, outside) = workspace();
+        std::os::unix::fs::symlink(&outside, root.join("linked")).unwrap();
+        let state = state_for(&root, &[outside.join(".env")]);
+        assert!(matches!(
+            ensure_allowed_path(&state, &root.join("linked/.env")),
+            Err(AppError::PathNotAllowed)
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn refuses_symlinked_file_outside_the_root() {
+        let (_dir, root, outside) = workspace();
+        std::os::unix::fs::symlink(outside.join(".env"), root.join("app/.env.local")).unwrap();
+        let state = state_for(&root, &[outside.join(".env")]);
+        assert!(matches!(
+            ensure_allowed_path(&state, &root.join("app/.env.local")),
+            Err(AppError::PathNotAllowed)
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn allows_symlinked_parent_inside_the_root() {
+        let (_dir, root, _) = workspace();
+        std::os::unix::fs::symlink(root.join("app"), root.join("alias")).unwrap();
+        let state = state_for(&root, &[root.join("app/.env")]);
+        assert!(ensure_allowed_path(&state, &root.join("alias/.env")).is_ok());
+    }
+
+    fn assignment(line: &EnvLine) -> (&str, &str) {
+        match line {
+            EnvLine::Kv { key, value, .. } => (key, value),
+            _ => panic!("not an assignment"),
+        }
+    }
+
+    fn invalid_reason(text: &str) -> ParseReason {
+        match &parse_env_lines(text)[0] {
+            EnvLine::Invalid { reason, .. } => *reason,
+            _ => panic!("{:?} parsed", text),
+        }
+    }
+
+    #[test]
+    fn parses_assignments_comments_and_blanks() {
+        let lines = parse_env_lines("# Database host\nexport HOST = localhost # dev\n\nPORT=5432");
+        assert!(matches!(lines[0], EnvLine::Comment { .. }));
+        let EnvLine::Kv {
+            key,
+            value,
+            has_export,
+            separator,
+            inline_comment,
+            description,
+            ..
+        } = &lines[1]
+        else {
+            panic!("not an assignment");
+        };
+        assert_eq!(key, "HOST");
+        assert_eq!(value, "localhost");
+        assert!(*has_export);
+        assert_eq!(separator, " = ");
+        assert_eq!(inline_comment.as_deref(), Some("dev"));
+        assert_eq!(description.as_deref(), Some("Database host"));
+        assert!(matches!(lines[2], EnvLine::Blank { .. }));
+        assert_eq!(assignment(&lines[3]), ("PORT", "5432"));
+    }
+
+    #[test]
+    fn splits_inline_comments_only_outside_quotes_and_words() {
+        let lines = parse_env_lines("A=\"x # y\" # note\nB=a#b");
+        assert_eq!(assignment(&lines[0]), ("A", "\"x # y\""));
+        assert_eq!(assignment(&lines[1]), ("B", "a#b"));
+        assert!(matches!(
+            &lines[0],
+            EnvLine::Kv { inline_comment: Some(comment), .. } if comment == "note"
+        ));
+        assert!(matches!(
+            &lines[1],
+            EnvLine::Kv {
+                inline_comment: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn parses_unset_and_append_directives() {
+        let lines = parse_env_lines("unset OLD\nPATH+=:/opt/bin # tools");
+        assert!(matches!(&lines[0], EnvLine::Unset { key, .. } if key == "OLD"));
+        assert!(matches!(
+            &lines[1],
+            EnvLine::Append { key, value, inline_comment: Some(comment), .. }
+                if key == "PATH" && value == ":/opt/bin" && comment == "tools"
+        ));
+    }
+
+    #[test]
+    fn explains_lines_that_do_not_parse() {
+        assert!(invalid_reason("1KEY=value") == ParseReason::InvalidKey);
+        assert!(invalid_reason("=value") == ParseReason::InvalidKey);
+        assert!(invalid_reason("just words") == ParseReason::MissingEquals);
+        assert!(invalid_reason("\"still open") == ParseReason::UnterminatedQuote);
+    }
+
+    #[test]
+    fn records_key_and_value_spans_in_bytes_and_chars() {
+        let line = "  export KEY=v\u{e4}lue # c";
+        let lines = parse_env_lines(line);
+        let position = lines[0].position();
+        assert_eq!(position.line_number, 1);
+        let key = position.key_span.expect("key span");
+        assert_eq!(&line[key.start..key.end], "KEY");
+        assert_eq!((key.start_char, key.end_char), (9, 12));
+        let value = position.value_span.expect("value span");
+        assert_eq!(&line[value.start..value.end], "v\u{e4}lue");
+        assert_eq!((value.start, value.end), (13, 19));
+        assert_eq!((value.start_char, value.end_char), (13, 18));
+    }
+
+    #[test]
+    fn numbers_lines_from_one() {
+        let lines = parse_env_lines("A=1\n\nB+=2");
+        let numbers: Vec<usize> = lines
+            .iter()
+            .map(|line| line.position().line_number)
+            .collect();
+        assert_eq!(numbers, [1, 2, 3]);
+    }
+
+    #[test]
+    fn serializes_unedited_text_unchanged() {
+        let texts = [
+            "# c\nexport A = 1 # x\n\nunset B\nC+=2\nnot valid\n",
+            "A=1\r\nB=\"two\"\r\n",
+            "NO_TRAILING_NEWLINE=1",
+            "",
+        ];
+        for text in texts {
+            let (lines, layout) = parse_env_text(text);
+            assert_eq!(serialize_with_layout(&lines, layout), text);
+        }
+    }
+
+    #[test]
+    fn refuses_single_line_edits_of_keys_with_directives() {
+        let mut lines = parse_env_lines("A=1\nA+=2\nB=3\nC=4\nunset C");
+        assert!(ensure_no_directives(&lines, "A").is_err());
+        assert!(ensure_no_directives(&lines, "C").is_err());
+        assert!(ensure_no_directives(&lines, "B").is_ok());
+        assert!(set_kv_value(&mut lines, "B", "30"));
+        assert_eq!(find_kv_value(&lines, "B"), Some("30"));
+        assert_eq!(find_kv_value(&lines, "C"), None);
+    }
+}
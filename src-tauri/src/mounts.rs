@@ -0,0 +1,117 @@
+use std::path::{Path, PathBuf};
+
+/// File system types served over the network, as reported by the mount table.
+const NETWORK_FILE_SYSTEMS: [&str; 14] = [
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smbfs",
+    "smb3",
+    "afpfs",
+    "sshfs",
+    "fuse.sshfs",
+    "webdav",
+    "davfs",
+    "fuse.davfs2",
+    "9p",
+    "ncpfs",
+    "afs",
+];
+
+/// Undoes the octal escapes (`\040` for a space) used in `/proc/mounts`.
+#[cfg(target_os = "linux")]
+fn unescape_mount_point(raw: &str) -> String {
+    let bytes = raw.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escape = bytes.get(index + 1..index + 4).and_then(|digits| {
+            std::str::from_utf8(digits)
+                .ok()
+                .and_then(|digits| u8::from_str_radix(digits, 8).ok())
+        });
+        match (bytes[index], escape) {
+            (b'\\', Some(byte)) => {
+                output.push(byte);
+                index += 4;
+            }
+            (byte, _) => {
+                output.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&output).to_string()
+}
+
+#[cfg(target_os = "linux")]
+fn mount_table() -> Vec<(PathBuf, String)> {
+    let Ok(mounts) = std::fs::read_to_string("/proc/mounts") else {
+        return Vec::new();
+    };
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _device = fields.next()?;
+            let mount_point = unescape_mount_point(fields.next()?);
+            Some((PathBuf::from(mount_point), fields.next()?.to_string()))
+        })
+        .collect()
+}
+
+/// Parses `mount` output lines such as `//me@nas/share on /Volumes/share (smbfs, ...)`.
+#[cfg(target_os = "macos")]
+fn mount_table() -> Vec<(PathBuf, String)> {
+    let Ok(output) = std::process::Command::new("mount").output() else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.split_once(" on ")?;
+            let (mount_point, options) = rest.rsplit_once(" (")?;
+            let file_system = options.split([',', ')']).next()?.trim();
+            Some((PathBuf::from(mount_point), file_system.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn mount_table() -> Vec<(PathBuf, String)> {
+    Vec::new()
+}
+
+/// `\\server\share` and its verbatim form `\\?\UNC\server\share`.
+fn is_unc(path: &Path) -> bool {
+    let display = path.to_string_lossy();
+    display.starts_with(r"\\?\UNC\")
+        || (display.starts_with(r"\\") && !display.starts_with(r"\\?\"))
+}
+
+/// Whether `path` lives on a network share. Uses the mount table on Linux and macOS
+/// and UNC prefixes on Windows; mapped drive letters are not detected.
+pub fn is_network_path(path: &Path) -> bool {
+    if is_unc(path) {
+        return true;
+    }
+    mount_table()
+        .iter()
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.components().count())
+        .map(|(_, file_system)| NETWORK_FILE_SYSTEMS.contains(&file_system.as_str()))
+        .unwrap_or(false)
+}
+
+/// Network mount points strictly inside `root`.
+pub fn network_mounts_under(root: &Path) -> Vec<PathBuf> {
+    mount_table()
+        .into_iter()
+        .filter(|(mount_point, file_system)| {
+            mount_point != root
+                && mount_point.starts_with(root)
+                && NETWORK_FILE_SYSTEMS.contains(&file_system.as_str())
+        })
+        .map(|(mount_point, _)| mount_point)
+        .collect()
+}
@@ -0,0 +1,71 @@
+use crate::ipc_path::decode_path;
+use crate::{ensure_allowed_path, hash_path, run_blocking, store, AppError, AppState};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+const NOTES_FILE: &str = "notes.json";
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyNote {
+    text: String,
+    updated_at: i64,
+}
+
+/// Notes per file id, then per key. Stored in app data, never in the env file itself.
+type Notes = BTreeMap<String, BTreeMap<String, KeyNote>>;
+
+#[tauri::command]
+pub async fn get_notes(
+    app: AppHandle,
+    path: String,
+) -> Result<BTreeMap<String, KeyNote>, AppError> {
+    run_blocking(app, move |app, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+
+        let mut notes: Notes = store::load(&app, NOTES_FILE)?;
+        Ok(notes.remove(&hash_path(&path_buf)).unwrap_or_default())
+    })
+    .await
+}
+
+/// Sets the note for a key; an empty or missing `text` removes it.
+#[tauri::command]
+pub async fn set_note(
+    app: AppHandle,
+    path: String,
+    key: String,
+    text: Option<String>,
+) -> Result<Option<KeyNote>, AppError> {
+    run_blocking(app, move |app, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        let file_id = hash_path(&path_buf);
+
+        store::update(&app, &state, NOTES_FILE, |notes: &mut Notes| {
+            let file_notes = notes.entry(file_id.clone()).or_default();
+            let note = match text.filter(|text| !text.trim().is_empty()) {
+                Some(text) => {
+                    let note = KeyNote {
+                        text,
+                        updated_at: Utc::now().timestamp_millis(),
+                    };
+                    file_notes.insert(key, note.clone());
+                    Some(note)
+                }
+                None => {
+                    file_notes.remove(&key);
+                    None
+                }
+            };
+            if file_notes.is_empty() {
+                notes.remove(&file_id);
+            }
+            note
+        })
+    })
+    .await
+}
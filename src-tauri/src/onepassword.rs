@@ -0,0 +1,104 @@
+use crate::ipc_path::decode_path;
+use crate::quoting::decode_value;
+use crate::{
+    ensure_allowed_path, parse_env_lines, read_env_text, run_blocking, AppError, AppState, EnvLine,
+};
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+use tauri::AppHandle;
+
+const OP_SCHEME: &str = "op://";
+const MASK: &str = "••••••••";
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpReference {
+    key: String,
+    line: usize,
+    reference: String,
+    /// Resolved value; masked unless the caller asked to reveal it.
+    resolved: Option<String>,
+    error: Option<String>,
+}
+
+/// `op://vault/item/field` (optionally `/section/field`) with a non-empty vault,
+/// item and field.
+pub fn is_op_reference(value: &str) -> bool {
+    value
+        .strip_prefix(OP_SCHEME)
+        .map(|rest| {
+            let parts: Vec<&str> = rest.split('/').collect();
+            (3..=4).contains(&parts.len()) && parts.iter().all(|part| !part.is_empty())
+        })
+        .unwrap_or(false)
+}
+
+fn collect_references(lines: &[EnvLine]) -> Vec<OpReference> {
+    lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| match line {
+            EnvLine::Kv { key, value, .. } => {
+                let reference = decode_value(value);
+                is_op_reference(&reference).then(|| OpReference {
+                    key: key.clone(),
+                    line: index + 1,
+                    reference,
+                    resolved: None,
+                    error: None,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn op_read(reference: &str) -> Result<String, String> {
+    let output = Command::new("op")
+        .args(["read", "--no-newline", reference])
+        .output()
+        .map_err(|e| format!("Could not run the 1Password CLI (op): {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn load_references(state: &AppState, path: &str) -> Result<Vec<OpReference>, AppError> {
+    let path_buf = decode_path(path);
+    ensure_allowed_path(state, &path_buf)?;
+    let contents = read_env_text(state, &path_buf)?;
+    Ok(collect_references(&parse_env_lines(&contents)))
+}
+
+/// Lists `op://` references in a file without contacting 1Password.
+#[tauri::command]
+pub async fn list_op_references(
+    app: AppHandle,
+    path: String,
+) -> Result<Vec<OpReference>, AppError> {
+    run_blocking(app, move |_, state| load_references(&state, &path)).await
+}
+
+/// Resolves `op://` references with `op read`. Values stay in memory only and are
+/// masked unless `reveal` is set; nothing is written to disk.
+#[tauri::command]
+pub async fn resolve_op_references(
+    app: AppHandle,
+    path: String,
+    reveal: Option<bool>,
+) -> Result<Vec<OpReference>, AppError> {
+    run_blocking(app, move |_, state| {
+        let reveal = reveal.unwrap_or(false);
+        let mut references = load_references(&state, &path)?;
+        for reference in references.iter_mut() {
+            match op_read(&reference.reference) {
+                Ok(value) if reveal => reference.resolved = Some(value),
+                Ok(_) => reference.resolved = Some(MASK.to_string()),
+                Err(error) => reference.error = Some(error),
+            }
+        }
+        Ok(references)
+    })
+    .await
+}
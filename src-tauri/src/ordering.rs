@@ -0,0 +1,213 @@
+use crate::ipc_path::decode_path;
+use crate::{
+    ensure_allowed_path, parse_env_lines, parse_env_text, read_env_text, run_blocking,
+    serialize_with_layout, unified_diff, write_contents, AppError, AppState, EnvLine, WriteOptions,
+    WritePreview,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderDrift {
+    path: String,
+    reference_path: String,
+    in_order: bool,
+    /// Shared keys that would have to move for the file to follow the reference.
+    out_of_order: Vec<String>,
+    only_in_file: Vec<String>,
+    only_in_reference: Vec<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorderResult {
+    preview: WritePreview,
+    written: bool,
+}
+
+fn first_positions(lines: &[EnvLine]) -> (Vec<String>, HashMap<String, usize>) {
+    let mut keys = Vec::new();
+    let mut positions = HashMap::new();
+    for line in lines {
+        if let EnvLine::Kv { key, .. } = line {
+            if !positions.contains_key(key) {
+                positions.insert(key.clone(), keys.len());
+                keys.push(key.clone());
+            }
+        }
+    }
+    (keys, positions)
+}
+
+/// Indices (into `ranks`) of one longest strictly increasing subsequence.
+fn longest_increasing(ranks: &[usize]) -> Vec<usize> {
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous: Vec<Option<usize>> = vec![None; ranks.len()];
+    for (index, rank) in ranks.iter().enumerate() {
+        let slot = tails.partition_point(|&tail| ranks[tail] < *rank);
+        previous[index] = slot.checked_sub(1).map(|before| tails[before]);
+        if slot == tails.len() {
+            tails.push(index);
+        } else {
+            tails[slot] = index;
+        }
+    }
+    let mut sequence = Vec::new();
+    let mut cursor = tails.last().copied();
+    while let Some(index) = cursor {
+        sequence.push(index);
+        cursor = previous[index];
+    }
+    sequence.reverse();
+    sequence
+}
+
+fn drift(
+    path: String,
+    reference_path: String,
+    lines: &[EnvLine],
+    reference: &[EnvLine],
+) -> OrderDrift {
+    let (keys, _) = first_positions(lines);
+    let (reference_keys, reference_positions) = first_positions(reference);
+    let shared: Vec<&String> = keys
+        .iter()
+        .filter(|key| reference_positions.contains_key(*key))
+        .collect();
+    let ranks: Vec<usize> = shared.iter().map(|key| reference_positions[*key]).collect();
+    let kept = longest_increasing(&ranks);
+    let out_of_order: Vec<String> = shared
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !kept.contains(index))
+        .map(|(_, key)| key.to_string())
+        .collect();
+
+    OrderDrift {
+        path,
+        reference_path,
+        in_order: out_of_order.is_empty(),
+        out_of_order,
+        only_in_file: keys
+            .iter()
+            .filter(|key| !reference_positions.contains_key(*key))
+            .cloned()
+            .collect(),
+        only_in_reference: reference_keys
+            .into_iter()
+            .filter(|key| !keys.contains(key))
+            .collect(),
+    }
+}
+
+enum Item {
+    /// A key line with the comment lines directly above it.
+    Block(String, Vec<EnvLine>),
+    Loose(EnvLine),
+}
+
+fn split_items(lines: Vec<EnvLine>) -> Vec<Item> {
+    let mut items = Vec::new();
+    let mut comments: Vec<EnvLine> = Vec::new();
+    for line in lines {
+        match line {
+            EnvLine::Comment { .. } => comments.push(line),
+            EnvLine::Kv { ref key, .. } => {
+                let key = key.clone();
+                comments.push(line);
+                items.push(Item::Block(key, std::mem::take(&mut comments)));
+            }
+            _ => {
+                items.extend(comments.drain(..).map(Item::Loose));
+                items.push(Item::Loose(line));
+            }
+        }
+    }
+    items.extend(comments.into_iter().map(Item::Loose));
+    items
+}
+
+/// Reorders key blocks to follow `reference`. Blank lines and section comments keep
+/// their place; only blocks move between the slots keys occupied. Keys the reference
+/// lacks travel with the key above them.
+fn reorder(lines: Vec<EnvLine>, reference: &[EnvLine]) -> Vec<EnvLine> {
+    let (_, positions) = first_positions(reference);
+    let items = split_items(lines);
+
+    let mut chains: Vec<(Option<usize>, Vec<Vec<EnvLine>>)> = vec![(None, Vec::new())];
+    for item in &items {
+        if let Item::Block(key, block) = item {
+            match positions.get(key) {
+                Some(&rank) => chains.push((Some(rank), vec![block.clone()])),
+                None => {
+                    if let Some(chain) = chains.last_mut() {
+                        chain.1.push(block.clone());
+                    }
+                }
+            }
+        }
+    }
+    chains.sort_by_key(|(rank, _)| *rank);
+    let mut blocks = chains.into_iter().flat_map(|(_, blocks)| blocks);
+
+    let mut output = Vec::new();
+    for item in items {
+        match item {
+            Item::Block(..) => output.extend(blocks.next().unwrap_or_default()),
+            Item::Loose(line) => output.push(line),
+        }
+    }
+    output
+}
+
+fn load(state: &AppState, path: &str) -> Result<String, AppError> {
+    let path_buf = decode_path(path);
+    ensure_allowed_path(state, &path_buf)?;
+    read_env_text(state, &path_buf)
+}
+
+/// Compares key order between a file and a reference such as `.env.example`.
+#[tauri::command]
+pub async fn key_order_drift(
+    app: AppHandle,
+    path: String,
+    reference_path: String,
+) -> Result<OrderDrift, AppError> {
+    run_blocking(app, move |_, state| {
+        let lines = parse_env_lines(&load(&state, &path)?);
+        let reference = parse_env_lines(&load(&state, &reference_path)?);
+        Ok(drift(path, reference_path, &lines, &reference))
+    })
+    .await
+}
+
+/// Reorders a file's keys to match the reference, keeping each key's comments with
+/// it. Previews only unless `options` is provided.
+#[tauri::command]
+pub async fn reorder_keys_to_match(
+    app: AppHandle,
+    path: String,
+    reference_path: String,
+    options: Option<WriteOptions>,
+) -> Result<ReorderResult, AppError> {
+    run_blocking(app, move |_, state| {
+        let contents = load(&state, &path)?;
+        let reference = parse_env_lines(&load(&state, &reference_path)?);
+        let (lines, layout) = parse_env_text(&contents);
+        let reordered = serialize_with_layout(&reorder(lines, &reference), layout);
+        let preview = unified_diff(&path, &contents, &reordered);
+
+        let written = match options {
+            Some(options) if preview.changed => {
+                write_contents(&state, &decode_path(&path), &reordered, &options)?;
+                true
+            }
+            _ => false,
+        };
+
+        Ok(ReorderResult { preview, written })
+    })
+    .await
+}
@@ -0,0 +1,168 @@
+use crate::ipc_path::decode_path;
+use crate::{
+    app_lock, parse_env_lines, read_env_text, run_blocking, AppError, AppState, EnvLine, ScanResult,
+};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use tauri::AppHandle;
+
+const DEFAULT_LIMIT: usize = 50;
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PaletteKind {
+    File,
+    Project,
+    Key,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PaletteScope {
+    #[default]
+    All,
+    Files,
+    Projects,
+    Keys,
+}
+
+impl PaletteScope {
+    fn includes(self, kind: PaletteKind) -> bool {
+        matches!(
+            (self, kind),
+            (PaletteScope::All, _)
+                | (PaletteScope::Files, PaletteKind::File)
+                | (PaletteScope::Projects, PaletteKind::Project)
+                | (PaletteScope::Keys, PaletteKind::Key)
+        )
+    }
+}
+
+struct PaletteEntry {
+    kind: PaletteKind,
+    label: String,
+    /// Shown under the label: the folder for files and projects, the file for keys.
+    detail: String,
+    path: String,
+    group_id: String,
+}
+
+/// Names from the last scan, rebuilt after every scan so queries never touch disk.
+#[derive(Default)]
+pub struct PaletteIndex {
+    entries: Vec<PaletteEntry>,
+}
+
+impl PaletteIndex {
+    pub fn entry_count(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteMatch {
+    kind: PaletteKind,
+    label: String,
+    detail: String,
+    path: String,
+    group_id: String,
+    score: i64,
+    /// Character positions in `label` that matched the query.
+    indices: Vec<usize>,
+}
+
+fn file_keys(state: &AppState, path: &str) -> BTreeSet<String> {
+    let Ok(text) = read_env_text(state, &decode_path(path)) else {
+        return BTreeSet::new();
+    };
+    parse_env_lines(&text)
+        .into_iter()
+        .filter_map(|line| match line {
+            EnvLine::Kv { key, .. } => Some(key),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Replaces the index with the groups, files and keys of `result`.
+pub fn rebuild(state: &AppState, result: &ScanResult) {
+    let mut entries = Vec::new();
+    for group in &result.groups {
+        entries.push(PaletteEntry {
+            kind: PaletteKind::Project,
+            label: group.name.clone(),
+            detail: group.root_path.clone(),
+            path: group.root_path.clone(),
+            group_id: group.id.clone(),
+        });
+        for file in &group.env_files {
+            entries.push(PaletteEntry {
+                kind: PaletteKind::File,
+                label: file.file_name.clone(),
+                detail: file.folder_path.clone(),
+                path: file.absolute_path.clone(),
+                group_id: group.id.clone(),
+            });
+            for key in file_keys(state, &file.absolute_path) {
+                entries.push(PaletteEntry {
+                    kind: PaletteKind::Key,
+                    label: key,
+                    detail: format!("{} · {}", group.name, file.file_name),
+                    path: file.absolute_path.clone(),
+                    group_id: group.id.clone(),
+                });
+            }
+        }
+    }
+    *state.palette_index.write() = PaletteIndex { entries };
+}
+
+/// Ranks indexed names against `query` for the command palette, best match first.
+#[tauri::command]
+pub async fn fuzzy_find(
+    app: AppHandle,
+    query: String,
+    scope: Option<PaletteScope>,
+    limit: Option<usize>,
+) -> Result<Vec<PaletteMatch>, AppError> {
+    run_blocking(app, move |_, state| {
+        app_lock::ensure_unlocked(&state)?;
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let scope = scope.unwrap_or_default();
+        let matcher = SkimMatcherV2::default().ignore_case();
+        let index = state.palette_index.read();
+
+        let mut matches: Vec<PaletteMatch> = index
+            .entries
+            .iter()
+            .filter(|entry| scope.includes(entry.kind))
+            .filter_map(|entry| {
+                let (score, indices) = matcher.fuzzy_indices(&entry.label, query)?;
+                Some(PaletteMatch {
+                    kind: entry.kind,
+                    label: entry.label.clone(),
+                    detail: entry.detail.clone(),
+                    path: entry.path.clone(),
+                    group_id: entry.group_id.clone(),
+                    score,
+                    indices,
+                })
+            })
+            .collect();
+        matches.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| a.label.len().cmp(&b.label.len()))
+                .then_with(|| a.label.cmp(&b.label))
+        });
+        matches.truncate(limit.unwrap_or(DEFAULT_LIMIT));
+        Ok(matches)
+    })
+    .await
+}
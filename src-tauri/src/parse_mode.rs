@@ -0,0 +1,258 @@
+use crate::{has_open_quote, EnvLine, LinePosition, ParseReason, TextSpan};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParseMode {
+    /// Accepts what common dotenv libraries accept, for editing.
+    #[default]
+    Lenient,
+    /// Also reports everything outside plain `KEY=value` lines, for files that other
+    /// parsers will read.
+    Strict,
+}
+
+/// Something a strict parser would reject or read differently.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Deviation {
+    line_number: usize,
+    rule: String,
+    message: String,
+    /// The part of the line at fault; `None` for the whole line.
+    span: Option<TextSpan>,
+}
+
+struct Deviations<'a> {
+    position: &'a LinePosition,
+    found: &'a mut Vec<Deviation>,
+}
+
+impl Deviations<'_> {
+    fn push(&mut self, rule: &str, message: impl Into<String>, span: Option<TextSpan>) {
+        self.found.push(Deviation {
+            line_number: self.position.line_number,
+            rule: rule.to_string(),
+            message: message.into(),
+            span,
+        });
+    }
+}
+
+fn check_whitespace(raw: &str, deviations: &mut Deviations) {
+    let leading = raw.len() - raw.trim_start().len();
+    if leading > 0 && leading < raw.len() {
+        deviations.push(
+            "leading-whitespace",
+            "Line starts with whitespace",
+            Some(TextSpan::of(raw, 0, leading)),
+        );
+    }
+    let content = raw.trim_end();
+    if content.len() < raw.len() {
+        deviations.push(
+            "trailing-whitespace",
+            "Line ends with whitespace",
+            Some(TextSpan::of(raw, content.len(), raw.len())),
+        );
+    }
+}
+
+fn check_value(value: &str, deviations: &mut Deviations) {
+    let span = deviations.position.value_span;
+    match value.chars().next() {
+        Some('`') => deviations.push(
+            "backtick-quote",
+            "Backtick quotes are only understood by some parsers",
+            span,
+        ),
+        Some(quote @ ('"' | '\'')) => {
+            if has_open_quote(value) {
+                deviations.push("unterminated-quote", "Quoted value is not closed", span);
+            } else if !value.ends_with(quote) {
+                deviations.push(
+                    "trailing-text",
+                    "Text follows the closing quote of the value",
+                    span,
+                );
+            }
+        }
+        _ if value.contains(char::is_whitespace) => deviations.push(
+            "unquoted-whitespace",
+            "Unquoted value contains whitespace; quote it",
+            span,
+        ),
+        _ => {}
+    }
+}
+
+fn check_line(line: &EnvLine, deviations: &mut Deviations) {
+    match line {
+        EnvLine::Blank { .. } => {}
+        EnvLine::Comment { raw, .. } => {
+            check_whitespace(raw, deviations);
+            if raw.trim_start().starts_with(';') {
+                deviations.push("semicolon-comment", "Comments start with #, not ;", None);
+            }
+        }
+        EnvLine::Kv {
+            value,
+            has_export,
+            separator,
+            inline_comment,
+            dangerous,
+            raw,
+            ..
+        } => {
+            if let Some(raw) = raw {
+                check_whitespace(raw, deviations);
+            }
+            let key_span = deviations.position.key_span;
+            if *has_export {
+                deviations.push(
+                    "export-prefix",
+                    "export is a shell keyword that some parsers reject",
+                    key_span,
+                );
+            }
+            if separator != "=" {
+                deviations.push(
+                    "spaced-separator",
+                    "Whitespace around = is read as part of the key or value by some parsers",
+                    key_span,
+                );
+            }
+            check_value(value, deviations);
+            if inline_comment.is_some() {
+                deviations.push(
+                    "inline-comment",
+                    "Some parsers read an inline comment as part of the value",
+                    None,
+                );
+            }
+            if *dangerous {
+                deviations.push(
+                    "command-substitution",
+                    "Command substitution runs only when the file is sourced by a shell",
+                    deviations.position.value_span,
+                );
+            }
+        }
+        EnvLine::Unset { raw, .. } => {
+            if let Some(raw) = raw {
+                check_whitespace(raw, deviations);
+            }
+            deviations.push(
+                "unset-directive",
+                "unset is a shell command that dotenv parsers reject",
+                None,
+            );
+        }
+        EnvLine::Append { raw, .. } => {
+            if let Some(raw) = raw {
+                check_whitespace(raw, deviations);
+            }
+            deviations.push(
+                "append-directive",
+                "+= is shell syntax that dotenv parsers reject",
+                None,
+            );
+        }
+        EnvLine::Invalid {
+            reason, message, ..
+        } => {
+            let rule = match reason {
+                ParseReason::InvalidKey => "invalid-key",
+                ParseReason::MissingEquals => "missing-equals",
+                ParseReason::UnterminatedQuote => "unterminated-quote",
+            };
+            deviations.push(rule, message.clone(), None);
+        }
+    }
+}
+
+/// Everything in `lines` that a strict dotenv parser would not read as written,
+/// in line order. Lenient mode reports nothing.
+pub fn deviations(lines: &[EnvLine], mode: ParseMode) -> Vec<Deviation> {
+    if mode == ParseMode::Lenient {
+        return Vec::new();
+    }
+    let mut found = Vec::new();
+    for line in lines {
+        let mut deviations = Deviations {
+            position: line.position(),
+            found: &mut found,
+        };
+        check_line(line, &mut deviations);
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_env_lines;
+
+    fn rules(text: &str) -> Vec<String> {
+        deviations(&parse_env_lines(text), ParseMode::Strict)
+            .into_iter()
+            .map(|deviation| deviation.rule)
+            .collect()
+    }
+
+    #[test]
+    fn plain_assignments_have_no_deviations() {
+        assert!(rules("# comment\n\nKEY=value\nQUOTED=\"a b\"").is_empty());
+    }
+
+    #[test]
+    fn lenient_mode_reports_nothing() {
+        let lines = parse_env_lines("export KEY = a b");
+        assert!(deviations(&lines, ParseMode::Lenient).is_empty());
+    }
+
+    #[test]
+    fn reports_each_deviation_on_an_assignment() {
+        assert_eq!(
+            rules("export KEY = a b # note"),
+            [
+                "export-prefix",
+                "spaced-separator",
+                "unquoted-whitespace",
+                "inline-comment"
+            ]
+        );
+        assert_eq!(
+            rules("KEY=`tick`"),
+            ["backtick-quote", "command-substitution"]
+        );
+        assert_eq!(rules("KEY=\"open"), ["unterminated-quote"]);
+        assert_eq!(rules("KEY=\"a\"b"), ["trailing-text"]);
+    }
+
+    #[test]
+    fn reports_whitespace_comments_and_directives() {
+        assert_eq!(rules("  KEY=value"), ["leading-whitespace"]);
+        assert_eq!(rules("# note  "), ["trailing-whitespace"]);
+        assert_eq!(rules("; comment"), ["semicolon-comment"]);
+        assert_eq!(rules("unset KEY"), ["unset-directive"]);
+        assert_eq!(rules("KEY+=more"), ["append-directive"]);
+    }
+
+    #[test]
+    fn reports_lines_that_do_not_parse() {
+        assert_eq!(
+            rules("1KEY=value\nno equals"),
+            ["invalid-key", "missing-equals"]
+        );
+    }
+
+    #[test]
+    fn points_at_the_part_of_the_line_at_fault() {
+        let found = deviations(&parse_env_lines("A=1\n  KEY=value"), ParseMode::Strict);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].line_number, 2);
+        let span = found[0].span.expect("leading whitespace has a span");
+        assert_eq!((span.start, span.end), (0, 2));
+    }
+}
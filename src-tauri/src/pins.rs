@@ -0,0 +1,63 @@
+use crate::ipc_path::decode_path;
+use crate::{ensure_allowed_path, hash_path, run_blocking, store, AppError, AppState};
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+const PINS_FILE: &str = "pins.json";
+
+/// Pinned keys per file, keyed by the file id (`EnvFileRef.id`).
+type Pins = BTreeMap<String, Vec<String>>;
+
+#[tauri::command]
+pub async fn pin_key(app: AppHandle, path: String, key: String) -> Result<Vec<String>, AppError> {
+    run_blocking(app, move |app, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        let file_id = hash_path(&path_buf);
+
+        store::update(&app, &state, PINS_FILE, |pins: &mut Pins| {
+            let keys = pins.entry(file_id).or_default();
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+            keys.clone()
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn unpin_key(app: AppHandle, path: String, key: String) -> Result<Vec<String>, AppError> {
+    run_blocking(app, move |app, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        let file_id = hash_path(&path_buf);
+
+        store::update(&app, &state, PINS_FILE, |pins: &mut Pins| {
+            let keys = pins.entry(file_id.clone()).or_default();
+            keys.retain(|pinned| *pinned != key);
+            let remaining = keys.clone();
+            if remaining.is_empty() {
+                pins.remove(&file_id);
+            }
+            remaining
+        })
+    })
+    .await
+}
+
+/// Lists pinned keys for one file, or for every file when `path` is omitted.
+#[tauri::command]
+pub async fn list_pins(app: AppHandle, path: Option<String>) -> Result<Pins, AppError> {
+    run_blocking(app, move |app, state| {
+        let pins: Pins = store::load(&app, PINS_FILE)?;
+        let Some(path) = path else {
+            return Ok(pins);
+        };
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        let file_id = hash_path(&path_buf);
+        Ok(pins.into_iter().filter(|(id, _)| *id == file_id).collect())
+    })
+    .await
+}
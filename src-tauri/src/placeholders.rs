@@ -0,0 +1,95 @@
+use crate::ipc_path::encode_path;
+use crate::{
+    app_lock, is_example_file, parse_env_lines, read_env_text, run_blocking, unquote, AppError,
+    AppState, EnvLine,
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tauri::AppHandle;
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnfilledKind {
+    Empty,
+    Placeholder,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnfilledValue {
+    path: String,
+    line: usize,
+    key: String,
+    kind: UnfilledKind,
+    value: String,
+}
+
+fn placeholder_regex() -> Option<&'static Regex> {
+    static PLACEHOLDER_REGEX: OnceLock<Option<Regex>> = OnceLock::new();
+    PLACEHOLDER_REGEX
+        .get_or_init(|| {
+            Regex::new(
+                r"(?i)^(?:change[-_ ]?me|todo|tbd|fixme|replace[-_ ]?me|placeholder|x{3,}|\*{3,}|\.{3}|<[^>]*>|\{\{[^}]*\}\}|your[-_ ].*|(?:insert|enter|add)[-_ ]your[-_ ].*)$",
+            )
+            .ok()
+        })
+        .as_ref()
+}
+
+pub fn classify(value: &str) -> Option<UnfilledKind> {
+    let value = unquote(value).trim();
+    if value.is_empty() {
+        return Some(UnfilledKind::Empty);
+    }
+    placeholder_regex()
+        .filter(|regex| regex.is_match(value))
+        .map(|_| UnfilledKind::Placeholder)
+}
+
+/// Lists keys across the workspace whose values are empty or obvious placeholders
+/// (`changeme`, `TODO`, `xxx`, `<your-key-here>`, ...). Example files are skipped
+/// unless `include_examples` is set, since placeholders are expected there.
+#[tauri::command]
+pub async fn find_unfilled_values(
+    app: AppHandle,
+    include_examples: Option<bool>,
+) -> Result<Vec<UnfilledValue>, AppError> {
+    run_blocking(app, move |_, state| {
+        app_lock::ensure_unlocked(&state)?;
+        let include_examples = include_examples.unwrap_or(false);
+        let mut paths: Vec<PathBuf> = state.allowed_files.read().iter().cloned().collect();
+        paths.sort();
+
+        let mut results = Vec::new();
+        for path in paths {
+            let is_example = path
+                .file_name()
+                .map(|name| is_example_file(&name.to_string_lossy()))
+                .unwrap_or(false);
+            if is_example && !include_examples {
+                continue;
+            }
+            let Ok(contents) = read_env_text(&state, &path) else {
+                continue;
+            };
+            for (index, line) in parse_env_lines(&contents).into_iter().enumerate() {
+                let EnvLine::Kv { key, value, .. } = line else {
+                    continue;
+                };
+                if let Some(kind) = classify(&value) {
+                    results.push(UnfilledValue {
+                        path: encode_path(path),
+                        line: index + 1,
+                        key,
+                        kind,
+                        value,
+                    });
+                }
+            }
+        }
+        Ok(results)
+    })
+    .await
+}
@@ -0,0 +1,106 @@
+use crate::ipc_path::{decode_path, encode_path};
+use crate::{
+    app_lock, ensure_allowed_path, run_blocking, sibling_path, write_target, AppError, AppState,
+};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tauri::AppHandle;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteCheck {
+    path: String,
+    can_write: bool,
+    /// Why saving would fail, worded for the UI; `None` when `can_write` is true.
+    reason: Option<String>,
+    exists: bool,
+    read_only: bool,
+    /// Space needed for a backup copy plus the temp file that replaces the target.
+    required_bytes: u64,
+    available_bytes: Option<u64>,
+}
+
+/// Creates and removes a probe file next to `target`; a save needs to create its temp
+/// file in the same folder.
+fn folder_accepts_files(target: &Path) -> bool {
+    let timestamp = Local::now().format("%Y%m%d%H%M%S");
+    let Ok(probe) = sibling_path(target, &format!("probe-{}", timestamp)) else {
+        return false;
+    };
+    let created = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .is_ok();
+    if created {
+        let _ = fs::remove_file(&probe);
+    }
+    created
+}
+
+fn check(path: &Path, expected_size: Option<u64>) -> Result<WriteCheck, AppError> {
+    let target = write_target(path)?;
+    let metadata = fs::metadata(&target)?;
+    let read_only = metadata.permissions().readonly();
+    let required_bytes = metadata.len() + expected_size.unwrap_or(metadata.len());
+    let available_bytes = target
+        .parent()
+        .and_then(|folder| fs2::available_space(folder).ok());
+
+    let reason = if read_only {
+        Some("The file is read-only".to_string())
+    } else if fs::OpenOptions::new().write(true).open(&target).is_err() {
+        Some("You do not have permission to write this file".to_string())
+    } else if !folder_accepts_files(&target) {
+        Some("The folder does not allow creating the temporary file".to_string())
+    } else if available_bytes.is_some_and(|available| available < required_bytes) {
+        Some(format!(
+            "Not enough disk space: {} bytes needed, {} available",
+            required_bytes,
+            available_bytes.unwrap_or(0)
+        ))
+    } else {
+        None
+    };
+
+    Ok(WriteCheck {
+        path: encode_path(path),
+        can_write: reason.is_none(),
+        reason,
+        exists: true,
+        read_only,
+        required_bytes,
+        available_bytes,
+    })
+}
+
+/// Reports whether a save to `path` would succeed, so the UI can disable Save with a
+/// reason instead of failing halfway. `expected_size` is the size of the new content
+/// and defaults to the current size.
+#[tauri::command]
+pub async fn can_write(
+    app: AppHandle,
+    path: String,
+    expected_size: Option<u64>,
+) -> Result<WriteCheck, AppError> {
+    run_blocking(app, move |_, state| {
+        app_lock::ensure_unlocked(&state)?;
+        let path_buf = decode_path(&path);
+        if !path_buf.exists() {
+            return Ok(WriteCheck {
+                path,
+                can_write: false,
+                reason: Some("The file no longer exists".to_string()),
+                exists: false,
+                read_only: false,
+                required_bytes: expected_size.unwrap_or(0),
+                available_bytes: None,
+            });
+        }
+        ensure_allowed_path(&state, &path_buf)?;
+        check(&path_buf, expected_size)
+    })
+    .await
+}
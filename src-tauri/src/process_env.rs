@@ -0,0 +1,276 @@
+use crate::interop::{import_values, read_values, ImportResult};
+use crate::io_error::IoCode;
+use crate::ipc_path::decode_path;
+use crate::{ensure_allowed_path, run_blocking, AppError, AppState, WriteOptions};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::process::{Command, Stdio};
+use tauri::AppHandle;
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessEnvStatus {
+    Same,
+    Different,
+    /// Set in the file but not in the environment.
+    Missing,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessEnvEntry {
+    key: String,
+    status: ProcessEnvStatus,
+    file_value: String,
+    process_value: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessEnvDiff {
+    /// `app` for the app's own environment, otherwise the shell that was asked.
+    source: String,
+    entries: Vec<ProcessEnvEntry>,
+}
+
+#[cfg(not(windows))]
+fn shell_env(shell: &str) -> Result<BTreeMap<String, String>, AppError> {
+    // A login shell picks up the profile files a terminal would; `env -0` keeps
+    // multi-line values intact.
+    let output = Command::new(shell)
+        .args(["-l", "-c", "env -0"])
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| AppError::io(e.kind().into(), format!("Could not start {}: {}", shell, e)))?;
+    if !output.status.success() {
+        return Err(AppError::io(
+            IoCode::Other,
+            format!(
+                "{} failed: {}",
+                shell,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter_map(|entry| entry.split_once('='))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect())
+}
+
+#[cfg(windows)]
+fn shell_env(shell: &str) -> Result<BTreeMap<String, String>, AppError> {
+    let output = Command::new(shell)
+        .args(["/C", "set"])
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| AppError::io(e.kind().into(), format!("Could not start {}: {}", shell, e)))?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect())
+}
+
+#[cfg(not(windows))]
+const KNOWN_SHELLS: &[&str] = &["sh", "bash", "zsh", "fish", "dash", "ksh"];
+#[cfg(not(windows))]
+const SHELL_VARIABLE: &str = "SHELL";
+
+#[cfg(windows)]
+const KNOWN_SHELLS: &[&str] = &["cmd", "cmd.exe"];
+#[cfg(windows)]
+const SHELL_VARIABLE: &str = "COMSPEC";
+
+/// Whether `shell` may be started: a known shell looked up on `PATH`, the user's own
+/// shell, or on unix one listed in `/etc/shells`. The name comes from the webview, so
+/// anything else is refused rather than run.
+fn is_allowed_shell(shell: &str) -> bool {
+    if KNOWN_SHELLS.contains(&shell) {
+        return true;
+    }
+    if std::env::var(SHELL_VARIABLE).ok().as_deref() == Some(shell) {
+        return true;
+    }
+    cfg!(not(windows))
+        && std::fs::read_to_string("/etc/shells")
+            .map(|shells| shells.lines().any(|line| line.trim() == shell))
+            .unwrap_or(false)
+}
+
+/// The app's own environment, or the one a fresh login `shell` ends up with.
+pub fn capture(shell: Option<&str>) -> Result<BTreeMap<String, String>, AppError> {
+    match shell.map(str::trim).filter(|shell| !shell.is_empty()) {
+        Some(shell) if !is_allowed_shell(shell) => Err(AppError::InvalidInput(format!(
+            "{} is not a known shell",
+            shell
+        ))),
+        Some(shell) => shell_env(shell),
+        None => Ok(std::env::vars_os()
+            .map(|(key, value)| {
+                (
+                    key.to_string_lossy().to_string(),
+                    value.to_string_lossy().to_string(),
+                )
+            })
+            .collect()),
+    }
+}
+
+/// Compares a file's values with what is actually set in the app's environment or a
+/// chosen shell, to explain why something works in a terminal but not elsewhere.
+#[tauri::command]
+pub async fn diff_with_process_env(
+    app: AppHandle,
+    path: String,
+    shell: Option<String>,
+) -> Result<ProcessEnvDiff, AppError> {
+    run_blocking(app, move |app, state| {
+        // Reading the file authorizes it before a shell is started.
+        let values = read_values(&state, &decode_path(&path), None)?;
+        let environment = capture(shell.as_deref())?;
+
+        let mut entries: BTreeMap<String, ProcessEnvEntry> = BTreeMap::new();
+        for (key, file_value) in values {
+            let process_value = environment.get(&key).cloned();
+            let status = match &process_value {
+                None => ProcessEnvStatus::Missing,
+                Some(current) if *current == file_value => ProcessEnvStatus::Same,
+                Some(_) => ProcessEnvStatus::Different,
+            };
+            // Later assignments win, as they do when the file is loaded.
+            entries.insert(
+                key.clone(),
+                ProcessEnvEntry {
+                    key,
+                    status,
+                    file_value,
+                    process_value,
+                },
+            );
+        }
+
+        Ok(ProcessEnvDiff {
+            source: shell
+                .filter(|shell| !shell.trim().is_empty())
+                .unwrap_or_else(|| "app".to_string()),
+            entries: entries.into_values().collect(),
+        })
+    })
+    .await
+}
+
+fn is_variable_name(key: &str) -> bool {
+    !key.is_empty()
+        && !key.starts_with(|c: char| c.is_ascii_digit())
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Reads `printenv` or `env` output. A line that does not start with `NAME=` belongs
+/// to the previous value, which is how multi-line values are printed.
+fn parse_dump(dump: &str) -> BTreeMap<String, String> {
+    let mut values = BTreeMap::new();
+    let mut current: Option<(String, String)> = None;
+    for line in dump.lines() {
+        match line
+            .split_once('=')
+            .filter(|(key, _)| is_variable_name(key))
+        {
+            Some((key, value)) => {
+                if let Some((key, value)) = current.take() {
+                    values.insert(key, value);
+                }
+                current = Some((key.to_string(), value.to_string()));
+            }
+            None => {
+                if let Some((_, value)) = current.as_mut() {
+                    value.push('\n');
+                    value.push_str(line);
+                }
+            }
+        }
+    }
+    if let Some((key, value)) = current {
+        values.insert(key, value);
+    }
+    values
+}
+
+/// Copies the variables whose names start with one of `filter_prefixes` from a pasted
+/// `printenv` dump, or else from the app's or a login shell's environment, into an env
+/// file.
+#[tauri::command]
+pub async fn import_process_env(
+    app: AppHandle,
+    target_path: String,
+    filter_prefixes: Vec<String>,
+    dump: Option<String>,
+    shell: Option<String>,
+    overwrite: bool,
+    options: Option<WriteOptions>,
+) -> Result<ImportResult, AppError> {
+    run_blocking(app, move |_, state| {
+        let prefixes: Vec<&str> = filter_prefixes
+            .iter()
+            .map(|prefix| prefix.trim())
+            .filter(|prefix| !prefix.is_empty())
+            .collect();
+        if prefixes.is_empty() {
+            return Err(AppError::InvalidInput(
+                "At least one prefix is required".to_string(),
+            ));
+        }
+        // Authorize the target before a shell is started on its behalf.
+        ensure_allowed_path(&state, &decode_path(&target_path))?;
+        let environment = match dump {
+            Some(dump) => parse_dump(&dump),
+            None => capture(shell.as_deref())?,
+        };
+        let entries: Vec<(String, String)> = environment
+            .into_iter()
+            .filter(|(key, _)| {
+                is_variable_name(key) && prefixes.iter().any(|prefix| key.starts_with(prefix))
+            })
+            .collect();
+        import_values(&state, &target_path, entries, overwrite, options)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_printenv_dump() {
+        let values = parse_dump("HOME=/home/me\nAPP_NAME=shelf\nEMPTY=\n");
+        assert_eq!(values["HOME"], "/home/me");
+        assert_eq!(values["APP_NAME"], "shelf");
+        assert_eq!(values["EMPTY"], "");
+    }
+
+    #[test]
+    fn continuation_lines_belong_to_the_previous_value() {
+        let values = parse_dump("CERT=line one\nline two\n1BAD=x\nNEXT=1");
+        assert_eq!(values["CERT"], "line one\nline two\n1BAD=x");
+        assert_eq!(values["NEXT"], "1");
+    }
+
+    #[test]
+    fn text_before_the_first_variable_is_ignored() {
+        let values = parse_dump("not a variable\nKEY=1");
+        assert_eq!(values.len(), 1);
+        assert_eq!(values["KEY"], "1");
+    }
+
+    #[test]
+    fn refuses_programs_that_are_not_shells() {
+        assert!(!is_allowed_shell("/tmp/payload"));
+        assert!(!is_allowed_shell("python3"));
+        assert!(matches!(
+            capture(Some("/tmp/payload")),
+            Err(AppError::InvalidInput(_))
+        ));
+    }
+}
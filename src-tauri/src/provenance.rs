@@ -0,0 +1,89 @@
+use crate::io_error::IoCode;
+use crate::{hash_content, hash_path, normalize_path, store, AppError};
+use chrono::Utc;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tauri::AppHandle;
+
+const PROVENANCE_FILE: &str = "write-provenance.json";
+
+/// Set once on startup; writes are not tracked when the app data directory is
+/// unavailable.
+static TRACKER: OnceLock<Mutex<Tracker>> = OnceLock::new();
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Provenance {
+    /// The file is exactly what the app last wrote to it.
+    App,
+    /// The app wrote the file before, but something else changed it since.
+    External,
+    /// The app has never written the file.
+    Unknown,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AppWrite {
+    content_hash: String,
+    written_at: i64,
+}
+
+fn file_id(path: &Path) -> String {
+    let canonical = normalize_path(path).unwrap_or_else(|_| path.to_path_buf());
+    hash_path(&canonical)
+}
+
+struct Tracker {
+    file: PathBuf,
+    /// Last app write per file id.
+    writes: BTreeMap<String, AppWrite>,
+}
+
+/// Loads the recorded writes; called on app setup.
+pub fn init(app: &AppHandle) -> Result<(), AppError> {
+    let writes = store::load(app, PROVENANCE_FILE)?;
+    let file = store::app_data_dir(app)?.join(PROVENANCE_FILE);
+    let _ = TRACKER.set(Mutex::new(Tracker { file, writes }));
+    Ok(())
+}
+
+/// Remembers the hash of content the app just put in `path`.
+pub fn record(path: &Path, content: &[u8]) -> Result<(), AppError> {
+    let Some(tracker) = TRACKER.get() else {
+        return Ok(());
+    };
+    let mut tracker = tracker.lock();
+    tracker.writes.insert(
+        file_id(path),
+        AppWrite {
+            content_hash: hash_content(content),
+            written_at: Utc::now().timestamp_millis(),
+        },
+    );
+    let json = serde_json::to_string_pretty(&tracker.writes)
+        .map_err(|e| AppError::io(IoCode::InvalidData, e.to_string()))?;
+    let temp_path = tracker.file.with_extension("tmp");
+    fs::write(&temp_path, json)?;
+    fs::rename(&temp_path, &tracker.file)?;
+    Ok(())
+}
+
+/// Who made the change that produced `content_hash`, and when the app last wrote the
+/// file.
+pub fn of(path: &Path, content_hash: &str) -> (Provenance, Option<i64>) {
+    let Some(tracker) = TRACKER.get().map(Mutex::lock) else {
+        return (Provenance::Unknown, None);
+    };
+    match tracker.writes.get(&file_id(path)) {
+        Some(write) if write.content_hash == content_hash => {
+            (Provenance::App, Some(write.written_at))
+        }
+        Some(write) => (Provenance::External, Some(write.written_at)),
+        None => (Provenance::Unknown, None),
+    }
+}
@@ -0,0 +1,375 @@
+use crate::{AppError, EnvLine};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QuotePolicy {
+    /// Bare values. Shell values are backslash-escaped; other dialects reject values
+    /// that cannot be written bare.
+    Never,
+    Always,
+    #[default]
+    WhenNeeded,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Dialect {
+    /// dotenv libraries: double quotes support `\n`, `\"`, `\\` and `\$`.
+    #[default]
+    Dotenv,
+    /// POSIX shell `source`-able files.
+    Shell,
+    /// `docker --env-file`: everything after `=` is taken literally, so no quoting.
+    Docker,
+    /// Laravel's `vlucas/phpdotenv`: like dotenv, but bare values may not contain
+    /// whitespace and only `${VAR}` is expanded.
+    Phpdotenv,
+    /// python-dotenv: only `${VAR}` / `${VAR:-default}` is expanded and quoted values
+    /// may span several lines.
+    Python,
+}
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct QuoteStyle {
+    pub policy: QuotePolicy,
+    pub dialect: Dialect,
+}
+
+/// A value as the program sees it, plus whether `$` references in it are expanded.
+struct Decoded {
+    text: String,
+    expands: bool,
+}
+
+fn decode(value: &str) -> Decoded {
+    let value = value.trim();
+    let quoted =
+        |quote: char| value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote);
+    if quoted('\'') || quoted('`') {
+        return Decoded {
+            text: value[1..value.len() - 1].to_string(),
+            expands: false,
+        };
+    }
+    if !quoted('"') {
+        return Decoded {
+            text: value.to_string(),
+            expands: true,
+        };
+    }
+
+    let mut text = String::new();
+    let mut chars = value[1..value.len() - 1].chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            text.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => text.push('\n'),
+            Some('r') => text.push('\r'),
+            Some('t') => text.push('\t'),
+            // Keep `\$` escaped so the literal dollar survives re-encoding.
+            Some('$') => text.push_str("\\$"),
+            Some(other) => text.push(other),
+            None => text.push('\\'),
+        }
+    }
+    Decoded {
+        text,
+        expands: true,
+    }
+}
+
+fn is_shell_special(ch: char) -> bool {
+    ch.is_whitespace() || "|&;<>()$`\\\"'*?[]#~=%{}!".contains(ch)
+}
+
+fn needs_quotes(decoded: &Decoded, dialect: Dialect) -> bool {
+    let text = &decoded.text;
+    if text.is_empty() {
+        return false;
+    }
+    let literal_dollar = !decoded.expands && text.contains('$');
+    match dialect {
+        Dialect::Dotenv | Dialect::Phpdotenv | Dialect::Python => {
+            (dialect == Dialect::Phpdotenv && text.contains(char::is_whitespace))
+                || literal_dollar
+                || text.trim() != text
+                || text.contains(['#', '"', '\'', '`', '\\', '\n', '\r'])
+        }
+        Dialect::Shell => text
+            .chars()
+            .any(|ch| is_shell_special(ch) && !(ch == '$' && decoded.expands)),
+        Dialect::Docker => false,
+    }
+}
+
+fn double_quote(decoded: &Decoded, dialect: Dialect) -> String {
+    let mut output = String::from("\"");
+    let mut chars = decoded.text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            // `\$` from `decode` is already an escaped dollar.
+            '\\' if decoded.expands && chars.peek() == Some(&'$') => {
+                chars.next();
+                output.push_str("\\$");
+            }
+            '\\' => output.push_str("\\\\"),
+            '"' => output.push_str("\\\""),
+            '$' if !decoded.expands => output.push_str("\\$"),
+            '`' if dialect == Dialect::Shell => output.push_str("\\`"),
+            '\n' if dialect != Dialect::Shell => output.push_str("\\n"),
+            '\r' if dialect != Dialect::Shell => output.push_str("\\r"),
+            _ => output.push(ch),
+        }
+    }
+    output.push('"');
+    output
+}
+
+fn backslash_escape(decoded: &Decoded) -> String {
+    let mut output = String::new();
+    let mut chars = decoded.text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' && decoded.expands && chars.peek() == Some(&'$') {
+            chars.next();
+            output.push_str("\\$");
+        } else if is_shell_special(ch) && !(ch == '$' && decoded.expands) {
+            output.push('\\');
+            output.push(ch);
+        } else {
+            output.push(ch);
+        }
+    }
+    output
+}
+
+fn encode(value: &str, style: QuoteStyle) -> Result<String, &'static str> {
+    encode_decoded(decode(value), style)
+}
+
+fn encode_decoded(decoded: Decoded, style: QuoteStyle) -> Result<String, &'static str> {
+    let multi_line_ok = matches!(
+        style.dialect,
+        Dialect::Dotenv | Dialect::Phpdotenv | Dialect::Python
+    );
+    if decoded.text.contains(['\n', '\r']) && !multi_line_ok {
+        return Err("multi-line values can only be written in dotenv dialects");
+    }
+    if style.dialect == Dialect::Docker {
+        return Ok(unescape_dollars(decoded));
+    }
+
+    let quote = needs_quotes(&decoded, style.dialect);
+    match style.policy {
+        QuotePolicy::WhenNeeded | QuotePolicy::Never if !quote => return Ok(decoded.text),
+        QuotePolicy::Never if style.dialect == Dialect::Shell => {
+            return Ok(backslash_escape(&decoded))
+        }
+        QuotePolicy::Never => return Err("value cannot be written without quotes"),
+        _ => {}
+    }
+    // Single quotes are the simplest safe form when nothing inside needs escaping.
+    if !decoded.expands && !decoded.text.contains(['\'', '\n', '\r']) {
+        return Ok(format!("'{}'", decoded.text));
+    }
+    Ok(double_quote(&decoded, style.dialect))
+}
+
+/// Re-encodes one raw value under `style`, e.g. for lint suggestions.
+pub fn encode_value(value: &str, style: QuoteStyle) -> Result<String, AppError> {
+    encode(value, style).map_err(|message| AppError::InvalidInput(message.to_string()))
+}
+
+/// Re-encodes the values of lines edited through structured commands (lines without
+/// raw text) from their decoded form, so a value can switch between bare and quoted
+/// without changing what the program reads. Untouched lines keep their bytes.
+pub fn apply_quote_style(lines: &mut [EnvLine], style: QuoteStyle) -> Result<(), AppError> {
+    for line in lines.iter_mut() {
+        if let EnvLine::Kv {
+            key,
+            value,
+            raw: None,
+            ..
+        } = line
+        {
+            *value = encode(value, style)
+                .map_err(|message| AppError::InvalidInput(format!("{}: {}", key, message)))?;
+        }
+    }
+    Ok(())
+}
+
+/// Drops the `\$` markers of an expanding value; literal text has none.
+fn unescape_dollars(decoded: Decoded) -> String {
+    if decoded.expands {
+        decoded.text.replace("\\$", "$")
+    } else {
+        decoded.text
+    }
+}
+
+/// The text a program reads for a raw env value: quotes removed, escapes applied.
+pub fn decode_value(value: &str) -> String {
+    unescape_dollars(decode(value))
+}
+
+/// Rewrites the decoded text of a raw value with `edit` and encodes the result the way
+/// the original was written: a quoted value stays quoted and `$` expands only where it
+/// did before. Escaped dollars reach `edit` as `\$`. An unchanged text keeps its bytes.
+pub fn replace_decoded(value: &str, edit: impl FnOnce(&str) -> String) -> String {
+    let decoded = decode(value);
+    let text = edit(&decoded.text);
+    if text == decoded.text {
+        return value.to_string();
+    }
+    let policy = if value.trim_start().starts_with(['"', '\'', '`']) {
+        QuotePolicy::Always
+    } else {
+        QuotePolicy::WhenNeeded
+    };
+    let style = QuoteStyle {
+        policy,
+        dialect: Dialect::Dotenv,
+    };
+    let expands = decoded.expands;
+    encode_decoded(Decoded { text, expands }, style).unwrap_or_else(|_| value.to_string())
+}
+
+/// Encodes a literal value (no `$` expansion) for a dotenv file, quoting only when
+/// needed. Used when importing values from other tools.
+pub fn encode_literal(text: &str) -> String {
+    encode_literal_with(text, QuotePolicy::WhenNeeded)
+}
+
+/// Like `encode_literal` but always quoted, for tools that expect quoted dotenv.
+pub fn quote_literal(text: &str) -> String {
+    encode_literal_with(text, QuotePolicy::Always)
+}
+
+fn encode_literal_with(text: &str, policy: QuotePolicy) -> String {
+    let decoded = Decoded {
+        text: text.to_string(),
+        expands: false,
+    };
+    let style = QuoteStyle {
+        policy,
+        dialect: Dialect::Dotenv,
+    };
+    encode_decoded(decoded, style).unwrap_or_else(|_| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LITERALS: &[&str] = &[
+        "",
+        "plain",
+        "with space",
+        " padded ",
+        "has#hash",
+        "quote\"d",
+        "it's",
+        "line\nbreak",
+        "$HOME stays literal",
+        "back\\slash",
+        "a\\$b",
+        "it's $5",
+        "it's \\$5",
+    ];
+
+    fn style(policy: QuotePolicy, dialect: Dialect) -> QuoteStyle {
+        QuoteStyle { policy, dialect }
+    }
+
+    #[test]
+    fn decodes_quotes_and_escapes() {
+        assert_eq!(decode_value("  bare  "), "bare");
+        assert_eq!(decode_value("\"a\\nb\\t\\\"c\\\"\""), "a\nb\t\"c\"");
+        assert_eq!(decode_value("'a\\nb'"), "a\\nb");
+        assert_eq!(decode_value("`tick`"), "tick");
+        assert_eq!(decode_value("\"cost \\$5\""), "cost $5");
+    }
+
+    #[test]
+    fn literals_round_trip_through_encode_literal() {
+        for literal in LITERALS {
+            assert_eq!(
+                decode_value(&encode_literal(literal)),
+                *literal,
+                "{:?}",
+                literal
+            );
+        }
+    }
+
+    #[test]
+    fn literals_round_trip_through_quote_literal() {
+        for literal in LITERALS {
+            let encoded = quote_literal(literal);
+            assert!(encoded.starts_with(['"', '\'']), "{:?}", encoded);
+            assert_eq!(decode_value(&encoded), *literal, "{:?}", literal);
+        }
+    }
+
+    #[test]
+    fn quotes_only_when_needed() {
+        assert_eq!(encode_literal("plain"), "plain");
+        assert_eq!(encode_literal("has#hash"), "'has#hash'");
+        assert_eq!(encode_literal("$HOME"), "'$HOME'");
+        assert_eq!(encode_literal("it's"), "\"it's\"");
+    }
+
+    #[test]
+    fn re_encoding_keeps_the_decoded_value() {
+        let values = ["plain", "\"a b\"", "'$lit'", "\"x\\ny\"", "\"cost \\$5\""];
+        let styles = [
+            style(QuotePolicy::Always, Dialect::Dotenv),
+            style(QuotePolicy::WhenNeeded, Dialect::Dotenv),
+            style(QuotePolicy::WhenNeeded, Dialect::Phpdotenv),
+            style(QuotePolicy::WhenNeeded, Dialect::Python),
+        ];
+        for value in values {
+            for quote_style in styles {
+                let encoded = encode_value(value, quote_style).unwrap();
+                assert_eq!(decode_value(&encoded), decode_value(value), "{:?}", encoded);
+            }
+        }
+    }
+
+    #[test]
+    fn shell_values_are_escaped_without_quotes() {
+        let shell = style(QuotePolicy::Never, Dialect::Shell);
+        assert_eq!(encode_value("\"a b\"", shell).unwrap(), "a\\ b");
+        assert_eq!(encode_value("'$x'", shell).unwrap(), "\\$x");
+    }
+
+    #[test]
+    fn docker_values_are_written_literally() {
+        let docker = style(QuotePolicy::WhenNeeded, Dialect::Docker);
+        assert_eq!(encode_value("\"a # b\"", docker).unwrap(), "a # b");
+        assert_eq!(encode_value("\"cost \\$5\"", docker).unwrap(), "cost $5");
+    }
+
+    #[test]
+    fn refuses_what_a_dialect_cannot_express() {
+        let multi_line = "\"a\\nb\"";
+        assert!(encode_value(multi_line, style(QuotePolicy::WhenNeeded, Dialect::Shell)).is_err());
+        assert!(encode_value("\"a#b\"", style(QuotePolicy::Never, Dialect::Dotenv)).is_err());
+    }
+
+    #[test]
+    fn replacing_decoded_text_keeps_the_quoting() {
+        let rename = |text: &str| text.replace("old", "new");
+        assert_eq!(replace_decoded("old", rename), "new");
+        assert_eq!(replace_decoded("'old'", rename), "'new'");
+        assert_eq!(replace_decoded("\"old \\$x\"", rename), "\"new \\$x\"");
+        assert_eq!(replace_decoded("\"a\\nold\"", rename), "\"a\\nnew\"");
+        assert_eq!(replace_decoded("\"same\"", rename), "\"same\"");
+        assert_eq!(replace_decoded("old", |_| "a#b".to_string()), "\"a#b\"");
+    }
+}
@@ -0,0 +1,279 @@
+use crate::ipc_path::decode_path;
+use crate::lint::lint_paths;
+use crate::variants::{variant_matrix, RowStatus, VariantMatrix};
+use crate::{app_lock, files_in_group, run_blocking, AppError, AppState};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::AppHandle;
+
+const REDACTED: &str = "••••••";
+const EMPTY: &str = "(empty)";
+const MISSING: &str = "—";
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvReport {
+    /// Suggested name when the UI saves the report.
+    pub file_name: String,
+    pub content: String,
+}
+
+struct Section {
+    title: &'static str,
+    /// Shown instead of the table when there are no rows.
+    empty: &'static str,
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+fn status_label(status: RowStatus) -> &'static str {
+    match status {
+        RowStatus::Same => "same",
+        RowStatus::Different => "different",
+        RowStatus::Missing => "missing",
+    }
+}
+
+fn value_cell(value: Option<&String>, redact: bool) -> String {
+    match value {
+        None => MISSING.to_string(),
+        Some(value) if value.is_empty() => EMPTY.to_string(),
+        Some(_) if redact => REDACTED.to_string(),
+        Some(value) => value.clone(),
+    }
+}
+
+fn files_section(matrix: &VariantMatrix) -> Section {
+    let rows = matrix
+        .files
+        .iter()
+        .enumerate()
+        .map(|(index, file)| {
+            let keys = matrix
+                .rows
+                .iter()
+                .filter(|row| row.values[index].is_some())
+                .count();
+            vec![file.clone(), keys.to_string()]
+        })
+        .collect();
+    Section {
+        title: "Files",
+        empty: "No env files in this project.",
+        headers: vec!["File".to_string(), "Keys".to_string()],
+        rows,
+    }
+}
+
+fn keys_section(matrix: &VariantMatrix, redact: bool) -> Section {
+    let mut headers = vec!["Key".to_string()];
+    headers.extend(matrix.files.iter().cloned());
+    headers.push("Status".to_string());
+    let rows = matrix
+        .rows
+        .iter()
+        .map(|row| {
+            let mut cells = vec![row.key.clone()];
+            cells.extend(
+                row.values
+                    .iter()
+                    .map(|value| value_cell(value.as_ref(), redact)),
+            );
+            cells.push(status_label(row.status).to_string());
+            cells
+        })
+        .collect();
+    Section {
+        title: "Keys per variant",
+        empty: "No keys defined.",
+        headers,
+        rows,
+    }
+}
+
+/// Keys that are missing from some variants or differ between them.
+fn drift_section(matrix: &VariantMatrix) -> Section {
+    let rows = matrix
+        .rows
+        .iter()
+        .filter(|row| row.status != RowStatus::Same)
+        .map(|row| {
+            let missing_from: Vec<&str> = matrix
+                .files
+                .iter()
+                .zip(&row.values)
+                .filter(|(_, value)| value.is_none())
+                .map(|(file, _)| file.as_str())
+                .collect();
+            vec![
+                row.key.clone(),
+                status_label(row.status).to_string(),
+                missing_from.join(", "),
+            ]
+        })
+        .collect();
+    Section {
+        title: "Drift",
+        empty: "All variants define the same keys with the same values.",
+        headers: vec![
+            "Key".to_string(),
+            "Status".to_string(),
+            "Missing from".to_string(),
+        ],
+        rows,
+    }
+}
+
+fn lint_section(state: &AppState, group_id: &str) -> Result<Section, AppError> {
+    let diagnostics = lint_paths(state, files_in_group(state, group_id)?);
+    let rows = diagnostics
+        .into_iter()
+        .map(|diagnostic| {
+            let file = Path::new(&diagnostic.path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or(diagnostic.path);
+            vec![
+                diagnostic.severity.label().to_string(),
+                file,
+                diagnostic
+                    .line
+                    .map(|line| line.to_string())
+                    .unwrap_or_default(),
+                diagnostic.rule,
+                diagnostic.message,
+            ]
+        })
+        .collect();
+    Ok(Section {
+        title: "Lint findings",
+        empty: "No lint findings.",
+        headers: ["Severity", "File", "Line", "Rule", "Message"]
+            .map(String::from)
+            .to_vec(),
+        rows,
+    })
+}
+
+fn markdown_cell(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\r', '\n'], " ")
+}
+
+fn render_markdown(title: &str, subtitle: &str, sections: &[Section]) -> String {
+    let mut output = format!("# {}\n\n{}\n", markdown_cell(title), subtitle);
+    for section in sections {
+        output.push_str(&format!("\n## {}\n\n", section.title));
+        if section.rows.is_empty() {
+            output.push_str(&format!("{}\n", section.empty));
+            continue;
+        }
+        let header: Vec<String> = section.headers.iter().map(|h| markdown_cell(h)).collect();
+        output.push_str(&format!("| {} |\n", header.join(" | ")));
+        output.push_str(&format!("|{}\n", " --- |".repeat(header.len())));
+        for row in &section.rows {
+            let cells: Vec<String> = row.iter().map(|cell| markdown_cell(cell)).collect();
+            output.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+    }
+    output
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn render_html(title: &str, subtitle: &str, sections: &[Section]) -> String {
+    let mut output = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n\
+         <style>body{{font-family:sans-serif}}table{{border-collapse:collapse}}\
+         th,td{{border:1px solid #ccc;padding:4px 8px;text-align:left}}</style>\n\
+         </head>\n<body>\n<h1>{0}</h1>\n<p>{1}</p>\n",
+        html_escape(title),
+        html_escape(subtitle)
+    );
+    for section in sections {
+        output.push_str(&format!("<h2>{}</h2>\n", html_escape(section.title)));
+        if section.rows.is_empty() {
+            output.push_str(&format!("<p>{}</p>\n", html_escape(section.empty)));
+            continue;
+        }
+        output.push_str("<table>\n<tr>");
+        for header in &section.headers {
+            output.push_str(&format!("<th>{}</th>", html_escape(header)));
+        }
+        output.push_str("</tr>\n");
+        for row in &section.rows {
+            output.push_str("<tr>");
+            for cell in row {
+                output.push_str(&format!("<td>{}</td>", html_escape(cell)));
+            }
+            output.push_str("</tr>\n");
+        }
+        output.push_str("</table>\n");
+    }
+    output.push_str("</body>\n</html>\n");
+    output
+}
+
+/// Builds a shareable report for one project: the files, every key per variant,
+/// drift between variants and lint findings. Values are redacted unless `redact` is
+/// explicitly false.
+#[tauri::command]
+pub async fn export_report(
+    app: AppHandle,
+    group_id: String,
+    format: ReportFormat,
+    redact: Option<bool>,
+) -> Result<EnvReport, AppError> {
+    run_blocking(app, move |_, state| {
+        app_lock::ensure_unlocked(&state)?;
+        let redact = redact.unwrap_or(true);
+        let matrix = variant_matrix(&state, group_id.clone())?;
+        let project = matrix
+            .paths
+            .first()
+            .map(|path| decode_path(path))
+            .and_then(|path| {
+                path.parent()
+                    .and_then(Path::file_name)
+                    .map(|name| name.to_string_lossy().to_string())
+            })
+            .unwrap_or_else(|| "project".to_string());
+
+        let sections = vec![
+            files_section(&matrix),
+            keys_section(&matrix, redact),
+            drift_section(&matrix),
+            lint_section(&state, &group_id)?,
+        ];
+        let title = format!("Environment report: {}", project);
+        let subtitle = format!(
+            "Generated {}{}",
+            Local::now().format("%Y-%m-%d %H:%M"),
+            if redact { ", values redacted" } else { "" }
+        );
+        let (content, extension) = match format {
+            ReportFormat::Markdown => (render_markdown(&title, &subtitle, &sections), "md"),
+            ReportFormat::Html => (render_html(&title, &subtitle, &sections), "html"),
+        };
+        Ok(EnvReport {
+            file_name: format!("{}-env-report.{}", project, extension),
+            content,
+        })
+    })
+    .await
+}
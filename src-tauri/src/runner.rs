@@ -0,0 +1,147 @@
+use crate::interop::read_values;
+use crate::ipc_path::decode_path;
+use crate::{app_lock, run_blocking, AppError, AppState};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, Stdio};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+pub const RUN_OUTPUT_EVENT: &str = "run-output";
+pub const RUN_EXIT_EVENT: &str = "run-exit";
+const EXIT_POLL: Duration = Duration::from_millis(100);
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunOutput {
+    run_id: String,
+    stream: OutputStream,
+    line: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunExit {
+    run_id: String,
+    /// `None` when the process was killed by a signal or could not be waited on.
+    code: Option<i32>,
+}
+
+/// Processes started by `run_with_env` that have not exited yet.
+pub type RunningProcesses = RwLock<Vec<(String, Arc<Mutex<Child>>)>>;
+
+fn forward(
+    app: AppHandle,
+    run_id: String,
+    stream: OutputStream,
+    source: impl Read + Send + 'static,
+) {
+    thread::spawn(move || {
+        for line in BufReader::new(source).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            let _ = app.emit_all(
+                RUN_OUTPUT_EVENT,
+                RunOutput {
+                    run_id: run_id.clone(),
+                    stream,
+                    line,
+                },
+            );
+        }
+    });
+}
+
+fn wait(app: AppHandle, run_id: String, child: Arc<Mutex<Child>>) {
+    thread::spawn(move || {
+        let code = loop {
+            let status = child.lock().try_wait();
+            match status {
+                Ok(Some(status)) => break status.code(),
+                Ok(None) => thread::sleep(EXIT_POLL),
+                Err(_) => break None,
+            }
+        };
+        let state = app.state::<AppState>();
+        state
+            .running_processes
+            .write()
+            .retain(|(id, _)| *id != run_id);
+        let _ = app.emit_all(RUN_EXIT_EVENT, RunExit { run_id, code });
+    });
+}
+
+/// Starts `program` in the file's folder with the file's variables added to the app's
+/// environment, like dotenv-cli. Output lines arrive as `run-output` events and the
+/// exit code as a `run-exit` event; the returned id ties them to this run.
+#[tauri::command]
+pub async fn run_with_env(
+    app: AppHandle,
+    path: String,
+    program: String,
+    args: Option<Vec<String>>,
+) -> Result<String, AppError> {
+    run_blocking(app, move |app, state| {
+        app_lock::ensure_unlocked(&state)?;
+        if program.trim().is_empty() {
+            return Err(AppError::InvalidInput("Program is required".to_string()));
+        }
+        let path_buf = decode_path(&path);
+        let values = read_values(&state, &path_buf, None)?;
+
+        let mut command = Command::new(&program);
+        command
+            .args(args.unwrap_or_default())
+            .envs(values)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(folder) = path_buf.parent() {
+            command.current_dir(folder);
+        }
+        let mut child = command.spawn().map_err(|e| {
+            AppError::io(
+                e.kind().into(),
+                format!("Could not start {}: {}", program, e),
+            )
+        })?;
+
+        let run_id = format!("{:016x}", rand::random::<u64>());
+        if let Some(stdout) = child.stdout.take() {
+            forward(app.clone(), run_id.clone(), OutputStream::Stdout, stdout);
+        }
+        if let Some(stderr) = child.stderr.take() {
+            forward(app.clone(), run_id.clone(), OutputStream::Stderr, stderr);
+        }
+        let child = Arc::new(Mutex::new(child));
+        state
+            .running_processes
+            .write()
+            .push((run_id.clone(), child.clone()));
+        wait(app, run_id.clone(), child);
+        Ok(run_id)
+    })
+    .await
+}
+
+/// Kills a process started by `run_with_env`. Returns false if it already exited.
+#[tauri::command]
+pub async fn stop_run(state: State<'_, AppState>, run_id: String) -> Result<bool, AppError> {
+    let running = state.running_processes.read();
+    let Some((_, child)) = running.iter().find(|(id, _)| *id == run_id) else {
+        return Ok(false);
+    };
+    let killed = child.lock().kill().is_ok();
+    Ok(killed)
+}
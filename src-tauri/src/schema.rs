@@ -0,0 +1,256 @@
+use crate::ipc_path::{decode_path, encode_path};
+use crate::{
+    ensure_allowed_path, infer_value_type, normalize_path, parse_env_lines, path_starts_with,
+    read_env_text, run_blocking, unquote, AppError, AppState, EnvLine, ValueType,
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+const SCHEMA_FILE_NAMES: [&str; 2] = [".env.schema.json", ".env.schema.toml"];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvSchema {
+    #[serde(default)]
+    keys: BTreeMap<String, KeyRule>,
+    #[serde(default = "default_allow_unknown")]
+    allow_unknown: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyRule {
+    #[serde(default)]
+    required: bool,
+    #[serde(default, rename = "type")]
+    value_type: Option<ValueType>,
+    #[serde(default)]
+    pattern: Option<String>,
+    #[serde(default)]
+    allowed: Option<Vec<String>>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Violation {
+    key: String,
+    rule: String,
+    message: String,
+    line: Option<usize>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationReport {
+    path: String,
+    schema_path: Option<String>,
+    violations: Vec<Violation>,
+}
+
+fn default_allow_unknown() -> bool {
+    true
+}
+
+fn load_schema(path: &Path) -> Result<EnvSchema, AppError> {
+    let contents = fs::read_to_string(path)?;
+    let is_toml = path
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case("toml"))
+        .unwrap_or(false);
+    if is_toml {
+        toml::from_str(&contents).map_err(|e| AppError::SchemaError(e.to_string()))
+    } else {
+        serde_json::from_str(&contents).map_err(|e| AppError::SchemaError(e.to_string()))
+    }
+}
+
+/// Finds the closest schema file, walking up from the env file's folder to the scan
+/// root. A file outside the root only looks in its own folder.
+fn find_schema(env_path: &Path, root: Option<&Path>) -> Option<PathBuf> {
+    let root = root.filter(|root| path_starts_with(env_path, root));
+    let mut current = env_path.parent();
+    while let Some(dir) = current {
+        for name in SCHEMA_FILE_NAMES {
+            let candidate = dir.join(name);
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
+        match root {
+            Some(root) if !path_starts_with(root, dir) => current = dir.parent(),
+            _ => break,
+        }
+    }
+    None
+}
+
+fn type_matches(expected: ValueType, actual: ValueType) -> bool {
+    match expected {
+        ValueType::String => true,
+        ValueType::Float => matches!(actual, ValueType::Float | ValueType::Integer),
+        ValueType::Path => matches!(actual, ValueType::Path | ValueType::String),
+        _ => expected == actual,
+    }
+}
+
+pub fn validate_lines(schema: &EnvSchema, lines: &[EnvLine]) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let mut seen: BTreeMap<&str, usize> = BTreeMap::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        let EnvLine::Kv { key, value, .. } = line else {
+            continue;
+        };
+        seen.insert(key.as_str(), index + 1);
+        let line_number = Some(index + 1);
+
+        let Some(rule) = schema.keys.get(key) else {
+            if !schema.allow_unknown {
+                violations.push(Violation {
+                    key: key.clone(),
+                    rule: "unknown".to_string(),
+                    message: format!("{} is not declared in the schema", key),
+                    line: line_number,
+                });
+            }
+            continue;
+        };
+
+        let value = unquote(value);
+        if let Some(expected) = rule.value_type {
+            let actual = infer_value_type(value);
+            if actual != ValueType::Empty && !type_matches(expected, actual) {
+                violations.push(Violation {
+                    key: key.clone(),
+                    rule: "type".to_string(),
+                    message: format!("{} should be of type {}", key, expected.as_str()),
+                    line: line_number,
+                });
+            }
+        }
+        if let Some(pattern) = &rule.pattern {
+            match Regex::new(pattern) {
+                Ok(regex) if !regex.is_match(value) => violations.push(Violation {
+                    key: key.clone(),
+                    rule: "pattern".to_string(),
+                    message: format!("{} does not match pattern {}", key, pattern),
+                    line: line_number,
+                }),
+                Ok(_) => {}
+                Err(_) => violations.push(Violation {
+                    key: key.clone(),
+                    rule: "pattern".to_string(),
+                    message: format!("Invalid pattern in schema: {}", pattern),
+                    line: line_number,
+                }),
+            }
+        }
+        if let Some(allowed) = &rule.allowed {
+            if !allowed.iter().any(|candidate| candidate == value) {
+                violations.push(Violation {
+                    key: key.clone(),
+                    rule: "allowed".to_string(),
+                    message: format!("{} must be one of: {}", key, allowed.join(", ")),
+                    line: line_number,
+                });
+            }
+        }
+        if rule.required && value.is_empty() {
+            violations.push(Violation {
+                key: key.clone(),
+                rule: "required".to_string(),
+                message: format!("{} is required but empty", key),
+                line: line_number,
+            });
+        }
+    }
+
+    for (key, rule) in &schema.keys {
+        if rule.required && !seen.contains_key(key.as_str()) {
+            violations.push(Violation {
+                key: key.clone(),
+                rule: "required".to_string(),
+                message: format!("{} is required but missing", key),
+                line: None,
+            });
+        }
+    }
+
+    violations
+}
+
+#[tauri::command]
+pub async fn validate_env(app: AppHandle, path: String) -> Result<ValidationReport, AppError> {
+    run_blocking(app, move |_, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        let path_buf = normalize_path(&path_buf)?;
+
+        let root = state.root_path.read().clone();
+        let Some(schema_path) = find_schema(&path_buf, root.as_deref()) else {
+            return Ok(ValidationReport {
+                path,
+                schema_path: None,
+                violations: Vec::new(),
+            });
+        };
+
+        let schema = load_schema(&schema_path)?;
+        let contents = read_env_text(&state, &path_buf)?;
+        let violations = validate_lines(&schema, &parse_env_lines(&contents));
+
+        Ok(ValidationReport {
+            path,
+            schema_path: Some(encode_path(&schema_path)),
+            violations,
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_search_stops_at_the_owning_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().canonicalize().unwrap();
+        let root = base.join("root");
+        fs::create_dir_all(root.join("app")).unwrap();
+        fs::write(base.join(".env.schema.json"), "{}").unwrap();
+        let env = root.join("app/.env");
+        assert!(find_schema(&env, Some(&root)).is_none());
+        assert!(find_schema(&env, None).is_none());
+
+        fs::write(root.join(".env.schema.json"), "{}").unwrap();
+        assert_eq!(
+            find_schema(&env, Some(&root)),
+            Some(root.join(".env.schema.json"))
+        );
+    }
+
+    #[test]
+    fn file_outside_the_root_only_searches_its_own_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().canonicalize().unwrap();
+        let outside = base.join("outside/nested");
+        fs::create_dir_all(&outside).unwrap();
+        fs::create_dir_all(base.join("root")).unwrap();
+        fs::write(base.join("outside/.env.schema.json"), "{}").unwrap();
+        let env = outside.join(".env");
+        assert!(find_schema(&env, Some(&base.join("root"))).is_none());
+
+        fs::write(outside.join(".env.schema.json"), "{}").unwrap();
+        assert_eq!(
+            find_schema(&env, Some(&base.join("root"))),
+            Some(outside.join(".env.schema.json"))
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use crate::ipc_path::encode_path;
+use crate::{
+    app_lock, ensure_allowed_path, has_command_substitution, hash_content, infer_value_type,
+    interpolation, parse_env_text, quoting, read_env_text, run_blocking, serialize_for_write,
+    unified_diff, write_contents, AppError, AppState, EnvLine, WriteOptions, WritePreview,
+};
+use regex::{NoExpand, Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchScope {
+    Keys,
+    Values,
+    #[default]
+    Both,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FindReplaceOptions {
+    regex: bool,
+    case_sensitive: bool,
+    scope: SearchScope,
+    /// Preview only unless set; the UI sets it after the user confirms.
+    apply: bool,
+    create_backup: bool,
+    /// `contentHash` of each file as previewed, by path. On apply, only these files are
+    /// written, and only if they are unchanged since the preview.
+    expected_hashes: HashMap<String, String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileReplacement {
+    path: String,
+    matches: usize,
+    content_hash: String,
+    preview: WritePreview,
+    applied: bool,
+    /// Why the file cannot be written; a file with conflicts is never applied.
+    conflicts: Vec<String>,
+}
+
+fn build_matcher(query: &str, options: &FindReplaceOptions) -> Result<Regex, AppError> {
+    let pattern = if options.regex {
+        Cow::Borrowed(query)
+    } else {
+        Cow::Owned(regex::escape(query))
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!options.case_sensitive)
+        .build()
+        .map_err(|_| AppError::RegexError)
+}
+
+fn replace_text(matcher: &Regex, text: &str, replacement: &str, literal: bool) -> String {
+    if literal {
+        matcher
+            .replace_all(text, NoExpand(replacement))
+            .into_owned()
+    } else {
+        matcher.replace_all(text, replacement).into_owned()
+    }
+}
+
+fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+/// Replaces matches in the keys of every assignment, `unset` and `+=` line, and in the
+/// decoded values, which are re-encoded with their original quoting. References to a
+/// renamed key follow it. Returns the match count and why the result cannot be written.
+fn replace_in_lines(
+    lines: &mut [EnvLine],
+    matcher: &Regex,
+    replacement: &str,
+    literal: bool,
+    scope: SearchScope,
+) -> (usize, Vec<String>) {
+    let original_keys: HashSet<String> = lines
+        .iter()
+        .filter_map(|line| match line {
+            EnvLine::Kv { key, .. } | EnvLine::Unset { key, .. } | EnvLine::Append { key, .. } => {
+                Some(key.clone())
+            }
+            _ => None,
+        })
+        .collect();
+    let mut renamed: HashMap<String, String> = HashMap::new();
+    let mut conflicts = Vec::new();
+    let mut matches = 0;
+
+    for line in lines.iter_mut() {
+        let (key, value, raw, position) = match line {
+            EnvLine::Kv {
+                key,
+                value,
+                raw,
+                position,
+                ..
+            }
+            | EnvLine::Append {
+                key,
+                value,
+                raw,
+                position,
+                ..
+            } => (key, Some(value), raw, position),
+            EnvLine::Unset { key, raw, position } => (key, None, raw, position),
+            _ => continue,
+        };
+        if scope != SearchScope::Values && matcher.is_match(key) {
+            matches += matcher.find_iter(key).count();
+            let next = replace_text(matcher, key, replacement, literal);
+            if next != *key {
+                if !is_valid_key(&next) {
+                    conflicts.push(format!("{} would become an invalid key: {:?}", key, next));
+                } else if original_keys.contains(&next)
+                    || renamed
+                        .insert(next.clone(), key.clone())
+                        .is_some_and(|from| from != *key)
+                {
+                    conflicts.push(format!("{} would duplicate {}", key, next));
+                }
+                *key = next;
+                *raw = None;
+                position.key_span = None;
+            }
+        }
+        let Some(value) = value else {
+            continue;
+        };
+        if scope != SearchScope::Keys {
+            let next = quoting::replace_decoded(value, |text| {
+                matches += matcher.find_iter(text).count();
+                replace_text(matcher, text, replacement, literal)
+            });
+            if next != *value {
+                *value = next;
+                *raw = None;
+                position.value_span = None;
+            }
+        }
+    }
+
+    let renames: HashMap<String, String> =
+        renamed.into_iter().map(|(new, old)| (old, new)).collect();
+    for line in lines.iter_mut() {
+        let (EnvLine::Kv {
+            value,
+            raw,
+            position,
+            ..
+        }
+        | EnvLine::Append {
+            value,
+            raw,
+            position,
+            ..
+        }) = line
+        else {
+            continue;
+        };
+        let next = interpolation::rename_references(value, &renames);
+        if next != *value {
+            *value = next;
+            *raw = None;
+            position.value_span = None;
+        }
+    }
+
+    for line in lines.iter_mut() {
+        if let EnvLine::Kv {
+            value,
+            value_type,
+            dangerous,
+            raw: None,
+            ..
+        } = line
+        {
+            *value_type = infer_value_type(value);
+            *dangerous = has_command_substitution(value);
+        }
+    }
+
+    (matches, conflicts)
+}
+
+/// Replaces matches in keys and/or values of every allowed file. Returns a diff per
+/// affected file; files are only written (one atomic rename each) when `apply` is set,
+/// and a file whose replaced keys are invalid or collide is reported instead of written.
+#[tauri::command]
+pub async fn find_replace(
+    app: AppHandle,
+    query: String,
+    replacement: String,
+    options: Option<FindReplaceOptions>,
+) -> Result<Vec<FileReplacement>, AppError> {
+    run_blocking(app, move |_, state| {
+        app_lock::ensure_unlocked(&state)?;
+        let options = options.unwrap_or_default();
+        if query.is_empty() {
+            return Err(AppError::InvalidInput("Search query is empty".to_string()));
+        }
+        let matcher = build_matcher(&query, &options)?;
+        let literal = !options.regex;
+        let write_options = WriteOptions {
+            create_backup: options.create_backup,
+            expected_hash: None,
+            expected_mtime: None,
+            quote_style: None,
+        };
+
+        let mut paths: Vec<PathBuf> = state.allowed_files.read().iter().cloned().collect();
+        paths.sort();
+
+        let mut results = Vec::new();
+        for path in paths {
+            ensure_allowed_path(&state, &path)?;
+            let Ok(contents) = read_env_text(&state, &path) else {
+                continue;
+            };
+            let (mut lines, layout) = parse_env_text(&contents);
+            let (matches, mut conflicts) =
+                replace_in_lines(&mut lines, &matcher, &replacement, literal, options.scope);
+
+            let updated = serialize_for_write(&state, &path, &lines, layout, &write_options)?;
+            if matches == 0 || updated == contents {
+                continue;
+            }
+
+            let display_path = encode_path(&path);
+            let content_hash = hash_content(contents.as_bytes());
+            let mut applied = false;
+            if options.apply && conflicts.is_empty() {
+                match options.expected_hashes.get(&display_path) {
+                    Some(expected) if *expected == content_hash => {
+                        let write_options = WriteOptions {
+                            expected_hash: Some(expected.clone()),
+                            ..write_options.clone()
+                        };
+                        write_contents(&state, &path, &updated, &write_options)?;
+                        applied = true;
+                    }
+                    Some(_) => conflicts.push("File changed since the preview".to_string()),
+                    None => conflicts.push("File was not in the preview".to_string()),
+                }
+            }
+            results.push(FileReplacement {
+                preview: unified_diff(&display_path, &contents, &updated),
+                path: display_path,
+                matches,
+                content_hash,
+                applied,
+                conflicts,
+            });
+        }
+
+        Ok(results)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{serialize_with_layout, ValueType};
+
+    fn replace(text: &str, query: &str, replacement: &str, scope: SearchScope) -> String {
+        let (mut lines, layout) = parse_env_text(text);
+        let options = FindReplaceOptions {
+            scope,
+            ..FindReplaceOptions::default()
+        };
+        let matcher = build_matcher(query, &options).unwrap();
+        let (_, conflicts) = replace_in_lines(&mut lines, &matcher, replacement, true, scope);
+        assert!(conflicts.is_empty(), "{:?}", conflicts);
+        serialize_with_layout(&lines, layout)
+    }
+
+    #[test]
+    fn renames_follow_every_line_kind_and_reference() {
+        let text = "OLD_HOST=db\nURL=\"http://${OLD_HOST}:$OLD_HOST/\"\nLIT='$OLD_HOST'\nOLD_HOST+=.local\nunset OLD_HOST\n";
+        assert_eq!(
+            replace(text, "OLD_", "NEW_", SearchScope::Keys),
+            "NEW_HOST=db\nURL=\"http://${NEW_HOST}:$NEW_HOST/\"\nLIT='$OLD_HOST'\nNEW_HOST+=.local\nunset NEW_HOST\n"
+        );
+    }
+
+    #[test]
+    fn values_are_replaced_as_decoded_and_keep_their_quoting() {
+        let text = "A=\"one\\ntwo\"\nB='two'\nC=two\n";
+        assert_eq!(
+            replace(text, "two", "2 # x", SearchScope::Values),
+            "A=\"one\\n2 # x\"\nB='2 # x'\nC=\"2 # x\"\n"
+        );
+    }
+
+    #[test]
+    fn replaced_values_get_their_type_again() {
+        let (mut lines, _) = parse_env_text("PORT=http\nCMD=echo\n");
+        let matcher = build_matcher("http", &FindReplaceOptions::default()).unwrap();
+        replace_in_lines(&mut lines, &matcher, "8080", true, SearchScope::Values);
+        let matcher = build_matcher("echo", &FindReplaceOptions::default()).unwrap();
+        replace_in_lines(&mut lines, &matcher, "$(id)", true, SearchScope::Values);
+        let EnvLine::Kv { value_type, .. } = &lines[0] else {
+            panic!("not an assignment");
+        };
+        assert!(*value_type == ValueType::Integer);
+        let EnvLine::Kv { dangerous, .. } = &lines[1] else {
+            panic!("not an assignment");
+        };
+        assert!(*dangerous);
+    }
+}
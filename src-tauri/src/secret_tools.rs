@@ -0,0 +1,126 @@
+use crate::interop::{decoded_values, import_values, read_values, ExportOutput, ImportResult};
+use crate::io_error::IoCode;
+use crate::ipc_path::decode_path;
+use crate::quoting::quote_literal;
+use crate::{parse_env_lines, run_blocking, AppError, AppState, WriteOptions};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use tauri::AppHandle;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretTool {
+    Doppler,
+    Infisical,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolFormat {
+    Json,
+    Dotenv,
+}
+
+fn json_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Reads the JSON exports of both CLIs:
+/// - `doppler secrets download --format json`: `{"KEY": "value"}`
+/// - `doppler secrets --json`: `{"KEY": {"computed": "...", "raw": "..."}}`
+/// - `infisical export --format=json`: `[{"key": "KEY", "value": "..."}]`
+fn parse_json_export(contents: &str) -> Result<Vec<(String, String)>, AppError> {
+    let parsed: Value = serde_json::from_str(contents)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid JSON export: {}", e)))?;
+    let entries = match parsed {
+        Value::Object(map) => map
+            .into_iter()
+            .map(|(key, value)| {
+                let text = match value.get("computed").or_else(|| value.get("raw")) {
+                    Some(inner) => json_text(inner),
+                    None => json_text(&value),
+                };
+                (key, text)
+            })
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| {
+                let key = item.get("key").and_then(Value::as_str)?;
+                let value = item.get("value").map(json_text).unwrap_or_default();
+                Some((key.to_string(), value))
+            })
+            .collect(),
+        _ => {
+            return Err(AppError::InvalidInput(
+                "Expected a JSON object or array of secrets".to_string(),
+            ))
+        }
+    };
+    Ok(entries)
+}
+
+/// Imports a Doppler or Infisical export (JSON or dotenv, detected from the content)
+/// into a local env file.
+#[tauri::command]
+pub async fn import_tool_export(
+    app: AppHandle,
+    contents: String,
+    target_path: String,
+    overwrite: bool,
+    options: Option<WriteOptions>,
+) -> Result<ImportResult, AppError> {
+    run_blocking(app, move |_, state| {
+        let trimmed = contents.trim_start();
+        let entries = if trimmed.starts_with('{') || trimmed.starts_with('[') {
+            parse_json_export(trimmed)?
+        } else {
+            decoded_values(&parse_env_lines(&contents))
+        };
+        import_values(&state, &target_path, entries, overwrite, options)
+    })
+    .await
+}
+
+/// Renders an env file in the export format of `tool`, so it can be uploaded with
+/// `doppler secrets upload` or `infisical secrets set --file`.
+#[tauri::command]
+pub async fn export_for_tool(
+    app: AppHandle,
+    path: String,
+    tool: SecretTool,
+    format: ToolFormat,
+    keys: Option<Vec<String>>,
+) -> Result<ExportOutput, AppError> {
+    run_blocking(app, move |_, state| {
+        let values = read_values(&state, &decode_path(&path), keys.as_deref())?;
+        let content = match (tool, format) {
+            (SecretTool::Doppler, ToolFormat::Json) => {
+                let map: Map<String, Value> = values
+                    .iter()
+                    .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+                    .collect();
+                serde_json::to_string_pretty(&map)
+                    .map_err(|e| AppError::io(IoCode::InvalidData, e.to_string()))?
+            }
+            (SecretTool::Infisical, ToolFormat::Json) => {
+                let items: Vec<Value> = values
+                    .iter()
+                    .map(|(key, value)| json!({ "key": key, "value": value, "type": "shared" }))
+                    .collect();
+                serde_json::to_string_pretty(&items)
+                    .map_err(|e| AppError::io(IoCode::InvalidData, e.to_string()))?
+            }
+            (_, ToolFormat::Dotenv) => values
+                .iter()
+                .map(|(key, value)| format!("{}={}\n", key, quote_literal(value)))
+                .collect(),
+        };
+        Ok(ExportOutput::new(content, values.len()))
+    })
+    .await
+}
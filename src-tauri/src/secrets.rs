@@ -0,0 +1,304 @@
+use crate::gitleaks;
+use crate::ipc_path::encode_path;
+use crate::lint::{Diagnostic, Severity};
+use crate::{
+    app_lock, files_in_group, logging, parse_env_lines, read_env_text, run_blocking, store,
+    unquote, AppError, AppState, EnvLine,
+};
+use parking_lot::RwLockWriteGuard;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tauri::{AppHandle, Manager, State};
+
+const SECRET_RULES_FILE: &str = "secret-rules.json";
+/// Diagnostics from these rules use `secret-<id>` as their rule name.
+const RULE_PREFIX: &str = "secret-";
+
+const BUILTIN_RULES: [(&str, &str, &str, Severity); 8] = [
+    (
+        "aws-access-key",
+        "AWS access key ID",
+        r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b",
+        Severity::Error,
+    ),
+    (
+        "github-token",
+        "GitHub token",
+        r"\bgh[pousr]_[A-Za-z0-9]{36,}\b",
+        Severity::Error,
+    ),
+    (
+        "slack-token",
+        "Slack token",
+        r"\bxox[abprs]-[A-Za-z0-9-]{10,}",
+        Severity::Error,
+    ),
+    (
+        "stripe-live-key",
+        "Stripe live secret key",
+        r"\b(?:sk|rk)_live_[A-Za-z0-9]{16,}\b",
+        Severity::Error,
+    ),
+    (
+        "google-api-key",
+        "Google API key",
+        r"\bAIza[0-9A-Za-z_-]{35}\b",
+        Severity::Error,
+    ),
+    (
+        "private-key",
+        "private key",
+        r"-----BEGIN [A-Z ]*PRIVATE KEY-----",
+        Severity::Error,
+    ),
+    (
+        "jwt",
+        "JSON web token",
+        r"\beyJ[A-Za-z0-9_-]+\.eyJ[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+",
+        Severity::Warning,
+    ),
+    (
+        "url-credentials",
+        "URL with embedded credentials",
+        r"[A-Za-z][A-Za-z0-9+.-]*://[^/\s:@]+:[^/\s:@]+@",
+        Severity::Warning,
+    ),
+];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretRule {
+    /// Short name, unique across built-in and custom rules.
+    pub id: String,
+    /// Completes "KEY contains a ..."; custom rules without one use the id.
+    #[serde(default)]
+    pub description: String,
+    pub pattern: String,
+    pub severity: Severity,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretRuleSet {
+    builtin: Vec<SecretRule>,
+    custom: Vec<SecretRule>,
+    gitleaks: Vec<SecretRule>,
+}
+
+pub struct CompiledRule {
+    pub rule: SecretRule,
+    regex: Regex,
+    /// Lowercase; when set, the rule only runs on text containing one of them.
+    keywords: Vec<String>,
+    /// Matches that also match one of these are not reported.
+    allowlist: Vec<Regex>,
+    /// Match `KEY=value` rather than the value alone, as gitleaks rules expect.
+    whole_line: bool,
+}
+
+impl CompiledRule {
+    pub fn new(rule: SecretRule) -> Result<Self, AppError> {
+        let regex = Regex::new(&rule.pattern).map_err(|_| AppError::RegexError)?;
+        Ok(CompiledRule {
+            rule,
+            regex,
+            keywords: Vec::new(),
+            allowlist: Vec::new(),
+            whole_line: false,
+        })
+    }
+
+    pub fn with_context(mut self, keywords: &[String], allowlist: Vec<Regex>) -> Self {
+        self.keywords = keywords.iter().map(|word| word.to_lowercase()).collect();
+        self.allowlist = allowlist;
+        self.whole_line = true;
+        self
+    }
+
+    fn matches(&self, key: &str, value: &str) -> bool {
+        let line;
+        let text = if self.whole_line {
+            line = format!("{}={}", key, value);
+            line.as_str()
+        } else {
+            value
+        };
+        if !self.keywords.is_empty() {
+            let lower = text.to_lowercase();
+            if !self.keywords.iter().any(|word| lower.contains(word)) {
+                return false;
+            }
+        }
+        self.regex.find_iter(text).any(|found| {
+            !self
+                .allowlist
+                .iter()
+                .any(|allowed| allowed.is_match(found.as_str()))
+        })
+    }
+}
+
+/// User-defined rules and rules loaded from a gitleaks config, kept in `AppState`.
+#[derive(Default)]
+pub struct SecretRules {
+    custom: Vec<CompiledRule>,
+    pub gitleaks: gitleaks::GitleaksRules,
+}
+
+pub fn builtin_rules() -> &'static [CompiledRule] {
+    static RULES: OnceLock<Vec<CompiledRule>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        BUILTIN_RULES
+            .iter()
+            .filter_map(|(id, description, pattern, severity)| {
+                CompiledRule::new(SecretRule {
+                    id: id.to_string(),
+                    description: description.to_string(),
+                    pattern: pattern.to_string(),
+                    severity: *severity,
+                })
+                .ok()
+            })
+            .collect()
+    })
+}
+
+/// Checks every value against `rules`. Messages name the key and rule, never the value.
+pub fn scan_lines(path: &str, lines: &[EnvLine], rules: &[CompiledRule]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let EnvLine::Kv { key, value, .. } = line else {
+            continue;
+        };
+        let value = unquote(value);
+        if value.is_empty() {
+            continue;
+        }
+        for compiled in rules.iter().filter(|compiled| compiled.matches(key, value)) {
+            diagnostics.push(Diagnostic {
+                rule: format!("{}{}", RULE_PREFIX, compiled.rule.id),
+                severity: compiled.rule.severity,
+                message: format!("{} contains a {}", key, compiled.rule.description),
+                path: path.to_string(),
+                line: Some(index + 1),
+                key: Some(key.clone()),
+                suggestion: None,
+            });
+        }
+    }
+    diagnostics
+}
+
+/// Checks and compiles user rules: ids must be unique and not shadow a built-in rule.
+fn compile_custom(rules: Vec<SecretRule>) -> Result<Vec<CompiledRule>, AppError> {
+    let mut ids: HashSet<String> = builtin_rules()
+        .iter()
+        .map(|compiled| compiled.rule.id.clone())
+        .collect();
+    rules
+        .into_iter()
+        .map(|mut rule| {
+            rule.id = rule.id.trim().to_string();
+            if rule.id.is_empty() {
+                return Err(AppError::InvalidInput("Rule name is required".to_string()));
+            }
+            if !ids.insert(rule.id.clone()) {
+                return Err(AppError::InvalidInput(format!(
+                    "A rule named {} already exists",
+                    rule.id
+                )));
+            }
+            if rule.description.trim().is_empty() {
+                rule.description = rule.id.clone();
+            }
+            let id = rule.id.clone();
+            CompiledRule::new(rule)
+                .map_err(|_| AppError::InvalidInput(format!("Rule {} has an invalid pattern", id)))
+        })
+        .collect()
+}
+
+pub fn resume(app: &AppHandle) -> Result<(), AppError> {
+    let rules: Vec<SecretRule> = store::load(app, SECRET_RULES_FILE)?;
+    let state = app.state::<AppState>();
+    state.secret_rules.write().custom = compile_custom(rules)?;
+    Ok(())
+}
+
+pub fn scan_paths(state: &AppState, paths: Vec<PathBuf>) -> Result<Vec<Diagnostic>, AppError> {
+    let mut rules = state.secret_rules.write();
+    if let Err(error) = gitleaks::refresh(&mut rules.gitleaks) {
+        tracing::warn!(
+            "failed to reload gitleaks config: {}",
+            logging::redacted(&error)
+        );
+    }
+    // Reading the files only needs shared access; other scans may proceed.
+    let rules = RwLockWriteGuard::downgrade(rules);
+    let builtin: &[CompiledRule] = if rules.gitleaks.replaces_builtin() {
+        &[]
+    } else {
+        builtin_rules()
+    };
+    let mut diagnostics = Vec::new();
+    for path in paths {
+        let lines = parse_env_lines(&read_env_text(state, &path)?);
+        let display = encode_path(&path);
+        diagnostics.extend(scan_lines(&display, &lines, builtin));
+        diagnostics.extend(scan_lines(&display, &lines, &rules.gitleaks.rules));
+        diagnostics.extend(scan_lines(&display, &lines, &rules.custom));
+    }
+    Ok(diagnostics)
+}
+
+/// Scans the values of every file in a project for known token and key formats.
+#[tauri::command]
+pub async fn scan_secrets(app: AppHandle, group_id: String) -> Result<Vec<Diagnostic>, AppError> {
+    run_blocking(app, move |_, state| {
+        scan_paths(&state, files_in_group(&state, &group_id)?)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn get_secret_rules(state: State<'_, AppState>) -> Result<SecretRuleSet, AppError> {
+    Ok(rule_set(&state))
+}
+
+fn rule_set(state: &AppState) -> SecretRuleSet {
+    let rules = |compiled: &[CompiledRule]| -> Vec<SecretRule> {
+        compiled
+            .iter()
+            .map(|compiled| compiled.rule.clone())
+            .collect()
+    };
+    let loaded = state.secret_rules.read();
+    SecretRuleSet {
+        builtin: rules(builtin_rules()),
+        custom: rules(&loaded.custom),
+        gitleaks: rules(&loaded.gitleaks.rules),
+    }
+}
+
+/// Replaces the custom rules, which are checked alongside the built-in ones.
+#[tauri::command]
+pub async fn set_secret_rules(
+    app: AppHandle,
+    rules: Vec<SecretRule>,
+) -> Result<SecretRuleSet, AppError> {
+    run_blocking(app, move |app, state| {
+        app_lock::ensure_unlocked(&state)?;
+        let compiled = compile_custom(rules)?;
+        let stored: Vec<SecretRule> = compiled
+            .iter()
+            .map(|compiled| compiled.rule.clone())
+            .collect();
+        store::save(&app, SECRET_RULES_FILE, &stored)?;
+        state.secret_rules.write().custom = compiled;
+        Ok(rule_set(&state))
+    })
+    .await
+}
@@ -0,0 +1,227 @@
+use crate::interop::new_kv_line;
+use crate::ipc_path::decode_path;
+use crate::quoting::encode_literal;
+use crate::{
+    ensure_allowed_path, parse_env_text, read_env_text, run_blocking, serialize_for_write,
+    write_contents, AppError, AppState, EnvLine, LinePosition, WriteOptions,
+};
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::OnceLock;
+use tauri::AppHandle;
+
+/// A value line: key, value with `{param}` placeholders, and whether the parameters are
+/// percent-encoded because they end up inside a URL.
+type SnippetLine = (&'static str, &'static str, bool);
+
+struct SnippetDef {
+    id: &'static str,
+    name: &'static str,
+    /// Parameter names with their defaults.
+    params: &'static [(&'static str, &'static str)],
+    lines: &'static [SnippetLine],
+}
+
+const SNIPPETS: [SnippetDef; 3] = [
+    SnippetDef {
+        id: "postgres",
+        name: "PostgreSQL connection",
+        params: &[
+            ("host", "localhost"),
+            ("port", "5432"),
+            ("user", "postgres"),
+            ("password", ""),
+            ("database", "app"),
+        ],
+        lines: &[(
+            "DATABASE_URL",
+            "postgresql://{user}:{password}@{host}:{port}/{database}",
+            true,
+        )],
+    },
+    SnippetDef {
+        id: "smtp",
+        name: "SMTP mail server",
+        params: &[
+            ("host", "localhost"),
+            ("port", "587"),
+            ("user", ""),
+            ("password", ""),
+            ("from", ""),
+        ],
+        lines: &[
+            ("SMTP_HOST", "{host}", false),
+            ("SMTP_PORT", "{port}", false),
+            ("SMTP_USER", "{user}", false),
+            ("SMTP_PASSWORD", "{password}", false),
+            ("SMTP_FROM", "{from}", false),
+        ],
+    },
+    SnippetDef {
+        id: "aws",
+        name: "AWS credentials",
+        params: &[
+            ("access_key_id", ""),
+            ("secret_access_key", ""),
+            ("region", "us-east-1"),
+        ],
+        lines: &[
+            ("AWS_ACCESS_KEY_ID", "{access_key_id}", false),
+            ("AWS_SECRET_ACCESS_KEY", "{secret_access_key}", false),
+            ("AWS_REGION", "{region}", false),
+        ],
+    },
+];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnippetParam {
+    name: String,
+    default_value: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snippet {
+    id: String,
+    name: String,
+    params: Vec<SnippetParam>,
+    keys: Vec<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnippetInsert {
+    path: String,
+    added: Vec<String>,
+    /// Keys the file already defines; their values are left alone.
+    skipped: Vec<String>,
+}
+
+fn placeholder_regex() -> Option<&'static Regex> {
+    static PLACEHOLDER_REGEX: OnceLock<Option<Regex>> = OnceLock::new();
+    PLACEHOLDER_REGEX
+        .get_or_init(|| Regex::new(r"\{([a-z_]+)\}").ok())
+        .as_ref()
+}
+
+fn render(template: &str, params: &BTreeMap<&str, String>, url_encoded: bool) -> String {
+    let Some(regex) = placeholder_regex() else {
+        return template.to_string();
+    };
+    regex
+        .replace_all(template, |caps: &Captures| {
+            let param = params.get(&caps[1]).map(String::as_str).unwrap_or("");
+            if url_encoded {
+                urlencoding::encode(param).into_owned()
+            } else {
+                param.to_string()
+            }
+        })
+        .into_owned()
+}
+
+#[tauri::command]
+pub async fn list_snippets() -> Vec<Snippet> {
+    SNIPPETS
+        .iter()
+        .map(|snippet| Snippet {
+            id: snippet.id.to_string(),
+            name: snippet.name.to_string(),
+            params: snippet
+                .params
+                .iter()
+                .map(|(name, default_value)| SnippetParam {
+                    name: name.to_string(),
+                    default_value: default_value.to_string(),
+                })
+                .collect(),
+            keys: snippet
+                .lines
+                .iter()
+                .map(|(key, _, _)| key.to_string())
+                .collect(),
+        })
+        .collect()
+}
+
+/// Appends a built-in block such as a Postgres URL under a comment naming it. Missing
+/// parameters use their defaults; keys the file already has are skipped.
+#[tauri::command]
+pub async fn insert_snippet(
+    app: AppHandle,
+    path: String,
+    snippet_id: String,
+    params: Option<BTreeMap<String, String>>,
+    options: WriteOptions,
+) -> Result<SnippetInsert, AppError> {
+    run_blocking(app, move |_, state| {
+        let snippet = SNIPPETS
+            .iter()
+            .find(|snippet| snippet.id == snippet_id)
+            .ok_or_else(|| AppError::InvalidInput(format!("Unknown snippet: {}", snippet_id)))?;
+        let mut values: BTreeMap<&str, String> = snippet
+            .params
+            .iter()
+            .map(|(name, default_value)| (*name, default_value.to_string()))
+            .collect();
+        for (name, value) in params.unwrap_or_default() {
+            match values.get_mut(name.as_str()) {
+                Some(slot) => *slot = value,
+                None => {
+                    return Err(AppError::InvalidInput(format!(
+                        "{} has no parameter named {}",
+                        snippet.name, name
+                    )))
+                }
+            }
+        }
+
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        let (mut lines, layout) = parse_env_text(&read_env_text(&state, &path_buf)?);
+        let existing: HashSet<String> = lines
+            .iter()
+            .filter_map(|line| match line {
+                EnvLine::Kv { key, .. } => Some(key.clone()),
+                _ => None,
+            })
+            .collect();
+
+        let mut block = Vec::new();
+        let mut result = SnippetInsert {
+            path,
+            added: Vec::new(),
+            skipped: Vec::new(),
+        };
+        for (key, template, url_encoded) in snippet.lines {
+            if existing.contains(*key) {
+                result.skipped.push(key.to_string());
+                continue;
+            }
+            let value = render(template, &values, *url_encoded);
+            block.push(new_kv_line(key, encode_literal(&value)));
+            result.added.push(key.to_string());
+        }
+        if block.is_empty() {
+            return Ok(result);
+        }
+
+        if !matches!(lines.last(), None | Some(EnvLine::Blank { .. })) {
+            lines.push(EnvLine::Blank {
+                position: LinePosition::default(),
+            });
+        }
+        lines.push(EnvLine::Comment {
+            raw: format!("# {}", snippet.name),
+            position: LinePosition::default(),
+        });
+        lines.extend(block);
+
+        let content = serialize_for_write(&state, &path_buf, &lines, layout, &options)?;
+        write_contents(&state, &path_buf, &content, &options)?;
+        Ok(result)
+    })
+    .await
+}
@@ -0,0 +1,127 @@
+use crate::ProjectGroup;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// How scanned groups are ordered. Files inside a group are always sorted by name.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GroupSort {
+    #[default]
+    Name,
+    /// Most recently modified file first.
+    Modified,
+    /// Groups with the most env files first.
+    FileCount,
+}
+
+/// Splits `text` into runs of digits and runs of everything else.
+fn chunks(text: &str) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut digits = None;
+    for (index, ch) in text.char_indices() {
+        let is_digit = ch.is_ascii_digit();
+        if digits.is_some_and(|previous| previous != is_digit) {
+            chunks.push(&text[start..index]);
+            start = index;
+        }
+        digits = Some(is_digit);
+    }
+    if start < text.len() {
+        chunks.push(&text[start..]);
+    }
+    chunks
+}
+
+fn compare_numbers(a: &str, b: &str) -> Ordering {
+    let a = a.trim_start_matches('0');
+    let b = b.trim_start_matches('0');
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+fn compare_text(a: &str, b: &str) -> Ordering {
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .cmp(b.chars().flat_map(char::to_lowercase))
+}
+
+/// Case-insensitive order that compares digit runs by value, so `.env.2` sorts before
+/// `.env.10`. Names equal under that order fall back to a plain comparison to stay
+/// stable.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let left = chunks(a);
+    let right = chunks(b);
+    for (x, y) in left.iter().zip(right.iter()) {
+        let numeric = x.starts_with(|ch: char| ch.is_ascii_digit())
+            && y.starts_with(|ch: char| ch.is_ascii_digit());
+        let order = if numeric {
+            compare_numbers(x, y)
+        } else {
+            compare_text(x, y)
+        };
+        if order != Ordering::Equal {
+            return order;
+        }
+    }
+    left.len().cmp(&right.len()).then_with(|| a.cmp(b))
+}
+
+fn last_modified(group: &ProjectGroup) -> i64 {
+    group
+        .env_files
+        .iter()
+        .map(|file| file.modified_at)
+        .max()
+        .unwrap_or(0)
+}
+
+pub fn sort_groups(groups: &mut [ProjectGroup], sort: GroupSort) {
+    groups.sort_by(|a, b| {
+        let primary = match sort {
+            GroupSort::Name => Ordering::Equal,
+            GroupSort::Modified => last_modified(b).cmp(&last_modified(a)),
+            GroupSort::FileCount => b.env_files.len().cmp(&a.env_files.len()),
+        };
+        primary
+            .then_with(|| natural_cmp(&a.name, &b.name))
+            .then_with(|| natural_cmp(&a.root_path, &b.root_path))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(names: &[&'static str]) -> Vec<&'static str> {
+        let mut names = names.to_vec();
+        names.sort_by(|a, b| natural_cmp(a, b));
+        names
+    }
+
+    #[test]
+    fn compares_digit_runs_by_value() {
+        assert_eq!(
+            sorted(&[".env.10", ".env.2", ".env.1", ".env"]),
+            [".env", ".env.1", ".env.2", ".env.10"]
+        );
+        assert_eq!(
+            sorted(&["app-020", "app-3", "app-0003"]),
+            ["app-0003", "app-3", "app-020"]
+        );
+    }
+
+    #[test]
+    fn ignores_case_but_stays_total() {
+        assert_eq!(
+            sorted(&["beta", "Alpha", "alpha"]),
+            ["Alpha", "alpha", "beta"]
+        );
+        assert_eq!(natural_cmp("Api", "api"), "Api".cmp("api"));
+    }
+
+    #[test]
+    fn splits_text_and_digit_runs() {
+        assert_eq!(chunks("v12.3rc"), ["v", "12", ".", "3", "rc"]);
+        assert!(chunks("").is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+use crate::direnv::shell_quote;
+use crate::interop::{read_values, ExportOutput};
+use crate::io_error::IoCode;
+use crate::ipc_path::decode_path;
+use crate::lint::looks_like_secret_key;
+use crate::{run_blocking, AppError, AppState};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::AppHandle;
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SsmExportFormat {
+    /// One `--cli-input-json` document per parameter.
+    #[default]
+    Json,
+    /// `aws ssm put-parameter` command lines.
+    Cli,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SsmExportOptions {
+    /// Hierarchy prefix such as `/myapp/prod`; parameters become `<prefix>/<KEY>`.
+    prefix: String,
+    format: SsmExportFormat,
+    /// Keys stored as SecureString in addition to the ones that look like secrets.
+    secure_keys: Vec<String>,
+    overwrite: bool,
+}
+
+fn parameter_name(prefix: &str, key: &str) -> String {
+    let prefix = prefix.trim().trim_end_matches('/');
+    if prefix.is_empty() {
+        format!("/{}", key)
+    } else if prefix.starts_with('/') {
+        format!("{}/{}", prefix, key)
+    } else {
+        format!("/{}/{}", prefix, key)
+    }
+}
+
+/// Renders an env file as SSM `put-parameter` input. Keys that look like secrets
+/// (or are listed in `secure_keys`) are marked `SecureString`. Empty values are
+/// skipped because SSM rejects them.
+#[tauri::command]
+pub async fn export_ssm_parameters(
+    app: AppHandle,
+    path: String,
+    options: Option<SsmExportOptions>,
+    keys: Option<Vec<String>>,
+) -> Result<ExportOutput, AppError> {
+    run_blocking(app, move |_, state| {
+        let options = options.unwrap_or_default();
+        let values = read_values(&state, &decode_path(&path), keys.as_deref())?;
+
+        let parameters: Vec<(String, &str, &str)> = values
+            .iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(key, value)| {
+                let secure = looks_like_secret_key(key) || options.secure_keys.contains(key);
+                let kind = if secure { "SecureString" } else { "String" };
+                (parameter_name(&options.prefix, key), value.as_str(), kind)
+            })
+            .collect();
+
+        let content = match options.format {
+            SsmExportFormat::Json => {
+                let inputs: Vec<serde_json::Value> = parameters
+                    .iter()
+                    .map(|(name, value, kind)| {
+                        json!({
+                            "Name": name,
+                            "Value": value,
+                            "Type": kind,
+                            "Overwrite": options.overwrite,
+                        })
+                    })
+                    .collect();
+                serde_json::to_string_pretty(&inputs)
+                    .map_err(|e| AppError::io(IoCode::InvalidData, e.to_string()))?
+            }
+            SsmExportFormat::Cli => parameters
+                .iter()
+                .map(|(name, value, kind)| {
+                    let overwrite = if options.overwrite {
+                        " --overwrite"
+                    } else {
+                        ""
+                    };
+                    format!(
+                        "aws ssm put-parameter --name {} --value {} --type {}{}\n",
+                        shell_quote(name),
+                        shell_quote(value),
+                        kind,
+                        overwrite
+                    )
+                })
+                .collect(),
+        };
+
+        Ok(ExportOutput::new(content, parameters.len()))
+    })
+    .await
+}
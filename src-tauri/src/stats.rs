@@ -0,0 +1,115 @@
+use crate::ipc_path::encode_path;
+use crate::{
+    app_lock, modified_millis, parse_env_lines, read_env_text, run_blocking, AppError, AppState,
+    EnvLine,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+const TOP_FILES: usize = 5;
+const TOP_KEYS: usize = 10;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileStat {
+    path: String,
+    size: u64,
+    modified_at: i64,
+    key_count: usize,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyFrequency {
+    key: String,
+    files: usize,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceStats {
+    total_files: usize,
+    total_keys: usize,
+    total_projects: usize,
+    /// Allowed files that were deleted or became unreadable since the scan.
+    missing_files: usize,
+    largest_files: Vec<FileStat>,
+    recent_files: Vec<FileStat>,
+    common_keys: Vec<KeyFrequency>,
+}
+
+#[tauri::command]
+pub async fn workspace_stats(app: AppHandle) -> Result<WorkspaceStats, AppError> {
+    run_blocking(app, move |_, state| {
+        app_lock::ensure_unlocked(&state)?;
+        let paths: Vec<PathBuf> = state.allowed_files.read().iter().cloned().collect();
+
+        let mut file_stats = Vec::with_capacity(paths.len());
+        let mut key_files: BTreeMap<String, usize> = BTreeMap::new();
+        let mut projects = HashSet::new();
+        let mut total_keys = 0;
+        let mut missing_files = 0;
+
+        for path in &paths {
+            let Ok(metadata) = fs::metadata(path) else {
+                missing_files += 1;
+                continue;
+            };
+            // Unreadable or binary files still count towards size/recency, just without keys.
+            let keys: BTreeSet<String> = read_env_text(&state, path)
+                .map(|contents| {
+                    parse_env_lines(&contents)
+                        .into_iter()
+                        .filter_map(|line| match line {
+                            EnvLine::Kv { key, .. } => Some(key),
+                            _ => None,
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            total_keys += keys.len();
+            for key in &keys {
+                *key_files.entry(key.clone()).or_default() += 1;
+            }
+            if let Some(parent) = path.parent() {
+                projects.insert(parent.to_path_buf());
+            }
+            file_stats.push(FileStat {
+                path: encode_path(path),
+                size: metadata.len(),
+                modified_at: modified_millis(&metadata),
+                key_count: keys.len(),
+            });
+        }
+
+        let mut largest_files = file_stats.clone();
+        largest_files.sort_by(|a, b| b.size.cmp(&a.size));
+        largest_files.truncate(TOP_FILES);
+
+        let mut recent_files = file_stats;
+        recent_files.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+        recent_files.truncate(TOP_FILES);
+
+        let mut common_keys: Vec<KeyFrequency> = key_files
+            .into_iter()
+            .map(|(key, files)| KeyFrequency { key, files })
+            .collect();
+        common_keys.sort_by(|a, b| b.files.cmp(&a.files).then_with(|| a.key.cmp(&b.key)));
+        common_keys.truncate(TOP_KEYS);
+
+        Ok(WorkspaceStats {
+            total_files: paths.len() - missing_files,
+            total_keys,
+            total_projects: projects.len(),
+            missing_files,
+            largest_files,
+            recent_files,
+            common_keys,
+        })
+    })
+    .await
+}
@@ -0,0 +1,56 @@
+use crate::io_error::IoCode;
+use crate::{AppError, AppState};
+use serde::{de::DeserializeOwned, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+pub fn app_data_dir(app: &AppHandle) -> Result<PathBuf, AppError> {
+    let dir = app
+        .path_resolver()
+        .app_data_dir()
+        .ok_or_else(|| AppError::io(IoCode::Unavailable, "App data directory is unavailable"))?;
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Loads a JSON document from the app data directory, falling back to the default
+/// value when it does not exist yet.
+pub fn load<T: DeserializeOwned + Default>(app: &AppHandle, name: &str) -> Result<T, AppError> {
+    let path = app_data_dir(app)?.join(name);
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let contents = fs::read_to_string(&path)?;
+    serde_json::from_str(&contents).map_err(|e| AppError::io(IoCode::InvalidData, e.to_string()))
+}
+
+pub fn save<T: Serialize>(app: &AppHandle, name: &str, value: &T) -> Result<(), AppError> {
+    let dir = app_data_dir(app)?;
+    let contents = serde_json::to_string_pretty(value)
+        .map_err(|e| AppError::io(IoCode::InvalidData, e.to_string()))?;
+    let temp_path = dir.join(format!(".{}.tmp", name));
+    let mut file = fs::File::create(&temp_path)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&temp_path, dir.join(name))?;
+    Ok(())
+}
+
+/// Read-modify-write of a stored document, serialized through the app state lock.
+pub fn update<T, R>(
+    app: &AppHandle,
+    state: &AppState,
+    name: &str,
+    change: impl FnOnce(&mut T) -> R,
+) -> Result<R, AppError>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
+    let _guard = state.store_lock.write();
+    let mut value = load::<T>(app, name)?;
+    let result = change(&mut value);
+    save(app, name, &value)?;
+    Ok(result)
+}
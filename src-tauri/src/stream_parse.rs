@@ -0,0 +1,121 @@
+use crate::io_error::PathContext;
+use crate::{
+    attach_descriptions, kv_line_regex, parse_line, AppError, EnvLine, LineEnding, LinePosition,
+    TextLayout, BINARY_SNIFF_BYTES,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+pub const PARSE_PROGRESS_EVENT: &str = "parse-progress";
+/// Progress is reported about once per this many bytes.
+const PROGRESS_STEP: u64 = 1024 * 1024;
+const READ_BUFFER_BYTES: usize = 64 * 1024;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseProgress {
+    pub path: String,
+    pub bytes_read: u64,
+    pub total_bytes: u64,
+}
+
+pub struct ParsedFile {
+    pub lines: Vec<EnvLine>,
+    pub layout: TextLayout,
+    pub content_hash: String,
+    pub size: u64,
+}
+
+/// Reads and parses a file one line at a time, so a tens-of-MB file never sits in
+/// memory as one string next to its parsed lines. `limit` is a hard cap on bytes read,
+/// which also holds when the file grows after its size was checked. Gives the same
+/// result as `parse_env_text` on the whole file.
+pub fn parse_file(
+    path: &Path,
+    limit: u64,
+    mut progress: impl FnMut(u64),
+) -> Result<ParsedFile, AppError> {
+    let file = File::open(path).at(path)?;
+    let size = file.metadata().at(path)?.len();
+    if size > limit {
+        return Err(AppError::FileTooLarge { size, limit });
+    }
+    let mut reader = BufReader::with_capacity(READ_BUFFER_BYTES, file.take(limit + 1));
+    let kv_regex = kv_line_regex();
+    let mut hasher = Sha256::new();
+    let mut lines = Vec::new();
+    let mut buffer = Vec::new();
+    let mut bytes_read: u64 = 0;
+    let mut next_progress = PROGRESS_STEP;
+    let mut crlf = false;
+    let mut trailing_newline = false;
+    let mut first_line_empty = false;
+
+    loop {
+        buffer.clear();
+        let read = reader.read_until(b'\n', &mut buffer).at(path)?;
+        if read == 0 {
+            break;
+        }
+        let sniffed = bytes_read as usize;
+        if sniffed < BINARY_SNIFF_BYTES {
+            let sniff_len = read.min(BINARY_SNIFF_BYTES - sniffed);
+            if buffer[..sniff_len].contains(&0) {
+                return Err(AppError::NotText);
+            }
+        }
+        bytes_read += read as u64;
+        if bytes_read > limit {
+            return Err(AppError::FileTooLarge {
+                size: size.max(bytes_read),
+                limit,
+            });
+        }
+        hasher.update(&buffer);
+
+        trailing_newline = buffer.last() == Some(&b'\n');
+        if trailing_newline {
+            buffer.pop();
+            if buffer.last() == Some(&b'\r') {
+                buffer.pop();
+                crlf = true;
+            }
+        }
+        let line = std::str::from_utf8(&buffer).map_err(|_| AppError::NotText)?;
+        if lines.is_empty() {
+            first_line_empty = line.is_empty();
+        }
+        lines.push(parse_line(line, lines.len() + 1, &kv_regex));
+
+        if bytes_read >= next_progress {
+            progress(bytes_read);
+            next_progress = bytes_read + PROGRESS_STEP;
+        }
+    }
+
+    // Match `parse_env_text`: an empty file is one blank line, a lone newline is none.
+    if lines.is_empty() {
+        lines.push(EnvLine::Blank {
+            position: LinePosition::line(1),
+        });
+    } else if trailing_newline && lines.len() == 1 && first_line_empty {
+        lines.clear();
+    }
+    attach_descriptions(&mut lines);
+    Ok(ParsedFile {
+        lines,
+        layout: TextLayout {
+            trailing_newline,
+            line_ending: if crlf {
+                LineEnding::Crlf
+            } else {
+                LineEnding::Lf
+            },
+        },
+        content_hash: format!("{:x}", hasher.finalize()),
+        size: bytes_read,
+    })
+}
@@ -0,0 +1,214 @@
+use crate::interop::{read_values, ExportOutput};
+use crate::interpolation::extract_references;
+use crate::ipc_path::decode_path;
+use crate::lint::{Diagnostic, Severity};
+use crate::{
+    ensure_allowed_path, parse_env_lines, read_env_text, run_blocking, unquote, AppError, AppState,
+    EnvLine,
+};
+use tauri::AppHandle;
+
+/// systemd accepts letters, digits and underscores, not starting with a digit.
+fn is_valid_name(key: &str) -> bool {
+    !key.is_empty()
+        && !key.starts_with(|c: char| c.is_ascii_digit())
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Values systemd reads back unchanged without quotes.
+fn is_bare(value: &str) -> bool {
+    value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "_-./:@,+=%".contains(c))
+}
+
+/// Double-quotes a value the way systemd's EnvironmentFile parser undoes it. Newlines
+/// inside quotes are kept as is.
+fn systemd_value(value: &str) -> String {
+    if is_bare(value) {
+        return value.to_string();
+    }
+    let mut output = String::from("\"");
+    for ch in value.chars() {
+        if matches!(ch, '"' | '\\' | '`' | '$') {
+            output.push('\\');
+        }
+        output.push(ch);
+    }
+    output.push('"');
+    output
+}
+
+fn finding(
+    rule: &str,
+    severity: Severity,
+    path: &str,
+    line: usize,
+    key: &str,
+    message: String,
+) -> Diagnostic {
+    Diagnostic {
+        rule: rule.to_string(),
+        severity,
+        message,
+        path: path.to_string(),
+        line: Some(line),
+        key: Some(key.to_string()),
+        suggestion: None,
+    }
+}
+
+/// Flags what systemd would reject or read differently from dotenv: invalid names,
+/// `export`, and `${VAR}` or `$(cmd)`, which systemd leaves as literal text.
+fn systemd_findings(path: &str, lines: &[EnvLine]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let line_number = index + 1;
+        let EnvLine::Kv {
+            key,
+            value,
+            has_export,
+            dangerous,
+            ..
+        } = line
+        else {
+            continue;
+        };
+        if !is_valid_name(key) {
+            diagnostics.push(finding(
+                "systemd-invalid-name",
+                Severity::Error,
+                path,
+                line_number,
+                key,
+                format!("{} is not a valid systemd variable name", key),
+            ));
+        }
+        if *has_export {
+            diagnostics.push(finding(
+                "systemd-export",
+                Severity::Error,
+                path,
+                line_number,
+                key,
+                format!("systemd does not accept `export` before {}", key),
+            ));
+        }
+        if *dangerous {
+            diagnostics.push(finding(
+                "systemd-command-substitution",
+                Severity::Warning,
+                path,
+                line_number,
+                key,
+                format!(
+                    "{} uses command substitution, which systemd does not run",
+                    key
+                ),
+            ));
+        } else if !extract_references(value).is_empty() {
+            diagnostics.push(finding(
+                "systemd-interpolation",
+                Severity::Warning,
+                path,
+                line_number,
+                key,
+                format!(
+                    "{} references other variables, which systemd does not expand",
+                    key
+                ),
+            ));
+        }
+        if unquote(value).contains('\n') {
+            diagnostics.push(finding(
+                "systemd-multiline",
+                Severity::Info,
+                path,
+                line_number,
+                key,
+                format!(
+                    "{} spans several lines; older systemd versions cut it at the first",
+                    key
+                ),
+            ));
+        }
+    }
+    diagnostics
+}
+
+/// Converts an env file to a systemd `EnvironmentFile=`: no `export`, systemd quoting
+/// and values written literally. Keys systemd would reject are left out.
+#[tauri::command]
+pub async fn export_systemd_env(
+    app: AppHandle,
+    path: String,
+    keys: Option<Vec<String>>,
+) -> Result<ExportOutput, AppError> {
+    run_blocking(app, move |_, state| {
+        let values = read_values(&state, &decode_path(&path), keys.as_deref())?;
+        let lines: Vec<String> = values
+            .iter()
+            .filter(|(key, _)| is_valid_name(key))
+            .map(|(key, value)| format!("{}={}\n", key, systemd_value(value)))
+            .collect();
+        Ok(ExportOutput::new(lines.concat(), lines.len()))
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn validate_systemd_env(
+    app: AppHandle,
+    path: String,
+) -> Result<Vec<Diagnostic>, AppError> {
+    run_blocking(app, move |_, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        let lines = parse_env_lines(&read_env_text(&state, &path_buf)?);
+        Ok(systemd_findings(&path, &lines))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(text: &str) -> Vec<(String, Option<usize>)> {
+        systemd_findings(".env", &parse_env_lines(text))
+            .into_iter()
+            .map(|diagnostic| (diagnostic.rule, diagnostic.line))
+            .collect()
+    }
+
+    #[test]
+    fn flags_what_systemd_reads_differently() {
+        let text = "PLAIN=value\nexport HOST=db\nURL=http://${HOST}\nNOW=$(date)";
+        assert_eq!(
+            rules(text),
+            [
+                ("systemd-export".to_string(), Some(2)),
+                ("systemd-interpolation".to_string(), Some(3)),
+                ("systemd-command-substitution".to_string(), Some(4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn validates_names() {
+        assert!(is_valid_name("_DB_HOST2"));
+        assert!(!is_valid_name("2FA_KEY"));
+        assert!(!is_valid_name("db.host"));
+        assert!(!is_valid_name(""));
+    }
+
+    #[test]
+    fn quotes_only_values_that_need_it() {
+        assert_eq!(systemd_value("user@host:5432/db"), "user@host:5432/db");
+        assert_eq!(systemd_value("a b"), "\"a b\"");
+        assert_eq!(
+            systemd_value("say \"$HOME\" `x` \\"),
+            "\"say \\\"\\$HOME\\\" \\`x\\` \\\\\""
+        );
+    }
+}
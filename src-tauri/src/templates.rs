@@ -0,0 +1,221 @@
+use crate::bundle::merge_contents;
+use crate::ipc_path::{decode_path, encode_path};
+use crate::{
+    access, app_lock, create_new_file, ensure_allowed_path, infer_value_type, logging,
+    normalize_path, parse_env_text, path_starts_with, provenance, read_env_text, run_blocking,
+    serialize_with_layout, store, write_contents, AppError, AppState, EnvLine, WriteOptions,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use tauri::AppHandle;
+
+const TEMPLATES_FILE: &str = "templates.json";
+const DEFAULT_FILE_NAME: &str = ".env";
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvTemplate {
+    id: String,
+    name: String,
+    description: Option<String>,
+    /// Env file text with every value blanked except the kept ones.
+    content: String,
+    keys: Vec<String>,
+    created_at: i64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateInstance {
+    path: String,
+    created: bool,
+    /// Keys the template added; existing keys are never changed.
+    added: Vec<String>,
+    written: bool,
+}
+
+/// Blanks every value not listed in `keep_values`, keeping keys, comments and layout.
+fn redacted_content(contents: &str, keep_values: &[String]) -> (String, Vec<String>) {
+    let (mut lines, layout) = parse_env_text(contents);
+    let mut keys = Vec::new();
+    for line in &mut lines {
+        let EnvLine::Kv {
+            key,
+            value,
+            value_type,
+            dangerous,
+            raw,
+            ..
+        } = line
+        else {
+            continue;
+        };
+        if !keys.contains(key) {
+            keys.push(key.clone());
+        }
+        if keep_values.contains(key) {
+            continue;
+        }
+        value.clear();
+        *value_type = infer_value_type(value);
+        *dangerous = false;
+        *raw = None;
+    }
+    (serialize_with_layout(&lines, layout), keys)
+}
+
+/// A folder inside the scanned root that new files may be created in.
+fn project_folder(state: &AppState, folder: &Path) -> Result<PathBuf, AppError> {
+    app_lock::ensure_unlocked(state)?;
+    access::ensure_active(state)?;
+    let folder = normalize_path(folder)?;
+    let root = state
+        .root_path
+        .read()
+        .clone()
+        .ok_or(AppError::InvalidRootPath)?;
+    if !folder.is_dir() || !path_starts_with(&folder, &root) {
+        return Err(AppError::PathNotAllowed);
+    }
+    Ok(folder)
+}
+
+fn is_plain_file_name(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+    matches!(components.next(), Some(Component::Normal(_))) && components.next().is_none()
+}
+
+#[tauri::command]
+pub async fn list_templates(app: AppHandle) -> Result<Vec<EnvTemplate>, AppError> {
+    run_blocking(app, move |app, _| store::load(&app, TEMPLATES_FILE)).await
+}
+
+/// Saves a file as a reusable template with its values removed; `keep_values` names
+/// harmless defaults such as ports worth keeping. A template with the same name is
+/// replaced.
+#[tauri::command]
+pub async fn save_template(
+    app: AppHandle,
+    path: String,
+    name: String,
+    description: Option<String>,
+    keep_values: Option<Vec<String>>,
+) -> Result<EnvTemplate, AppError> {
+    run_blocking(app, move |app, state| {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Template name is required".to_string(),
+            ));
+        }
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        let (content, keys) = redacted_content(
+            &read_env_text(&state, &path_buf)?,
+            &keep_values.unwrap_or_default(),
+        );
+        let template = EnvTemplate {
+            id: format!("{:016x}", rand::random::<u64>()),
+            name,
+            description: description.filter(|text| !text.trim().is_empty()),
+            content,
+            keys,
+            created_at: Utc::now().timestamp_millis(),
+        };
+
+        store::update(
+            &app,
+            &state,
+            TEMPLATES_FILE,
+            |templates: &mut Vec<EnvTemplate>| {
+                templates.retain(|existing| existing.name != template.name);
+                templates.push(template.clone());
+            },
+        )?;
+        Ok(template)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn delete_template(app: AppHandle, template_id: String) -> Result<bool, AppError> {
+    run_blocking(app, move |app, state| {
+        store::update(
+            &app,
+            &state,
+            TEMPLATES_FILE,
+            |templates: &mut Vec<EnvTemplate>| {
+                let before = templates.len();
+                templates.retain(|template| template.id != template_id);
+                templates.len() != before
+            },
+        )
+    })
+    .await
+}
+
+/// Writes a template into a project folder as `file_name` (`.env` by default). An
+/// existing file only gains the keys it is missing, and needs `options` to be written.
+#[tauri::command]
+pub async fn instantiate_template(
+    app: AppHandle,
+    template_id: String,
+    folder_path: String,
+    file_name: Option<String>,
+    options: Option<WriteOptions>,
+) -> Result<TemplateInstance, AppError> {
+    run_blocking(app, move |app, state| {
+        let templates: Vec<EnvTemplate> = store::load(&app, TEMPLATES_FILE)?;
+        let template = templates
+            .into_iter()
+            .find(|template| template.id == template_id)
+            .ok_or_else(|| AppError::InvalidInput("Template not found".to_string()))?;
+        let file_name = file_name
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| DEFAULT_FILE_NAME.to_string());
+        if !is_plain_file_name(&file_name) {
+            return Err(AppError::InvalidInput(format!(
+                "{} is not a file name",
+                file_name
+            )));
+        }
+        let target = project_folder(&state, &decode_path(&folder_path))?.join(&file_name);
+
+        if !target.exists() {
+            let mut file = create_new_file(&target)?;
+            file.write_all(template.content.as_bytes())?;
+            file.sync_all()?;
+            let target = normalize_path(&target)?;
+            if let Err(error) = provenance::record(&target, template.content.as_bytes()) {
+                tracing::warn!("failed to record write: {}", logging::redacted(&error));
+            }
+            state.allowed_files.write().insert(target.clone());
+            return Ok(TemplateInstance {
+                path: encode_path(&target),
+                created: true,
+                added: template.keys,
+                written: true,
+            });
+        }
+
+        ensure_allowed_path(&state, &target)?;
+        let (merged, added) = merge_contents(&read_env_text(&state, &target)?, &template.content);
+        let written = match options {
+            Some(options) if !added.is_empty() => {
+                write_contents(&state, &target, &merged, &options)?;
+                true
+            }
+            _ => false,
+        };
+        Ok(TemplateInstance {
+            path: encode_path(&target),
+            created: false,
+            added,
+            written,
+        })
+    })
+    .await
+}
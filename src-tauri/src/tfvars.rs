@@ -0,0 +1,190 @@
+use crate::interop::{read_values, ExportOutput};
+use crate::ipc_path::decode_path;
+use crate::{infer_value_type, run_blocking, AppError, AppState, ValueType};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::AppHandle;
+
+/// Terraform reads `TF_VAR_name` from the environment; the prefix is dropped so the
+/// variable name matches the `variable "name"` block.
+const TF_VAR_PREFIX: &str = "TF_VAR_";
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TfvarsOptions {
+    /// Lowercases variable names (`DB_HOST` → `db_host`), the usual Terraform style.
+    lowercase: bool,
+    /// Suggests `<name>.auto.tfvars`, which Terraform loads without `-var-file`.
+    auto: bool,
+}
+
+impl Default for TfvarsOptions {
+    fn default() -> Self {
+        TfvarsOptions {
+            lowercase: true,
+            auto: false,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TfvarsExport {
+    #[serde(flatten)]
+    output: ExportOutput,
+    file_name: String,
+}
+
+fn variable_name(key: &str, lowercase: bool) -> String {
+    let name = key.strip_prefix(TF_VAR_PREFIX).unwrap_or(key);
+    if lowercase {
+        name.to_ascii_lowercase()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Quotes a string for HCL, escaping template sequences so `${` stays literal.
+fn hcl_string(value: &str) -> String {
+    let mut output = String::from("\"");
+    let mut chars = value.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => output.push_str("\\\\"),
+            '"' => output.push_str("\\\""),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '$' | '%' if chars.peek() == Some(&'{') => {
+                output.push(ch);
+                output.push(ch);
+            }
+            _ => output.push(ch),
+        }
+    }
+    output.push('"');
+    output
+}
+
+fn json_to_hcl(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(text) => hcl_string(text),
+        serde_json::Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(json_to_hcl).collect();
+            format!("[{}]", items.join(", "))
+        }
+        serde_json::Value::Object(map) => {
+            let fields: Vec<String> = map
+                .iter()
+                .map(|(key, value)| format!("{} = {}", hcl_string(key), json_to_hcl(value)))
+                .collect();
+            format!("{{ {} }}", fields.join(", "))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Numbers HCL reads back unchanged; `007` or `+5` stay strings so nothing is lost.
+fn is_plain_number(value: &str) -> bool {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    let leading_zero = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+    !leading_zero
+        && digits.starts_with(|c: char| c.is_ascii_digit())
+        && digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '-' | '+'))
+}
+
+/// Renders a value as an HCL expression using the inferred env value type.
+fn hcl_value(value: &str) -> String {
+    match infer_value_type(value) {
+        ValueType::Boolean => {
+            let truthy = matches!(value.to_ascii_lowercase().as_str(), "true" | "yes" | "on");
+            truthy.to_string()
+        }
+        ValueType::Integer | ValueType::Float if is_plain_number(value) => value.to_string(),
+        ValueType::Json => serde_json::from_str::<serde_json::Value>(value)
+            .map(|json| json_to_hcl(&json))
+            .unwrap_or_else(|_| hcl_string(value)),
+        _ => hcl_string(value),
+    }
+}
+
+/// Converts an env file to `terraform.tfvars` syntax. Booleans, numbers and JSON
+/// values become native HCL types; everything else is a quoted string.
+#[tauri::command]
+pub async fn export_tfvars(
+    app: AppHandle,
+    path: String,
+    options: Option<TfvarsOptions>,
+    keys: Option<Vec<String>>,
+) -> Result<TfvarsExport, AppError> {
+    run_blocking(app, move |_, state| {
+        let options = options.unwrap_or_default();
+        let path_buf = &decode_path(&path);
+        let values = read_values(&state, path_buf, keys.as_deref())?;
+
+        let lines: Vec<(String, String)> = values
+            .iter()
+            .map(|(key, value)| (variable_name(key, options.lowercase), hcl_value(value)))
+            .collect();
+        let width = lines.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+        let content: String = lines
+            .iter()
+            .map(|(name, value)| format!("{:width$} = {}\n", name, value, width = width))
+            .collect();
+
+        let file_name = if options.auto {
+            let stem = path_buf
+                .parent()
+                .and_then(Path::file_name)
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| "env".to_string());
+            format!("{}.auto.tfvars", stem)
+        } else {
+            "terraform.tfvars".to_string()
+        };
+
+        Ok(TfvarsExport {
+            output: ExportOutput::new(content, lines.len()),
+            file_name,
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_the_tf_var_prefix() {
+        assert_eq!(variable_name("TF_VAR_DB_HOST", true), "db_host");
+        assert_eq!(variable_name("DB_HOST", false), "DB_HOST");
+    }
+
+    #[test]
+    fn keeps_native_types() {
+        assert_eq!(hcl_value("Yes"), "true");
+        assert_eq!(hcl_value("off"), "false");
+        assert_eq!(hcl_value("8080"), "8080");
+        assert_eq!(hcl_value("-1.5e3"), "-1.5e3");
+        assert_eq!(
+            hcl_value(r#"{"ports":[80,"443"],"tls":true}"#),
+            r#"{ "ports" = [80, "443"], "tls" = true }"#
+        );
+    }
+
+    #[test]
+    fn numbers_that_would_change_stay_strings() {
+        assert_eq!(hcl_value("007"), "\"007\"");
+        assert_eq!(hcl_value("+5"), "\"+5\"");
+    }
+
+    #[test]
+    fn escapes_strings_and_templates() {
+        assert_eq!(hcl_value("a \"b\"\n"), r#""a \"b\"\n""#);
+        assert_eq!(hcl_value("${HOME} %{if} $5"), r#""$${HOME} %%{if} $5""#);
+        assert_eq!(hcl_value(r"C:\dir"), r#""C:\\dir""#);
+    }
+}
@@ -0,0 +1,146 @@
+use crate::ipc_path::{decode_path, encode_path};
+use crate::{launch, store, AppError, AppState};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::{
+    AppHandle, CustomMenuItem, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu,
+    SystemTrayMenuItem, SystemTraySubmenu,
+};
+
+const RECENT_FILE: &str = "recent.json";
+const MAX_RECENT: usize = 8;
+pub const OPEN_FILE_EVENT: &str = "tray-open-file";
+
+const FILE_PREFIX: &str = "file:";
+const PROJECT_PREFIX: &str = "project:";
+const SHOW_ID: &str = "show";
+const RESCAN_ID: &str = "rescan";
+const QUIT_ID: &str = "quit";
+
+/// Most recently opened first.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct RecentItems {
+    files: Vec<String>,
+    projects: Vec<String>,
+}
+
+fn push_front(list: &mut Vec<String>, item: String) {
+    list.retain(|existing| *existing != item);
+    list.insert(0, item);
+    list.truncate(MAX_RECENT);
+}
+
+fn remember(
+    app: &AppHandle,
+    state: &AppState,
+    path: &Path,
+    pick: fn(&mut RecentItems) -> &mut Vec<String>,
+) -> Result<(), AppError> {
+    let path = encode_path(path);
+    store::update(app, state, RECENT_FILE, |recent: &mut RecentItems| {
+        push_front(pick(recent), path)
+    })?;
+    refresh(app);
+    Ok(())
+}
+
+pub fn remember_file(app: &AppHandle, state: &AppState, path: &Path) -> Result<(), AppError> {
+    remember(app, state, path, |recent| &mut recent.files)
+}
+
+pub fn remember_project(app: &AppHandle, state: &AppState, root: &Path) -> Result<(), AppError> {
+    remember(app, state, root, |recent| &mut recent.projects)
+}
+
+fn name_of(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+/// `.env.local — api`, so files with the same name in different projects stay apart.
+fn file_label(path: &str) -> String {
+    let path = decode_path(path);
+    match path.parent() {
+        Some(folder) => format!("{} — {}", name_of(&path), name_of(folder)),
+        None => name_of(&path),
+    }
+}
+
+fn recent_menu(paths: &[String], prefix: &str, label: fn(&str) -> String) -> SystemTrayMenu {
+    if paths.is_empty() {
+        return SystemTrayMenu::new()
+            .add_item(CustomMenuItem::new(format!("empty-{}", prefix), "Nothing yet").disabled());
+    }
+    paths.iter().fold(SystemTrayMenu::new(), |menu, path| {
+        menu.add_item(CustomMenuItem::new(
+            format!("{}{}", prefix, path),
+            label(path),
+        ))
+    })
+}
+
+fn menu(recent: &RecentItems) -> SystemTrayMenu {
+    SystemTrayMenu::new()
+        .add_item(CustomMenuItem::new(SHOW_ID, "Open EnvShelf"))
+        .add_item(CustomMenuItem::new(RESCAN_ID, "Rescan workspace"))
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_submenu(SystemTraySubmenu::new(
+            "Recent files",
+            recent_menu(&recent.files, FILE_PREFIX, file_label),
+        ))
+        .add_submenu(SystemTraySubmenu::new(
+            "Recent projects",
+            recent_menu(&recent.projects, PROJECT_PREFIX, |path| {
+                name_of(&decode_path(path))
+            }),
+        ))
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new(QUIT_ID, "Quit"))
+}
+
+pub fn system_tray() -> SystemTray {
+    SystemTray::new().with_menu(menu(&RecentItems::default()))
+}
+
+/// Rebuilds the tray menu from the persisted recent items.
+pub fn refresh(app: &AppHandle) {
+    if let Ok(recent) = store::load::<RecentItems>(app, RECENT_FILE) {
+        let _ = app.tray_handle().set_menu(menu(&recent));
+    }
+}
+
+fn rescan(app: &AppHandle) {
+    let root = app.state::<AppState>().root_path.read().clone();
+    match root {
+        Some(root) => launch::open(app, &encode_path(&root)),
+        None => launch::focus_main_window(app),
+    }
+}
+
+fn on_menu_item(app: &AppHandle, id: &str) {
+    if let Some(path) = id.strip_prefix(FILE_PREFIX) {
+        launch::focus_main_window(app);
+        let _ = app.emit_all(OPEN_FILE_EVENT, path.to_string());
+        return;
+    }
+    if let Some(root) = id.strip_prefix(PROJECT_PREFIX) {
+        launch::open(app, root);
+        return;
+    }
+    match id {
+        SHOW_ID => launch::focus_main_window(app),
+        RESCAN_ID => rescan(app),
+        QUIT_ID => app.exit(0),
+        _ => {}
+    }
+}
+
+pub fn on_event(app: &AppHandle, event: SystemTrayEvent) {
+    match event {
+        SystemTrayEvent::LeftClick { .. } => launch::focus_main_window(app),
+        SystemTrayEvent::MenuItemClick { id, .. } => on_menu_item(app, &id),
+        _ => {}
+    }
+}
@@ -0,0 +1,187 @@
+use crate::ipc_path::encode_path;
+use crate::{
+    app_lock, parse_env_lines, read_env_text, run_blocking, store, unquote, AppError, AppState,
+    EnvLine,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, State};
+
+const VALUE_INDEX_FILE: &str = "value-index.json";
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ValueIndexSettings {
+    /// Off by default: the index keeps every value of every scanned file in memory.
+    enabled: bool,
+}
+
+#[derive(Clone)]
+struct Location {
+    path: PathBuf,
+    key: String,
+    line: usize,
+}
+
+/// Values of the allowed files mapped to where they are assigned. Rebuilt after each
+/// scan while enabled and dropped as soon as it is disabled.
+#[derive(Default)]
+pub struct ValueIndex {
+    settings: ValueIndexSettings,
+    values: HashMap<String, Vec<Location>>,
+    file_count: usize,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueIndexStatus {
+    enabled: bool,
+    file_count: usize,
+    value_count: usize,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueUsage {
+    path: String,
+    key: String,
+    line: usize,
+    /// The whole value equals the query rather than containing it.
+    exact: bool,
+}
+
+pub fn status(index: &ValueIndex) -> ValueIndexStatus {
+    ValueIndexStatus {
+        enabled: index.settings.enabled,
+        file_count: index.file_count,
+        value_count: index.values.len(),
+    }
+}
+
+pub fn resume(app: &AppHandle) -> Result<(), AppError> {
+    let settings: ValueIndexSettings = store::load(app, VALUE_INDEX_FILE)?;
+    let state = app.state::<AppState>();
+    state.value_index.write().settings = settings;
+    Ok(())
+}
+
+/// Re-reads every allowed file into the index. Does nothing while the index is off.
+pub fn rebuild(state: &AppState) {
+    if !state.value_index.read().settings.enabled {
+        return;
+    }
+    let paths: Vec<PathBuf> = state.allowed_files.read().iter().cloned().collect();
+
+    let mut values: HashMap<String, Vec<Location>> = HashMap::new();
+    let mut file_count = 0;
+    for path in paths {
+        let Ok(text) = read_env_text(state, &path) else {
+            continue;
+        };
+        file_count += 1;
+        for (index, line) in parse_env_lines(&text).iter().enumerate() {
+            if let EnvLine::Kv { key, value, .. } = line {
+                let value = unquote(value);
+                if value.is_empty() {
+                    continue;
+                }
+                values.entry(value.to_string()).or_default().push(Location {
+                    path: path.clone(),
+                    key: key.clone(),
+                    line: index + 1,
+                });
+            }
+        }
+    }
+
+    let mut index = state.value_index.write();
+    index.values = values;
+    index.file_count = file_count;
+}
+
+/// Drops the indexed values but keeps the setting, so the next rebuild fills it again.
+pub fn clear(state: &AppState) {
+    let mut index = state.value_index.write();
+    index.values.clear();
+    index.file_count = 0;
+}
+
+#[tauri::command]
+pub async fn get_value_index_status(
+    state: State<'_, AppState>,
+) -> Result<ValueIndexStatus, AppError> {
+    Ok(status(&state.value_index.read()))
+}
+
+/// Turns the index on (building it from the current allow-list) or off (freeing it).
+#[tauri::command]
+pub async fn set_value_index_enabled(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<ValueIndexStatus, AppError> {
+    run_blocking(app, move |app, state| {
+        app_lock::ensure_unlocked(&state)?;
+        let settings = ValueIndexSettings { enabled };
+        store::save(&app, VALUE_INDEX_FILE, &settings)?;
+        *state.value_index.write() = ValueIndex {
+            settings,
+            ..ValueIndex::default()
+        };
+        rebuild(&state);
+        Ok(status(&state.value_index.read()))
+    })
+    .await
+}
+
+/// Lists where a value is assigned across the scanned files, e.g. which projects share
+/// a connection string. Substring matches are included unless `exact` is set. Files
+/// that have since left the allow-list are not reported.
+#[tauri::command]
+pub async fn find_value_usages(
+    app: AppHandle,
+    query: String,
+    exact: Option<bool>,
+) -> Result<Vec<ValueUsage>, AppError> {
+    run_blocking(app, move |_, state| {
+        app_lock::ensure_unlocked(&state)?;
+        if query.is_empty() {
+            return Err(AppError::InvalidInput("Search query is empty".to_string()));
+        }
+        let query = query.as_str();
+        let exact_only = exact.unwrap_or(false);
+        let index = state.value_index.read();
+        if !index.settings.enabled {
+            return Err(AppError::InvalidInput(
+                "The value index is turned off".to_string(),
+            ));
+        }
+        let allowed_guard = state.allowed_files.read();
+        let allowed: &HashSet<PathBuf> = &allowed_guard;
+
+        let mut usages: Vec<ValueUsage> = index
+            .values
+            .iter()
+            .filter(|(value, _)| value.as_str() == query || (!exact_only && value.contains(query)))
+            .flat_map(|(value, locations)| {
+                locations
+                    .iter()
+                    .filter(move |location| allowed.contains(&location.path))
+                    .map(move |location| ValueUsage {
+                        path: encode_path(&location.path),
+                        key: location.key.clone(),
+                        line: location.line,
+                        exact: value.as_str() == query,
+                    })
+            })
+            .collect();
+        usages.sort_by(|a, b| {
+            b.exact
+                .cmp(&a.exact)
+                .then_with(|| a.path.cmp(&b.path))
+                .then_with(|| a.line.cmp(&b.line))
+        });
+        Ok(usages)
+    })
+    .await
+}
@@ -0,0 +1,321 @@
+use crate::framework::{detect_framework, load_order, Framework};
+use crate::interop::decoded_values;
+use crate::ipc_path::{decode_path, encode_path};
+use crate::quoting::decode_value;
+use crate::{
+    app_lock, ensure_allowed_path, ensure_no_directives, files_in_group, find_kv_value,
+    parse_env_lines, parse_env_text, read_env_text, run_blocking, serialize_for_write,
+    set_kv_value, write_contents, AppError, AppState, EnvLine, WriteOptions,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RowStatus {
+    Same,
+    Different,
+    Missing,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatrixRow {
+    pub key: String,
+    pub values: Vec<Option<String>>,
+    pub status: RowStatus,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariantMatrix {
+    pub group_id: String,
+    pub files: Vec<String>,
+    pub paths: Vec<String>,
+    pub rows: Vec<MatrixRow>,
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+pub fn variant_matrix(state: &AppState, group_id: String) -> Result<VariantMatrix, AppError> {
+    let paths = files_in_group(state, &group_id)?;
+    let documents = paths
+        .iter()
+        .map(|path| read_env_text(state, path).map(|contents| parse_env_lines(&contents)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let keys: BTreeSet<&str> = documents
+        .iter()
+        .flatten()
+        .filter_map(|line| match line {
+            EnvLine::Kv { key, .. } => Some(key.as_str()),
+            _ => None,
+        })
+        .collect();
+    // Values as each file ends up with them, after `unset` and `+=` lines.
+    let resolved: Vec<BTreeMap<String, String>> = documents
+        .iter()
+        .map(|lines| decoded_values(lines).into_iter().collect())
+        .collect();
+
+    let rows = keys
+        .into_iter()
+        .map(|key| {
+            let values: Vec<Option<String>> = resolved
+                .iter()
+                .map(|values| values.get(key).cloned())
+                .collect();
+            let present: BTreeSet<&String> = values.iter().flatten().collect();
+            let status = if values.iter().any(Option::is_none) {
+                RowStatus::Missing
+            } else if present.len() > 1 {
+                RowStatus::Different
+            } else {
+                RowStatus::Same
+            };
+            MatrixRow {
+                key: key.to_string(),
+                values,
+                status,
+            }
+        })
+        .collect();
+
+    Ok(VariantMatrix {
+        group_id,
+        files: paths.iter().map(|path| file_name(path)).collect(),
+        paths: paths.iter().map(|path| encode_path(path)).collect(),
+        rows,
+    })
+}
+
+#[tauri::command]
+pub async fn compare_variants(app: AppHandle, group_id: String) -> Result<VariantMatrix, AppError> {
+    run_blocking(app, move |_, state| {
+        app_lock::ensure_unlocked(&state)?;
+        variant_matrix(&state, group_id)
+    })
+    .await
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyConflict {
+    key: String,
+    source_value: String,
+    target_value: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromoteResult {
+    added: Vec<String>,
+    updated: Vec<String>,
+    unchanged: Vec<String>,
+    conflicts: Vec<KeyConflict>,
+    missing: Vec<String>,
+}
+
+fn find_kv_line<'a>(lines: &'a [EnvLine], key: &str) -> Option<&'a EnvLine> {
+    lines
+        .iter()
+        .rev()
+        .find(|line| matches!(line, EnvLine::Kv { key: line_key, .. } if line_key == key))
+}
+
+/// Inserts `line` right after the last key-value, `unset` or `+=` line, so none of
+/// them changes it, or at the end of the file.
+pub fn insert_after_last_kv(lines: &mut Vec<EnvLine>, line: EnvLine) {
+    let position = lines
+        .iter()
+        .rposition(|line| {
+            matches!(
+                line,
+                EnvLine::Kv { .. } | EnvLine::Unset { .. } | EnvLine::Append { .. }
+            )
+        })
+        .map(|index| index + 1)
+        .unwrap_or(lines.len());
+    lines.insert(position, line);
+}
+
+/// Copies `keys` from one variant file into another. Keys that already exist with a
+/// different value are reported as conflicts and left alone unless `overwrite` is set.
+#[tauri::command]
+pub async fn promote_keys(
+    app: AppHandle,
+    source_path: String,
+    target_path: String,
+    keys: Vec<String>,
+    overwrite: bool,
+    options: WriteOptions,
+) -> Result<PromoteResult, AppError> {
+    run_blocking(app, move |_, state| {
+        let source_buf = decode_path(&source_path);
+        let target_buf = decode_path(&target_path);
+        ensure_allowed_path(&state, &source_buf)?;
+        ensure_allowed_path(&state, &target_buf)?;
+
+        let source_lines = parse_env_lines(&read_env_text(&state, &source_buf)?);
+        let (mut target_lines, layout) = parse_env_text(&read_env_text(&state, &target_buf)?);
+
+        let mut result = PromoteResult {
+            added: Vec::new(),
+            updated: Vec::new(),
+            unchanged: Vec::new(),
+            conflicts: Vec::new(),
+            missing: Vec::new(),
+        };
+
+        for key in keys {
+            ensure_no_directives(&source_lines, &key)?;
+            ensure_no_directives(&target_lines, &key)?;
+            let Some(source_line) = find_kv_line(&source_lines, &key) else {
+                result.missing.push(key);
+                continue;
+            };
+            let source_value = find_kv_value(&source_lines, &key).unwrap_or_default();
+            match find_kv_value(&target_lines, &key).map(str::to_string) {
+                None => {
+                    let mut line = source_line.clone();
+                    if let EnvLine::Kv { description, .. } = &mut line {
+                        *description = None;
+                    }
+                    insert_after_last_kv(&mut target_lines, line);
+                    result.added.push(key);
+                }
+                Some(target_value) if target_value == source_value => result.unchanged.push(key),
+                Some(target_value) if !overwrite => result.conflicts.push(KeyConflict {
+                    key,
+                    source_value: source_value.to_string(),
+                    target_value,
+                }),
+                Some(_) => {
+                    set_kv_value(&mut target_lines, &key, source_value);
+                    result.updated.push(key);
+                }
+            }
+        }
+
+        if !result.added.is_empty() || !result.updated.is_empty() {
+            let content =
+                serialize_for_write(&state, &target_buf, &target_lines, layout, &options)?;
+            write_contents(&state, &target_buf, &content, &options)?;
+        }
+
+        Ok(result)
+    })
+    .await
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveEntry {
+    key: String,
+    value: String,
+    /// The file that last set or appended to the value.
+    source: String,
+    overridden: Vec<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveEnv {
+    group_id: String,
+    mode: String,
+    framework: Framework,
+    load_order: Vec<String>,
+    entries: Vec<EffectiveEntry>,
+}
+
+/// Merges the variant files of a group following the framework's precedence rules.
+/// Without an explicit framework, the one detected for the group folder is used.
+/// Frameworks without a defined load order (see `load_order`) are refused.
+#[tauri::command]
+pub async fn resolve_effective_env(
+    app: AppHandle,
+    group_id: String,
+    mode: String,
+    framework: Option<Framework>,
+) -> Result<EffectiveEnv, AppError> {
+    run_blocking(app, move |app, state| {
+        app_lock::ensure_unlocked(&state)?;
+        let paths = files_in_group(&state, &group_id)?;
+        let framework = framework
+            .or_else(|| paths[0].parent().and_then(detect_framework))
+            .unwrap_or(Framework::Generic);
+
+        let order = load_order(framework, &mode).ok_or_else(|| {
+            AppError::InvalidInput(
+                "This framework does not load env files in a defined order".to_string(),
+            )
+        })?;
+        let ordered: Vec<&PathBuf> = order
+            .iter()
+            .filter_map(|name| paths.iter().find(|path| file_name(path) == *name))
+            .collect();
+
+        // Files are applied lowest precedence first, as if each were sourced over the one
+        // before, so `unset` and `+=` act on what the weaker files set.
+        let mut entries: BTreeMap<String, EffectiveEntry> = BTreeMap::new();
+        for path in ordered.iter().rev() {
+            let source = encode_path(path);
+            let lines = parse_env_lines(&read_env_text(&state, path)?);
+            for line in &lines {
+                let (key, value, append) = match line {
+                    EnvLine::Kv { key, value, .. } => (key, value, false),
+                    EnvLine::Append { key, value, .. } => (key, value, true),
+                    EnvLine::Unset { key, .. } => {
+                        entries.remove(key);
+                        continue;
+                    }
+                    _ => continue,
+                };
+                let value = decode_value(value);
+                match entries.get_mut(key) {
+                    Some(entry) => {
+                        if entry.source != source {
+                            let previous = std::mem::replace(&mut entry.source, source.clone());
+                            if !append && !entry.overridden.contains(&previous) {
+                                entry.overridden.insert(0, previous);
+                            }
+                        }
+                        if append {
+                            entry.value.push_str(&value);
+                        } else {
+                            entry.value = value;
+                        }
+                    }
+                    None => {
+                        // An append with nothing before it starts from an empty value, not
+                        // from the app's own environment.
+                        entries.insert(
+                            key.clone(),
+                            EffectiveEntry {
+                                key: key.clone(),
+                                value,
+                                source: source.clone(),
+                                overridden: Vec::new(),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(EffectiveEnv {
+            group_id,
+            mode,
+            framework,
+            load_order: ordered.iter().map(|path| file_name(path)).collect(),
+            entries: entries.into_values().collect(),
+        })
+    })
+    .await
+}
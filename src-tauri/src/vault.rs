@@ -0,0 +1,135 @@
+use crate::interop::{import_values, read_values, ExportOutput, ImportResult};
+use crate::io_error::IoCode;
+use crate::ipc_path::decode_path;
+use crate::{run_blocking, AppError, AppState, WriteOptions};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultExport {
+    /// Body for `POST <mount>/data/<path>` (or `vault kv put <path> @file.json`).
+    #[serde(flatten)]
+    data: ExportOutput,
+    /// Body for `POST <mount>/metadata/<path>` carrying `custom_metadata`.
+    metadata: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultImport {
+    #[serde(flatten)]
+    result: ImportResult,
+    version: Option<u64>,
+    created_time: Option<String>,
+    custom_metadata: BTreeMap<String, String>,
+}
+
+fn to_json(value: &Value) -> Result<String, AppError> {
+    serde_json::to_string_pretty(value)
+        .map_err(|e| AppError::io(IoCode::InvalidData, e.to_string()))
+}
+
+/// Renders an env file as a KV v2 write payload. `cas` enables check-and-set and
+/// `custom_metadata` is merged over a default `source` entry naming the file.
+#[tauri::command]
+pub async fn export_vault_kv(
+    app: AppHandle,
+    path: String,
+    cas: Option<u64>,
+    custom_metadata: Option<BTreeMap<String, String>>,
+    keys: Option<Vec<String>>,
+) -> Result<VaultExport, AppError> {
+    run_blocking(app, move |_, state| {
+        let path_buf = &decode_path(&path);
+        let values = read_values(&state, path_buf, keys.as_deref())?;
+        let data: Map<String, Value> = values
+            .iter()
+            .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+            .collect();
+
+        let mut payload = json!({ "data": data });
+        if let Some(cas) = cas {
+            payload["options"] = json!({ "cas": cas });
+        }
+
+        let mut metadata = BTreeMap::new();
+        if let Some(name) = path_buf.file_name() {
+            metadata.insert("source".to_string(), name.to_string_lossy().to_string());
+        }
+        metadata.extend(custom_metadata.unwrap_or_default());
+
+        Ok(VaultExport {
+            data: ExportOutput::new(to_json(&payload)?, values.len()),
+            metadata: to_json(&json!({ "custom_metadata": metadata }))?,
+        })
+    })
+    .await
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Imports the output of `vault kv get -format=json` into a local env file. KV v2
+/// dumps (`data.data` + `data.metadata`) and flat KV v1 dumps are both accepted;
+/// non-string values are written as compact JSON.
+#[tauri::command]
+pub async fn import_vault_kv(
+    app: AppHandle,
+    contents: String,
+    target_path: String,
+    overwrite: bool,
+    options: Option<WriteOptions>,
+) -> Result<VaultImport, AppError> {
+    run_blocking(app, move |_, state| {
+        let dump: Value = serde_json::from_str(&contents)
+            .map_err(|e| AppError::InvalidInput(format!("Invalid Vault JSON: {}", e)))?;
+        let outer = dump.get("data").unwrap_or(&dump);
+        let (data, metadata) = match outer.get("data").and_then(Value::as_object) {
+            Some(data) => (data, outer.get("metadata")),
+            None => (
+                outer.as_object().ok_or_else(|| {
+                    AppError::InvalidInput("Vault JSON has no data object".to_string())
+                })?,
+                None,
+            ),
+        };
+
+        let entries = data
+            .iter()
+            .map(|(key, value)| (key.clone(), value_text(value)))
+            .collect();
+        let result = import_values(&state, &target_path, entries, overwrite, options)?;
+
+        let custom_metadata = metadata
+            .and_then(|metadata| metadata.get("custom_metadata"))
+            .and_then(Value::as_object)
+            .map(|custom| {
+                custom
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value_text(value)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(VaultImport {
+            result,
+            version: metadata
+                .and_then(|metadata| metadata.get("version"))
+                .and_then(Value::as_u64),
+            created_time: metadata
+                .and_then(|metadata| metadata.get("created_time"))
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            custom_metadata,
+        })
+    })
+    .await
+}
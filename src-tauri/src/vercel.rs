@@ -0,0 +1,143 @@
+use crate::direnv::shell_quote;
+use crate::interop::{decoded_values, import_values, read_values, ExportOutput, ImportResult};
+use crate::io_error::IoCode;
+use crate::ipc_path::decode_path;
+use crate::{parse_env_lines, run_blocking, AppError, AppState, WriteOptions};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::Path;
+use tauri::AppHandle;
+
+/// Variables the Vercel CLI adds to pulled files; they describe the deployment,
+/// not the app.
+const SYSTEM_PREFIXES: [&str; 3] = ["VERCEL_", "TURBO_", "NX_"];
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VercelTarget {
+    Development,
+    Preview,
+    Production,
+}
+
+impl VercelTarget {
+    fn as_str(self) -> &'static str {
+        match self {
+            VercelTarget::Development => "development",
+            VercelTarget::Preview => "preview",
+            VercelTarget::Production => "production",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VercelExportFormat {
+    /// Shell lines piping each value into `vercel env add`.
+    Cli,
+    /// JSON body for the REST bulk endpoint (`POST /v10/projects/{id}/env`).
+    Rest,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VercelExport {
+    target: VercelTarget,
+    #[serde(flatten)]
+    output: ExportOutput,
+}
+
+/// Maps a local file name to the Vercel environment it corresponds to:
+/// `.env.production` → production, `.env.preview`/`.env.staging` → preview,
+/// anything else → development.
+pub fn target_for_file(path: &Path) -> VercelTarget {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    if name.contains("production") || name.ends_with(".prod") {
+        VercelTarget::Production
+    } else if name.contains("preview") || name.contains("staging") {
+        VercelTarget::Preview
+    } else {
+        VercelTarget::Development
+    }
+}
+
+fn is_system_key(key: &str) -> bool {
+    key == "VERCEL" || SYSTEM_PREFIXES.iter().any(|prefix| key.starts_with(prefix))
+}
+
+/// Imports a file produced by `vercel env pull` into a local env file. Variables the
+/// CLI injects (`VERCEL_*`, `TURBO_*`, `NX_*`) are dropped unless `include_system`.
+#[tauri::command]
+pub async fn import_vercel_env(
+    app: AppHandle,
+    contents: String,
+    target_path: String,
+    overwrite: bool,
+    include_system: Option<bool>,
+    options: Option<WriteOptions>,
+) -> Result<ImportResult, AppError> {
+    run_blocking(app, move |_, state| {
+        let include_system = include_system.unwrap_or(false);
+        let entries = decoded_values(&parse_env_lines(&contents))
+            .into_iter()
+            .filter(|(key, _)| include_system || !is_system_key(key))
+            .collect();
+        import_values(&state, &target_path, entries, overwrite, options)
+    })
+    .await
+}
+
+/// Renders an env file for `vercel env add` or the REST bulk API. The target
+/// environment defaults to the one matching the file name.
+#[tauri::command]
+pub async fn export_vercel_env(
+    app: AppHandle,
+    path: String,
+    format: VercelExportFormat,
+    target: Option<VercelTarget>,
+    keys: Option<Vec<String>>,
+) -> Result<VercelExport, AppError> {
+    run_blocking(app, move |_, state| {
+        let path_buf = &decode_path(&path);
+        let target = target.unwrap_or_else(|| target_for_file(path_buf));
+        let values = read_values(&state, path_buf, keys.as_deref())?;
+
+        let content = match format {
+            VercelExportFormat::Cli => values
+                .iter()
+                .map(|(key, value)| {
+                    format!(
+                        "printf '%s' {} | vercel env add {} {}\n",
+                        shell_quote(value),
+                        key,
+                        target.as_str()
+                    )
+                })
+                .collect(),
+            VercelExportFormat::Rest => {
+                let body: Vec<serde_json::Value> = values
+                    .iter()
+                    .map(|(key, value)| {
+                        json!({
+                            "key": key,
+                            "value": value,
+                            "type": "encrypted",
+                            "target": [target.as_str()],
+                        })
+                    })
+                    .collect();
+                serde_json::to_string_pretty(&body)
+                    .map_err(|e| AppError::io(IoCode::InvalidData, e.to_string()))?
+            }
+        };
+
+        Ok(VercelExport {
+            target,
+            output: ExportOutput::new(content, values.len()),
+        })
+    })
+    .await
+}
@@ -0,0 +1,128 @@
+use crate::ipc_path::{decode_path, encode_path};
+use crate::{
+    diff_key_values, ensure_allowed_path, hash_content, normalize_path, parse_env_lines,
+    run_blocking, unified_diff, AppError, AppState, KeyChange,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+pub const FILE_CHANGED_EVENT: &str = "env-file-changed";
+const POLL_INTERVAL: Duration = Duration::from_millis(1500);
+
+/// An open file: the editor's in-memory content and the last on-disk hash we know of.
+pub struct WatchedFile {
+    document: String,
+    disk_hash: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileChangedEvent {
+    path: String,
+    disk_content: String,
+    changes: Vec<KeyChange>,
+    diff: String,
+}
+
+/// Called after the app itself writes a file so the watcher does not report it as an
+/// external change.
+pub fn record_write(state: &AppState, path: &Path, content: &str) -> Result<(), AppError> {
+    let key = normalize_path(path)?;
+    let mut watched = state.watched_files.write();
+    if let Some(file) = watched.get_mut(&key) {
+        file.document = content.to_string();
+        file.disk_hash = hash_content(content.as_bytes());
+    }
+    Ok(())
+}
+
+/// Reads the files without holding the lock, so commands are not blocked by disk I/O.
+fn poll_once(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let snapshot: Vec<(PathBuf, String)> = state
+        .watched_files
+        .read()
+        .iter()
+        .map(|(path, file)| (path.clone(), file.disk_hash.clone()))
+        .collect();
+    for (path, known_hash) in snapshot {
+        let Ok(bytes) = fs::read(&path) else {
+            continue;
+        };
+        let disk_hash = hash_content(&bytes);
+        if disk_hash == known_hash {
+            continue;
+        }
+        // The file may have been recorded as written or unwatched while it was read.
+        let document = {
+            let mut watched = state.watched_files.write();
+            match watched.get_mut(&path) {
+                Some(file) if file.disk_hash == known_hash => {
+                    file.disk_hash = disk_hash;
+                    file.document.clone()
+                }
+                _ => continue,
+            }
+        };
+
+        let disk_content = String::from_utf8_lossy(&bytes).to_string();
+        let display_path = encode_path(&path);
+        let event = FileChangedEvent {
+            changes: diff_key_values(&parse_env_lines(&document), &parse_env_lines(&disk_content)),
+            diff: unified_diff(&display_path, &document, &disk_content).diff,
+            path: display_path,
+            disk_content,
+        };
+        let _ = app.emit_all(FILE_CHANGED_EVENT, event);
+    }
+}
+
+/// Starts the background polling loop; runs for the lifetime of the app.
+pub fn spawn(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(POLL_INTERVAL);
+        poll_once(&app);
+    });
+}
+
+/// Registers (or refreshes) an open file along with the editor's current content.
+#[tauri::command]
+pub async fn watch_open_file(
+    app: AppHandle,
+    path: String,
+    content: String,
+) -> Result<(), AppError> {
+    run_blocking(app, move |_, state| {
+        let path_buf = decode_path(&path);
+        ensure_allowed_path(&state, &path_buf)?;
+        let key = normalize_path(&path_buf)?;
+        let disk_hash = hash_content(&fs::read(&key)?);
+
+        let mut watched = state.watched_files.write();
+        match watched.get_mut(&key) {
+            Some(file) => file.document = content,
+            None => {
+                watched.insert(
+                    key,
+                    WatchedFile {
+                        document: content,
+                        disk_hash,
+                    },
+                );
+            }
+        }
+        Ok(())
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn unwatch_file(state: State<'_, AppState>, path: String) -> Result<(), AppError> {
+    let key = normalize_path(&decode_path(&path)).unwrap_or_else(|_| decode_path(&path));
+    state.watched_files.write().remove(&key);
+    Ok(())
+}
@@ -33,7 +33,7 @@ import {
   cancelScan,
   readEnvFile,
   scanEnvFiles,
-  writeEnvFile,
+  writeEnvDocument,
 } from "@/lib/tauri";
 import { appReducer, initialState } from "@/state/reducer";
 import type { EnvFileRef, EnvLine, ProjectGroup } from "@/types";
@@ -205,7 +205,6 @@ const App = () => {
 
   const handleSave = async () => {
     if (!document || !selectedFile) return;
-    const content = rawText;
     const pendingChanges = diffKv(originalLines, document.lines);
     const added = pendingChanges.filter(
       (item) => item.change === "added",
@@ -217,10 +216,15 @@ const App = () => {
       (item) => item.change === "removed",
     ).length;
     try {
-      await writeEnvFile(selectedFile.absolutePath, content, { createBackup });
+      await writeEnvDocument(selectedFile.absolutePath, document, {
+        createBackup,
+        expectedHash: document.file.contentHash,
+      });
+      const saved = await readEnvFile(selectedFile.absolutePath);
       dispatch({
         type: "patch",
         patch: {
+          document: { ...document, file: saved.file },
           originalLines: document.lines,
           statusMessage: tx("fileSavedStatus"),
         },
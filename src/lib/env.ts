@@ -1,22 +1,48 @@
-import type { DiffItem, EnvLine } from "../types";
+import type { DiffItem, EnvDocument, EnvLine } from "../types";
 
 export const linesToRaw = (lines: EnvLine[]): string => {
   return lines
     .map((line) => {
       if (line.kind === "blank") return "";
       if (line.kind === "comment") return line.raw;
-      if (line.kind === "unknown") return line.raw;
+      if (line.kind === "invalid") return line.raw;
       if (line.raw) return line.raw;
+      if (line.kind === "unset") return `unset ${line.key}`;
+      if (line.kind === "append") {
+        const comment = line.inlineComment ? ` # ${line.inlineComment}` : "";
+        return `${line.key}+=${line.value}${comment}`;
+      }
       const prefix = line.hasExport ? "export " : "";
-      return `${prefix}${line.key}=${line.value}`;
+      const comment = line.inlineComment ? ` # ${line.inlineComment}` : "";
+      return `${prefix}${line.key}${line.separator ?? "="}${line.value}${comment}`;
     })
     .join("\n");
 };
 
+export const documentToRaw = (document: EnvDocument): string => {
+  const raw = linesToRaw(document.lines);
+  return document.trailingNewline && document.lines.length > 0 ? `${raw}\n` : raw;
+};
+
+// Text areas only give back "\n", so CRLF files keep their line ending unless the
+// edited text itself has "\r\n".
+export const layoutFromRaw = (
+  raw: string,
+  lineEnding: EnvDocument["lineEnding"],
+): Pick<EnvDocument, "trailingNewline" | "lineEnding"> => ({
+  trailingNewline: raw.endsWith("\n"),
+  lineEnding: raw.includes("\r\n") ? "crlf" : lineEnding ?? "lf",
+});
+
 const kvRegex = /^\s*(export\s+)?([A-Za-z_][A-Za-z0-9_]*)\s*=\s*(.*)$/;
+const unsetRegex = /^\s*unset\s+([A-Za-z_][A-Za-z0-9_]*)\s*$/;
+const appendRegex = /^\s*([A-Za-z_][A-Za-z0-9_]*)\+=(.*)$/;
 
 export const parseRawToLines = (raw: string): EnvLine[] => {
   const rows = raw.split(/\r?\n/);
+  if (rows.length > 1 && rows[rows.length - 1] === "") {
+    rows.pop();
+  }
   return rows.map((row) => {
     if (row.trim() === "") {
       return { kind: "blank" };
@@ -31,7 +57,20 @@ export const parseRawToLines = (raw: string): EnvLine[] => {
       const value = match[3] ?? "";
       return { kind: "kv", key, value, hasExport, raw: row };
     }
-    return { kind: "unknown", raw: row };
+    const unset = row.match(unsetRegex);
+    if (unset) {
+      return { kind: "unset", key: unset[1] ?? "", raw: row };
+    }
+    const append = row.match(appendRegex);
+    if (append) {
+      return { kind: "append", key: append[1] ?? "", value: append[2] ?? "", raw: row };
+    }
+    const reason = row.includes("=") ? "invalidKey" : "missingEquals";
+    const message =
+      reason === "invalidKey"
+        ? "This line has no valid key before ="
+        : "This line has no =; assignments are written KEY=value";
+    return { kind: "invalid", raw: row, reason, severity: "error", message };
   });
 };
 
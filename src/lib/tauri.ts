@@ -1,12 +1,259 @@
 import { invoke } from "@tauri-apps/api/tauri";
-import type { EnvDocument, ScanResult, WriteOptions } from "@/types";
+import type {
+  AccessSettings,
+  BackupDiff,
+  BackupEntry,
+  BackupSettings,
+  BatchResult,
+  BundleInfo,
+  CleanupReport,
+  Diagnostic,
+  DiagnosticsReport,
+  Dialect,
+  DialectSetting,
+  DiscoveryPatterns,
+  DropPolicy,
+  DropTarget,
+  EditResult,
+  Editor,
+  EffectiveEnv,
+  EnvDocument,
+  EnvReport,
+  EnvTemplate,
+  EnvrcDocument,
+  ExportOutput,
+  ExpiringSecret,
+  FileChange,
+  FileReplacement,
+  FileStatus,
+  FileWrite,
+  FindingsFormat,
+  FindReplaceOptions,
+  FormatResult,
+  FormatStyle,
+  Framework,
+  GeneratedValue,
+  GeneratorKind,
+  GeneratorOptions,
+  GithubSecretsExport,
+  GithubSecretsOptions,
+  GitleaksStatus,
+  GroupSort,
+  HookStatus,
+  ImportReport,
+  ImportResult,
+  ImportStrategy,
+  InterpolationGraph,
+  KeyDefinitions,
+  KeyHistory,
+  KeyNote,
+  LockStatus,
+  LogLevel,
+  LogSettings,
+  OpReference,
+  OpenRequest,
+  OrderDrift,
+  PaletteMatch,
+  PaletteScope,
+  ParseMode,
+  PendingWrite,
+  ProcessEnvDiff,
+  PromoteResult,
+  RecentLogs,
+  ReorderResult,
+  ReportFormat,
+  ScanResult,
+  SecretExpiry,
+  SecretRule,
+  SecretRuleSet,
+  SecretTool,
+  Snippet,
+  SnippetInsert,
+  SsmExportOptions,
+  TemplateInstance,
+  TfvarsExport,
+  TfvarsOptions,
+  TransformResult,
+  UnfilledValue,
+  ValidationReport,
+  ValueIndexStatus,
+  ValueTransform,
+  ValueUsage,
+  VariantMatrix,
+  VaultExport,
+  VaultImport,
+  VercelExport,
+  VercelTarget,
+  WorkspaceStats,
+  WriteOptions,
+  WritePreview
+} from "@/types";
 
-export const scanEnvFiles = async (rootPath: string): Promise<ScanResult> => {
-  return invoke<ScanResult>("scan_env_files", { rootPath });
+export const scanEnvFiles = async (
+  rootPath: string,
+  patterns?: DiscoveryPatterns,
+  excludeGlobs?: string[],
+  sort?: GroupSort
+): Promise<ScanResult> => {
+  return invoke<ScanResult>("scan_env_files", { rootPath, patterns, excludeGlobs, sort });
 };
 
-export const readEnvFile = async (path: string): Promise<EnvDocument> => {
-  return invoke<EnvDocument>("read_env_file", { path });
+export const listChangedFiles = async (): Promise<FileChange[]> => {
+  return invoke<FileChange[]>("list_changed_files");
+};
+
+export const fuzzyFind = async (
+  query: string,
+  scope?: PaletteScope,
+  limit?: number
+): Promise<PaletteMatch[]> => {
+  return invoke<PaletteMatch[]>("fuzzy_find", { query, scope, limit });
+};
+
+export const getValueIndexStatus = async (): Promise<ValueIndexStatus> => {
+  return invoke<ValueIndexStatus>("get_value_index_status");
+};
+
+export const setValueIndexEnabled = async (enabled: boolean): Promise<ValueIndexStatus> => {
+  return invoke<ValueIndexStatus>("set_value_index_enabled", { enabled });
+};
+
+export const findValueUsages = async (query: string, exact?: boolean): Promise<ValueUsage[]> => {
+  return invoke<ValueUsage[]>("find_value_usages", { query, exact });
+};
+
+export const whereIsKeyDefined = async (key: string): Promise<KeyDefinitions> => {
+  return invoke<KeyDefinitions>("where_is_key_defined", { key });
+};
+
+export const exportReport = async (
+  groupId: string,
+  format: ReportFormat,
+  redact?: boolean
+): Promise<EnvReport> => {
+  return invoke<EnvReport>("export_report", { groupId, format, redact });
+};
+
+export const diffWithProcessEnv = async (
+  path: string,
+  shell?: string
+): Promise<ProcessEnvDiff> => {
+  return invoke<ProcessEnvDiff>("diff_with_process_env", { path, shell });
+};
+
+export const importProcessEnv = async (
+  targetPath: string,
+  filterPrefixes: string[],
+  overwrite: boolean,
+  dump?: string,
+  shell?: string,
+  options?: WriteOptions
+): Promise<ImportResult> => {
+  return invoke<ImportResult>("import_process_env", {
+    targetPath,
+    filterPrefixes,
+    dump,
+    shell,
+    overwrite,
+    options
+  });
+};
+
+export const RUN_OUTPUT_EVENT = "run-output";
+
+export const RUN_EXIT_EVENT = "run-exit";
+
+export const runWithEnv = async (
+  path: string,
+  program: string,
+  args?: string[]
+): Promise<string> => {
+  return invoke<string>("run_with_env", { path, program, args });
+};
+
+export const stopRun = async (runId: string): Promise<boolean> => {
+  return invoke<boolean>("stop_run", { runId });
+};
+
+export const listTemplates = async (): Promise<EnvTemplate[]> => {
+  return invoke<EnvTemplate[]>("list_templates");
+};
+
+export const saveTemplate = async (
+  path: string,
+  name: string,
+  description?: string,
+  keepValues?: string[]
+): Promise<EnvTemplate> => {
+  return invoke<EnvTemplate>("save_template", { path, name, description, keepValues });
+};
+
+export const deleteTemplate = async (templateId: string): Promise<boolean> => {
+  return invoke<boolean>("delete_template", { templateId });
+};
+
+export const instantiateTemplate = async (
+  templateId: string,
+  folderPath: string,
+  fileName?: string,
+  options?: WriteOptions
+): Promise<TemplateInstance> => {
+  return invoke<TemplateInstance>("instantiate_template", {
+    templateId,
+    folderPath,
+    fileName,
+    options
+  });
+};
+
+export const listSnippets = async (): Promise<Snippet[]> => {
+  return invoke<Snippet[]>("list_snippets");
+};
+
+export const insertSnippet = async (
+  path: string,
+  snippetId: string,
+  params: Record<string, string>,
+  options: WriteOptions
+): Promise<SnippetInsert> => {
+  return invoke<SnippetInsert>("insert_snippet", { path, snippetId, params, options });
+};
+
+export const getRecentLogs = async (lines?: number): Promise<RecentLogs> => {
+  return invoke<RecentLogs>("get_recent_logs", { lines });
+};
+
+export const setLogLevel = async (level: LogLevel): Promise<LogSettings> => {
+  return invoke<LogSettings>("set_log_level", { level });
+};
+
+export const diagnostics = async (): Promise<DiagnosticsReport> => {
+  return invoke<DiagnosticsReport>("diagnostics");
+};
+
+export const OPEN_PATH_EVENT = "open-path";
+
+export const TRAY_OPEN_FILE_EVENT = "tray-open-file";
+
+export const takeOpenRequest = async (): Promise<OpenRequest | null> => {
+  return invoke<OpenRequest | null>("take_open_request");
+};
+
+export const validateDropPath = async (
+  path: string,
+  policy?: DropPolicy
+): Promise<DropTarget> => {
+  return invoke<DropTarget>("validate_drop_path", { path, policy });
+};
+
+export const PARSE_PROGRESS_EVENT = "parse-progress";
+
+export const readEnvFile = async (path: string, mode?: ParseMode): Promise<EnvDocument> => {
+  return invoke<EnvDocument>("read_env_file", { path, mode });
+};
+
+export const allowSingleFile = async (path?: string): Promise<EnvDocument | null> => {
+  return invoke<EnvDocument | null>("allow_single_file", { path });
 };
 
 export const writeEnvFile = async (
@@ -17,6 +264,486 @@ export const writeEnvFile = async (
   return invoke<void>("write_env_file", { path, content, options });
 };
 
+export const canWrite = async (path: string, expectedSize?: number): Promise<WriteCheck> => {
+  return invoke<WriteCheck>("can_write", { path, expectedSize });
+};
+
+export const writeEnvDocument = async (
+  path: string,
+  document: EnvDocument,
+  options: WriteOptions
+): Promise<void> => {
+  return invoke<void>("write_env_document", { path, document, options });
+};
+
+export const writeBatch = async (writes: FileWrite[]): Promise<BatchResult> => {
+  return invoke<BatchResult>("write_batch", { writes });
+};
+
+export const previewWrite = async (path: string, content: string): Promise<WritePreview> => {
+  return invoke<WritePreview>("preview_write", { path, content });
+};
+
+export const checkChanges = async (paths: string[]): Promise<FileStatus[]> => {
+  return invoke<FileStatus[]>("check_changes", { paths });
+};
+
+export const validateEnv = async (path: string): Promise<ValidationReport> => {
+  return invoke<ValidationReport>("validate_env", { path });
+};
+
+export const interpolationGraph = async (path: string): Promise<InterpolationGraph> => {
+  return invoke<InterpolationGraph>("interpolation_graph", { path });
+};
+
+export const copyValueSecure = async (
+  path: string,
+  key: string,
+  ttlSecs: number
+): Promise<void> => {
+  return invoke<void>("copy_value_secure", { path, key, ttlSecs });
+};
+
+export const generateValue = async (
+  kind: GeneratorKind,
+  options?: GeneratorOptions
+): Promise<GeneratedValue> => {
+  return invoke<GeneratedValue>("generate_value", { kind, options });
+};
+
+export const transformValue = async (
+  path: string,
+  key: string,
+  transform: ValueTransform,
+  options?: WriteOptions
+): Promise<TransformResult> => {
+  return invoke<TransformResult>("transform_value", { path, key, transform, options });
+};
+
+export const setMaxFileSize = async (maxBytes: number): Promise<void> => {
+  return invoke<void>("set_max_file_size", { maxBytes });
+};
+
+export const setExportPrefix = async (
+  path: string,
+  enabled: boolean,
+  options: WriteOptions,
+  keys?: string[]
+): Promise<EditResult> => {
+  return invoke<EditResult>("set_export_prefix", { path, enabled, keys, options });
+};
+
+export const renamePrefix = async (
+  path: string,
+  oldPrefix: string,
+  newPrefix: string,
+  options: WriteOptions
+): Promise<EditResult> => {
+  return invoke<EditResult>("rename_prefix", { path, old: oldPrefix, new: newPrefix, options });
+};
+
+export const readEnvrc = async (path: string): Promise<EnvrcDocument> => {
+  return invoke<EnvrcDocument>("read_envrc", { path });
+};
+
+export const writeEnvrc = async (
+  path: string,
+  document: EnvrcDocument,
+  options: WriteOptions
+): Promise<void> => {
+  return invoke<void>("write_envrc", { path, document, options });
+};
+
+export const compareVariants = async (groupId: string): Promise<VariantMatrix> => {
+  return invoke<VariantMatrix>("compare_variants", { groupId });
+};
+
+export const promoteKeys = async (
+  sourcePath: string,
+  targetPath: string,
+  keys: string[],
+  overwrite: boolean,
+  options: WriteOptions
+): Promise<PromoteResult> => {
+  return invoke<PromoteResult>("promote_keys", {
+    sourcePath,
+    targetPath,
+    keys,
+    overwrite,
+    options
+  });
+};
+
+export const resolveEffectiveEnv = async (
+  groupId: string,
+  mode: string,
+  framework?: Framework
+): Promise<EffectiveEnv> => {
+  return invoke<EffectiveEnv>("resolve_effective_env", { groupId, mode, framework });
+};
+
+export const workspaceStats = async (): Promise<WorkspaceStats> => {
+  return invoke<WorkspaceStats>("workspace_stats");
+};
+
+export const pinKey = async (path: string, key: string): Promise<string[]> => {
+  return invoke<string[]>("pin_key", { path, key });
+};
+
+export const unpinKey = async (path: string, key: string): Promise<string[]> => {
+  return invoke<string[]>("unpin_key", { path, key });
+};
+
+export const listPins = async (path?: string): Promise<Record<string, string[]>> => {
+  return invoke<Record<string, string[]>>("list_pins", { path });
+};
+
+export const getNotes = async (path: string): Promise<Record<string, KeyNote>> => {
+  return invoke<Record<string, KeyNote>>("get_notes", { path });
+};
+
+export const setNote = async (
+  path: string,
+  key: string,
+  text?: string
+): Promise<KeyNote | null> => {
+  return invoke<KeyNote | null>("set_note", { path, key, text });
+};
+
+export const exportWorkspaceBundle = async (
+  passphrase: string,
+  outputPath: string
+): Promise<BundleInfo> => {
+  return invoke<BundleInfo>("export_workspace_bundle", { passphrase, outputPath });
+};
+
+export const importWorkspaceBundle = async (
+  bundlePath: string,
+  passphrase: string,
+  targetRoot: string,
+  strategy: ImportStrategy,
+  dryRun: boolean
+): Promise<ImportReport> => {
+  return invoke<ImportReport>("import_workspace_bundle", {
+    bundlePath,
+    passphrase,
+    targetRoot,
+    strategy,
+    dryRun
+  });
+};
+
+export const configureAutoBackup = async (
+  intervalSecs?: number
+): Promise<BackupSettings> => {
+  return invoke<BackupSettings>("configure_auto_backup", { intervalSecs });
+};
+
+export const getBackupSettings = async (): Promise<BackupSettings> => {
+  return invoke<BackupSettings>("get_backup_settings");
+};
+
+export const listBackups = async (path: string): Promise<BackupEntry[]> => {
+  return invoke<BackupEntry[]>("list_backups", { path });
+};
+
+export const diffAgainstBackup = async (
+  path: string,
+  backupId: string
+): Promise<BackupDiff> => {
+  return invoke<BackupDiff>("diff_against_backup", { path, backupId });
+};
+
+export const keyHistory = async (
+  path: string,
+  key: string,
+  mask?: boolean
+): Promise<KeyHistory> => {
+  return invoke<KeyHistory>("key_history", { path, key, mask });
+};
+
+export const FILE_CHANGED_EVENT = "env-file-changed";
+
+export const watchOpenFile = async (path: string, content: string): Promise<void> => {
+  return invoke<void>("watch_open_file", { path, content });
+};
+
+export const unwatchFile = async (path: string): Promise<void> => {
+  return invoke<void>("unwatch_file", { path });
+};
+
+export const findReplace = async (
+  query: string,
+  replacement: string,
+  options?: FindReplaceOptions
+): Promise<FileReplacement[]> => {
+  return invoke<FileReplacement[]>("find_replace", { query, replacement, options });
+};
+
+export const lintFile = async (path: string): Promise<Diagnostic[]> => {
+  return invoke<Diagnostic[]>("lint_file", { path });
+};
+
+export const lintProject = async (groupId: string): Promise<Diagnostic[]> => {
+  return invoke<Diagnostic[]>("lint_project", { groupId });
+};
+
+export const scanSecrets = async (groupId: string): Promise<Diagnostic[]> => {
+  return invoke<Diagnostic[]>("scan_secrets", { groupId });
+};
+
+export const getSecretRules = async (): Promise<SecretRuleSet> => {
+  return invoke<SecretRuleSet>("get_secret_rules");
+};
+
+export const setSecretRules = async (rules: SecretRule[]): Promise<SecretRuleSet> => {
+  return invoke<SecretRuleSet>("set_secret_rules", { rules });
+};
+
+export const getGitleaksStatus = async (): Promise<GitleaksStatus> => {
+  return invoke<GitleaksStatus>("get_gitleaks_status");
+};
+
+export const setGitleaksConfig = async (
+  configPath?: string,
+  replaceBuiltin?: boolean
+): Promise<GitleaksStatus> => {
+  return invoke<GitleaksStatus>("set_gitleaks_config", { configPath, replaceBuiltin });
+};
+
+export const exportFindings = async (
+  groupId: string | undefined,
+  format: FindingsFormat
+): Promise<EnvReport> => {
+  return invoke<EnvReport>("export_findings", { groupId, format });
+};
+
+export const formatEnvFile = async (
+  path: string,
+  style?: FormatStyle,
+  options?: WriteOptions
+): Promise<FormatResult> => {
+  return invoke<FormatResult>("format_env_file", { path, style, options });
+};
+
+export const findUnfilledValues = async (includeExamples?: boolean): Promise<UnfilledValue[]> => {
+  return invoke<UnfilledValue[]>("find_unfilled_values", { includeExamples });
+};
+
+export const setSecretExpiry = async (
+  path: string,
+  key: string,
+  expiresAt?: number
+): Promise<SecretExpiry | null> => {
+  return invoke<SecretExpiry | null>("set_secret_expiry", { path, key, expiresAt });
+};
+
+export const listExpiringSecrets = async (days: number): Promise<ExpiringSecret[]> => {
+  return invoke<ExpiringSecret[]>("list_expiring_secrets", { days });
+};
+
+export const importVercelEnv = async (
+  contents: string,
+  targetPath: string,
+  overwrite: boolean,
+  includeSystem?: boolean,
+  options?: WriteOptions
+): Promise<ImportResult> => {
+  return invoke<ImportResult>("import_vercel_env", {
+    contents,
+    targetPath,
+    overwrite,
+    includeSystem,
+    options
+  });
+};
+
+export const exportVercelEnv = async (
+  path: string,
+  format: "cli" | "rest",
+  target?: VercelTarget,
+  keys?: string[]
+): Promise<VercelExport> => {
+  return invoke<VercelExport>("export_vercel_env", { path, format, target, keys });
+};
+
+export const exportSsmParameters = async (
+  path: string,
+  options?: SsmExportOptions,
+  keys?: string[]
+): Promise<ExportOutput> => {
+  return invoke<ExportOutput>("export_ssm_parameters", { path, options, keys });
+};
+
+export const exportVaultKv = async (
+  path: string,
+  cas?: number,
+  customMetadata?: Record<string, string>,
+  keys?: string[]
+): Promise<VaultExport> => {
+  return invoke<VaultExport>("export_vault_kv", { path, cas, customMetadata, keys });
+};
+
+export const importVaultKv = async (
+  contents: string,
+  targetPath: string,
+  overwrite: boolean,
+  options?: WriteOptions
+): Promise<VaultImport> => {
+  return invoke<VaultImport>("import_vault_kv", { contents, targetPath, overwrite, options });
+};
+
+export const importToolExport = async (
+  contents: string,
+  targetPath: string,
+  overwrite: boolean,
+  options?: WriteOptions
+): Promise<ImportResult> => {
+  return invoke<ImportResult>("import_tool_export", { contents, targetPath, overwrite, options });
+};
+
+export const exportForTool = async (
+  path: string,
+  tool: SecretTool,
+  format: "json" | "dotenv",
+  keys?: string[]
+): Promise<ExportOutput> => {
+  return invoke<ExportOutput>("export_for_tool", { path, tool, format, keys });
+};
+
+export const listOpReferences = async (path: string): Promise<OpReference[]> => {
+  return invoke<OpReference[]>("list_op_references", { path });
+};
+
+export const resolveOpReferences = async (
+  path: string,
+  reveal?: boolean
+): Promise<OpReference[]> => {
+  return invoke<OpReference[]>("resolve_op_references", { path, reveal });
+};
+
+export const exportGithubSecrets = async (
+  path: string,
+  keys: string[],
+  options?: GithubSecretsOptions
+): Promise<GithubSecretsExport> => {
+  return invoke<GithubSecretsExport>("export_github_secrets", { path, keys, options });
+};
+
+export const exportTfvars = async (
+  path: string,
+  options?: TfvarsOptions,
+  keys?: string[]
+): Promise<TfvarsExport> => {
+  return invoke<TfvarsExport>("export_tfvars", { path, options, keys });
+};
+
+export const exportSystemdEnv = async (path: string, keys?: string[]): Promise<ExportOutput> => {
+  return invoke<ExportOutput>("export_systemd_env", { path, keys });
+};
+
+export const validateSystemdEnv = async (path: string): Promise<Diagnostic[]> => {
+  return invoke<Diagnostic[]>("validate_systemd_env", { path });
+};
+
+export const setFileDialect = async (
+  path: string,
+  dialect?: Dialect
+): Promise<DialectSetting> => {
+  return invoke<DialectSetting>("set_file_dialect", { path, dialect });
+};
+
+export const setProjectDialect = async (groupId: string, dialect?: Dialect): Promise<void> => {
+  return invoke<void>("set_project_dialect", { groupId, dialect });
+};
+
+export const getDialect = async (path: string): Promise<DialectSetting> => {
+  return invoke<DialectSetting>("get_dialect", { path });
+};
+
+export const installPrecommitHook = async (groupId: string): Promise<HookStatus> => {
+  return invoke<HookStatus>("install_precommit_hook", { groupId });
+};
+
+export const uninstallPrecommitHook = async (groupId: string): Promise<HookStatus> => {
+  return invoke<HookStatus>("uninstall_precommit_hook", { groupId });
+};
+
+export const getLockStatus = async (): Promise<LockStatus> => {
+  return invoke<LockStatus>("get_lock_status");
+};
+
+export const enableAppLock = async (
+  password: string,
+  autoLockSecs?: number,
+  currentPassword?: string
+): Promise<LockStatus> => {
+  return invoke<LockStatus>("enable_app_lock", { password, currentPassword, autoLockSecs });
+};
+
+export const disableAppLock = async (password: string): Promise<LockStatus> => {
+  return invoke<LockStatus>("disable_app_lock", { password });
+};
+
+export const unlockApp = async (password: string): Promise<LockStatus> => {
+  return invoke<LockStatus>("unlock_app", { password });
+};
+
+export const lockApp = async (): Promise<LockStatus> => {
+  return invoke<LockStatus>("lock_app");
+};
+
+export const getAccessSettings = async (): Promise<AccessSettings> => {
+  return invoke<AccessSettings>("get_access_settings");
+};
+
+export const setAccessSettings = async (settings: AccessSettings): Promise<AccessSettings> => {
+  return invoke<AccessSettings>("set_access_settings", { settings });
+};
+
+export const revalidateAccess = async (path: string): Promise<boolean> => {
+  return invoke<boolean>("revalidate_access", { path });
+};
+
+export const revealInFileManager = async (path: string): Promise<void> => {
+  return invoke<void>("reveal_in_file_manager", { path });
+};
+
+export const openInExternalEditor = async (path: string, editor?: Editor): Promise<void> => {
+  return invoke<void>("open_in_external_editor", { path, editor });
+};
+
+export const keyOrderDrift = async (
+  path: string,
+  referencePath: string
+): Promise<OrderDrift> => {
+  return invoke<OrderDrift>("key_order_drift", { path, referencePath });
+};
+
+export const reorderKeysToMatch = async (
+  path: string,
+  referencePath: string,
+  options?: WriteOptions
+): Promise<ReorderResult> => {
+  return invoke<ReorderResult>("reorder_keys_to_match", { path, referencePath, options });
+};
+
+export const cleanupTempFiles = async (): Promise<CleanupReport> => {
+  return invoke<CleanupReport>("cleanup_temp_files");
+};
+
+export const listPendingWrites = async (): Promise<PendingWrite[]> => {
+  return invoke<PendingWrite[]>("list_pending_writes");
+};
+
+export const reapplyPendingWrite = async (id: string, options: WriteOptions): Promise<void> => {
+  return invoke<void>("reapply_pending_write", { id, options });
+};
+
+export const discardPendingWrite = async (id: string): Promise<void> => {
+  return invoke<void>("discard_pending_write", { id });
+};
+
 export const cancelScan = async (): Promise<void> => {
   return invoke<void>("cancel_scan");
 };
@@ -1,4 +1,4 @@
-import { linesToRaw } from "@/lib/env";
+import { documentToRaw, layoutFromRaw } from "@/lib/env";
 import type { EnvDocument, EnvFileRef, EnvLine, ProjectGroup } from "@/types";
 
 export type ScanState = "idle" | "scanning" | "done" | "error";
@@ -75,7 +75,7 @@ export const appReducer = (state: AppState, action: AppAction): AppState => {
         selectedFile: action.file,
         document: action.document,
         originalLines: action.document.lines,
-        rawText: linesToRaw(action.document.lines),
+        rawText: documentToRaw(action.document),
         activeTab: "table",
         statusMessage: `Loaded ${action.file.fileName}`,
       };
@@ -84,14 +84,18 @@ export const appReducer = (state: AppState, action: AppAction): AppState => {
       return {
         ...state,
         document: { ...state.document, lines: action.lines },
-        rawText: linesToRaw(action.lines),
+        rawText: documentToRaw({ ...state.document, lines: action.lines }),
       };
     case "setRawText":
       if (state.document && action.lines) {
         return {
           ...state,
           rawText: action.rawText,
-          document: { ...state.document, lines: action.lines },
+          document: {
+            ...state.document,
+            ...layoutFromRaw(action.rawText, state.document.lineEnding),
+            lines: action.lines,
+          },
         };
       }
       return { ...state, rawText: action.rawText };
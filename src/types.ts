@@ -5,38 +5,800 @@ export type EnvFileRef = {
   folderPath: string;
   size: number;
   modifiedAt: number;
+  contentHash: string;
+  keyCount: number;
+  commentCount: number;
+  hasParseErrors: boolean;
+  modifiedBy: Provenance;
+  appWrittenAt?: number;
+  skipped: boolean;
+  skipReason?: string;
 };
 
+export type Provenance = "app" | "external" | "unknown";
+
 export type ProjectGroup = {
   id: string;
   name: string;
   rootPath: string;
   envFiles: EnvFileRef[];
+  framework?: Framework;
 };
 
-export type EnvLine =
-  | { kind: "blank" }
-  | { kind: "comment"; raw: string }
-  | { kind: "kv"; key: string; value: string; hasExport: boolean; raw?: string }
-  | { kind: "unknown"; raw: string };
+export type ValueType =
+  | "empty"
+  | "boolean"
+  | "integer"
+  | "float"
+  | "url"
+  | "path"
+  | "base64"
+  | "json"
+  | "string";
+
+export type TextSpan = {
+  start: number;
+  end: number;
+  startChar: number;
+  endChar: number;
+};
+
+export type LinePosition = {
+  lineNumber?: number;
+  keySpan?: TextSpan;
+  valueSpan?: TextSpan;
+};
+
+export type EnvLine = LinePosition &
+  (
+    | { kind: "blank" }
+    | { kind: "comment"; raw: string }
+    | {
+        kind: "kv";
+        key: string;
+        value: string;
+        hasExport: boolean;
+        separator?: string;
+        inlineComment?: string;
+        description?: string;
+        valueType?: ValueType;
+        dangerous?: boolean;
+        raw?: string;
+      }
+    | { kind: "unset"; key: string; raw?: string }
+    | { kind: "append"; key: string; value: string; inlineComment?: string; raw?: string }
+    | {
+        kind: "invalid";
+        raw: string;
+        reason: ParseReason;
+        severity: DiagnosticSeverity;
+        message: string;
+      }
+  );
+
+export type ParseReason = "invalidKey" | "missingEquals" | "unterminatedQuote";
+
+export type ParseProgress = {
+  path: string;
+  bytesRead: number;
+  totalBytes: number;
+};
 
 export type EnvDocument = {
   file: EnvFileRef;
   lines: EnvLine[];
+  trailingNewline?: boolean;
+  lineEnding?: "lf" | "crlf";
+  prefixGroups?: PrefixGroup[];
+  deviations?: Deviation[];
+};
+
+export type ParseMode = "lenient" | "strict";
+
+export type Deviation = {
+  lineNumber: number;
+  rule: string;
+  message: string;
+  span?: TextSpan;
+};
+
+export type PrefixGroup = {
+  prefix: string;
+  keys: string[];
 };
 
 export type ScanResult = {
   rootPath: string;
   groups: ProjectGroup[];
+  slowPaths?: SlowPath[];
+  skippedPaths?: SkippedPath[];
+  diff?: ScanDiff;
+};
+
+export type SkippedPath = {
+  path: string;
+  reason: string;
+};
+
+export type SlowPath = {
+  path: string;
+  reason: "network" | "timeout";
+};
+
+export type ScanDiff = {
+  addedFiles: string[];
+  removedFiles: string[];
+  addedGroups: string[];
+  removedGroups: string[];
+};
+
+export type DiscoveryPatterns = {
+  dotenv?: boolean;
+  examples?: boolean;
+  envrc?: boolean;
+  flaskenv?: boolean;
+  suffixEnv?: boolean;
+};
+
+export type GroupSort = "name" | "modified" | "fileCount";
+
+export type FileStatus = {
+  path: string;
+  exists: boolean;
+  contentHash?: string;
+  modifiedAt?: number;
+  size?: number;
+};
+
+export type WritePreview = {
+  path: string;
+  changed: boolean;
+  additions: number;
+  deletions: number;
+  diff: string;
 };
 
 export type WriteOptions = {
   createBackup: boolean;
+  expectedHash?: string;
+  expectedMtime?: number;
+  quoteStyle?: QuoteStyle;
+};
+
+export type Dialect = "dotenv" | "shell" | "docker" | "phpdotenv" | "python";
+
+export type QuoteStyle = {
+  policy?: "never" | "always" | "whenNeeded";
+  dialect?: Dialect;
+};
+
+export type IoCode =
+  | "notFound"
+  | "permissionDenied"
+  | "alreadyExists"
+  | "invalidData"
+  | "unavailable"
+  | "other";
+
+export type IoFailure = {
+  code: IoCode;
+  message: string;
+  path?: string;
+  kind?: string;
+  hint?: string;
 };
 
+export type AppError =
+  | { type: "InvalidRootPath" }
+  | { type: "PathNotAllowed" }
+  | { type: "ScanCanceled" }
+  | { type: "IoError"; message: IoFailure }
+  | { type: "RegexError" }
+  | { type: "SchemaError"; message: string }
+  | { type: "GroupNotFound" }
+  | { type: "KeyNotFound"; message: string }
+  | { type: "ClipboardError"; message: string }
+  | { type: "InvalidInput"; message: string }
+  | { type: "FileTooLarge"; message: { size: number; limit: number } }
+  | { type: "NotText" }
+  | { type: "CryptoError"; message: string }
+  | { type: "BundleError"; message: string }
+  | {
+      type: "Conflict";
+      message: { currentContent: string; currentHash: string; modifiedAt: number };
+    }
+  | { type: "Locked" }
+  | { type: "AccessExpired" };
+
 export type DiffItem = {
   key: string;
   change: "added" | "updated" | "removed";
   before?: string;
   after?: string;
 };
+
+export type Violation = {
+  key: string;
+  rule: "required" | "type" | "pattern" | "allowed" | "unknown";
+  message: string;
+  line?: number;
+};
+
+export type ValidationReport = {
+  path: string;
+  schemaPath?: string;
+  violations: Violation[];
+};
+
+export type InterpolationNode = {
+  key: string;
+  line: number;
+  references: string[];
+  referencedBy: string[];
+  dangerous: boolean;
+};
+
+export type InterpolationGraph = {
+  path: string;
+  nodes: InterpolationNode[];
+  unresolved: { key: string; reference: string }[];
+  cycles: string[][];
+};
+
+export type GeneratorKind = "hex" | "base64" | "base64url" | "uuid" | "bcrypt";
+
+export type GeneratorOptions = {
+  bytes?: number;
+  username?: string;
+  password?: string;
+  cost?: number;
+};
+
+export type GeneratedValue = {
+  kind: GeneratorKind;
+  value: string;
+  plaintext?: string;
+};
+
+export type ValueTransform = "base64Encode" | "base64Decode" | "urlEncode" | "urlDecode";
+
+export type TransformResult = {
+  key: string;
+  before: string;
+  after: string;
+  written: boolean;
+};
+
+export type EditResult = {
+  path: string;
+  changedKeys: string[];
+};
+
+export type EnvrcLine =
+  | { kind: "blank" }
+  | { kind: "comment"; raw: string }
+  | { kind: "export"; key: string; value: string; raw?: string }
+  | { kind: "pathAdd"; path: string; raw?: string }
+  | { kind: "dotenv"; path?: string; ifExists: boolean; raw?: string }
+  | { kind: "shell"; raw: string };
+
+export type EnvrcDocument = {
+  path: string;
+  lines: EnvrcLine[];
+  trailingNewline?: boolean;
+  lineEnding?: "lf" | "crlf";
+};
+
+export type VariantMatrix = {
+  groupId: string;
+  files: string[];
+  paths: string[];
+  rows: {
+    key: string;
+    values: (string | null)[];
+    status: "same" | "different" | "missing";
+  }[];
+};
+
+export type PromoteResult = {
+  added: string[];
+  updated: string[];
+  unchanged: string[];
+  conflicts: { key: string; sourceValue: string; targetValue: string }[];
+  missing: string[];
+};
+
+export type Framework =
+  | "nextjs"
+  | "vite"
+  | "cra"
+  | "rails"
+  | "django"
+  | "laravel"
+  | "spring"
+  | "generic";
+
+export type EffectiveEnv = {
+  groupId: string;
+  mode: string;
+  framework: Framework;
+  loadOrder: string[];
+  entries: { key: string; value: string; source: string; overridden: string[] }[];
+};
+
+export type FileStat = {
+  path: string;
+  size: number;
+  modifiedAt: number;
+  keyCount: number;
+};
+
+export type WorkspaceStats = {
+  totalFiles: number;
+  totalKeys: number;
+  totalProjects: number;
+  missingFiles: number;
+  largestFiles: FileStat[];
+  recentFiles: FileStat[];
+  commonKeys: { key: string; files: number }[];
+};
+
+export type KeyNote = {
+  text: string;
+  updatedAt: number;
+};
+
+export type BundleInfo = {
+  path: string;
+  fileCount: number;
+  size: number;
+};
+
+export type ImportStrategy = "overwrite" | "skip" | "merge";
+
+export type ImportReport = {
+  rootName: string;
+  createdAt: number;
+  dryRun: boolean;
+  entries: {
+    relativePath: string;
+    targetPath: string;
+    action: "create" | "overwrite" | "merge" | "skip" | "unchanged";
+    addedKeys: string[];
+  }[];
+};
+
+export type BackupEntry = {
+  id: string;
+  createdAt: number;
+  contentHash: string;
+  size: number;
+};
+
+export type BackupSettings = {
+  autoBackupIntervalSecs?: number;
+};
+
+export type KeyChange = {
+  key: string;
+  change: "added" | "updated" | "removed";
+  before?: string;
+  after?: string;
+};
+
+export type FileChangedEvent = {
+  path: string;
+  diskContent: string;
+  changes: KeyChange[];
+  diff: string;
+};
+
+export type BackupDiff = {
+  backupId: string;
+  changes: KeyChange[];
+  preview: WritePreview;
+};
+
+export type KeyVersion = {
+  firstSeenAt: number;
+  lastSeenAt: number;
+  backupId?: string;
+  present: boolean;
+  value?: string;
+  valueFingerprint?: string;
+};
+
+export type KeyHistory = {
+  key: string;
+  versions: KeyVersion[];
+};
+
+export type FindReplaceOptions = {
+  regex?: boolean;
+  caseSensitive?: boolean;
+  scope?: "keys" | "values" | "both";
+  apply?: boolean;
+  createBackup?: boolean;
+  expectedHashes?: Record<string, string>;
+};
+
+export type FileReplacement = {
+  path: string;
+  matches: number;
+  contentHash: string;
+  preview: WritePreview;
+  applied: boolean;
+  conflicts: string[];
+};
+
+export type DiagnosticSeverity = "error" | "warning" | "info";
+
+export type Diagnostic = {
+  rule: string;
+  severity: DiagnosticSeverity;
+  message: string;
+  path: string;
+  line?: number;
+  key?: string;
+  suggestion?: string;
+};
+
+export type SecretRule = {
+  id: string;
+  description?: string;
+  pattern: string;
+  severity: DiagnosticSeverity;
+};
+
+export type SecretRuleSet = {
+  builtin: SecretRule[];
+  custom: SecretRule[];
+  gitleaks: SecretRule[];
+};
+
+export type GitleaksStatus = {
+  configPath?: string;
+  replaceBuiltin: boolean;
+  ruleCount: number;
+  skipped: string[];
+};
+
+export type FindingsFormat = "sarif" | "json";
+
+export type FormatStyle = {
+  quotes?: "keep" | "double" | "single";
+  spacedSeparator?: boolean;
+};
+
+export type FormatResult = {
+  preview: WritePreview;
+  written: boolean;
+};
+
+export type UnfilledValue = {
+  path: string;
+  line: number;
+  key: string;
+  kind: "empty" | "placeholder";
+  value: string;
+};
+
+export type SecretExpiry = {
+  path: string;
+  expiresAt: number;
+  notifiedAt?: number;
+};
+
+export type ExpiringSecret = {
+  path: string;
+  key: string;
+  expiresAt: number;
+  daysLeft: number;
+  expired: boolean;
+};
+
+export type ImportResult = {
+  path: string;
+  added: string[];
+  updated: string[];
+  unchanged: string[];
+  skipped: string[];
+  written: boolean;
+};
+
+export type ExportOutput = {
+  content: string;
+  keyCount: number;
+};
+
+export type VercelTarget = "development" | "preview" | "production";
+
+export type VercelExport = ExportOutput & {
+  target: VercelTarget;
+};
+
+export type SsmExportOptions = {
+  prefix?: string;
+  format?: "json" | "cli";
+  secureKeys?: string[];
+  overwrite?: boolean;
+};
+
+export type VaultExport = ExportOutput & {
+  metadata: string;
+};
+
+export type VaultImport = ImportResult & {
+  version?: number;
+  createdTime?: string;
+  customMetadata: Record<string, string>;
+};
+
+export type SecretTool = "doppler" | "infisical";
+
+export type OpReference = {
+  key: string;
+  line: number;
+  reference: string;
+  resolved?: string;
+  error?: string;
+};
+
+export type GithubSecretsOptions = {
+  format?: "cli" | "rest";
+  repo?: string;
+  environment?: string;
+  publicKey?: string;
+  keyId?: string;
+};
+
+export type GithubSecretsExport = ExportOutput & {
+  missing: string[];
+};
+
+export type TfvarsOptions = {
+  lowercase?: boolean;
+  auto?: boolean;
+};
+
+export type TfvarsExport = ExportOutput & {
+  fileName: string;
+};
+
+export type DialectSetting = {
+  path: string;
+  dialect: Dialect;
+  fileLevel: boolean;
+};
+
+export type HookStatus = {
+  hookPath: string;
+  installed: boolean;
+};
+
+export type LockStatus = {
+  enabled: boolean;
+  locked: boolean;
+  autoLockSecs?: number;
+};
+
+export type AccessSettings = {
+  idleTimeoutSecs?: number;
+  paranoid: boolean;
+};
+
+export type Editor = "system" | "vscode" | "cursor" | "sublime" | "zed";
+
+export type OrderDrift = {
+  path: string;
+  referencePath: string;
+  inOrder: boolean;
+  outOfOrder: string[];
+  onlyInFile: string[];
+  onlyInReference: string[];
+};
+
+export type ReorderResult = {
+  preview: WritePreview;
+  written: boolean;
+};
+
+export type FileWrite = {
+  path: string;
+  content: string;
+  options: WriteOptions;
+};
+
+export type BatchResult = {
+  written: string[];
+};
+
+export type CleanupReport = {
+  removed: string[];
+  failed: string[];
+};
+
+export type PendingWrite = {
+  id: string;
+  path: string;
+  createdAt: number;
+  state: "pending" | "applied" | "diverged" | "lost";
+  preview: WritePreview;
+};
+
+export type FileChange = {
+  path: string;
+  change: "added" | "modified" | "deleted";
+};
+
+export type PaletteKind = "file" | "project" | "key";
+
+export type PaletteScope = "all" | "files" | "projects" | "keys";
+
+export type PaletteMatch = {
+  kind: PaletteKind;
+  label: string;
+  detail: string;
+  path: string;
+  groupId: string;
+  score: number;
+  indices: number[];
+};
+
+export type ValueIndexStatus = {
+  enabled: boolean;
+  fileCount: number;
+  valueCount: number;
+};
+
+export type ValueUsage = {
+  path: string;
+  key: string;
+  line: number;
+  exact: boolean;
+};
+
+export type KeyDefinition = {
+  path: string;
+  groupId: string;
+  project: string;
+  line: number;
+  valueFingerprint: string;
+  empty: boolean;
+};
+
+export type KeyDefinitions = {
+  key: string;
+  definitions: KeyDefinition[];
+  distinctValues: number;
+};
+
+export type ReportFormat = "markdown" | "html";
+
+export type EnvReport = {
+  fileName: string;
+  content: string;
+};
+
+export type ProcessEnvStatus = "same" | "different" | "missing";
+
+export type ProcessEnvEntry = {
+  key: string;
+  status: ProcessEnvStatus;
+  fileValue: string;
+  processValue?: string;
+};
+
+export type ProcessEnvDiff = {
+  source: string;
+  entries: ProcessEnvEntry[];
+};
+
+export type RunOutput = {
+  runId: string;
+  stream: "stdout" | "stderr";
+  line: string;
+};
+
+export type RunExit = {
+  runId: string;
+  code?: number;
+};
+
+export type EnvTemplate = {
+  id: string;
+  name: string;
+  description?: string;
+  content: string;
+  keys: string[];
+  createdAt: number;
+};
+
+export type TemplateInstance = {
+  path: string;
+  created: boolean;
+  added: string[];
+  written: boolean;
+};
+
+export type SnippetParam = {
+  name: string;
+  defaultValue: string;
+};
+
+export type Snippet = {
+  id: string;
+  name: string;
+  params: SnippetParam[];
+  keys: string[];
+};
+
+export type SnippetInsert = {
+  path: string;
+  added: string[];
+  skipped: string[];
+};
+
+export type LogLevel = "error" | "warn" | "info" | "debug" | "trace";
+
+export type LogSettings = {
+  level: LogLevel;
+};
+
+export type RecentLogs = {
+  dir: string;
+  lines: string[];
+};
+
+export type BackupUsage = {
+  fileCount: number;
+  versionCount: number;
+  storedBytes: number;
+};
+
+export type CacheSizes = {
+  paletteEntries: number;
+  valueIndex: ValueIndexStatus;
+  dialectSettings: number;
+  scanSnapshotFiles: number;
+};
+
+export type DiagnosticsReport = {
+  appVersion: string;
+  rootPath?: string;
+  singleFiles: string[];
+  allowedFiles: number;
+  caches: CacheSizes;
+  watchedFiles: number;
+  autoBackupRunning: boolean;
+  backups?: BackupUsage;
+  lastScanMs?: number;
+};
+
+export type OpenRequest = {
+  rootPath: string;
+  scan?: ScanResult;
+  error?: string;
+};
+
+export type DropPolicy = {
+  allowNetwork?: boolean;
+};
+
+export type DropTarget = {
+  rootPath: string;
+  network: boolean;
+  entries: number;
+  envFiles: number;
+  truncated: boolean;
+};
+
+export type WriteCheck = {
+  path: string;
+  canWrite: boolean;
+  reason?: string;
+  exists: boolean;
+  readOnly: boolean;
+  requiredBytes: number;
+  availableBytes?: number;
+};